@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS prevent_update_on_unavailable_timeslot ON timeslots;
+DROP FUNCTION IF EXISTS check_timeslot_availability();
+DROP TABLE IF EXISTS timeslots;
@@ -1,4 +1,4 @@
-CREATE TABLE timeslots (
+CREATE TABLE IF NOT EXISTS timeslots (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),  -- Auto-generate ID
     datetime TIMESTAMPTZ NOT NULL,
     available BOOLEAN NOT NULL DEFAULT true,
@@ -21,6 +21,7 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+DROP TRIGGER IF EXISTS prevent_update_on_unavailable_timeslot ON timeslots;
 CREATE TRIGGER prevent_update_on_unavailable_timeslot
 BEFORE UPDATE ON timeslots
 FOR EACH ROW
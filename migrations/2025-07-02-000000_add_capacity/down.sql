@@ -0,0 +1,18 @@
+CREATE OR REPLACE FUNCTION check_timeslot_availability()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.available = false THEN
+        RAISE EXCEPTION 'Timeslot not available.';
+    END IF;
+
+    IF OLD.datetime < NOW() THEN
+        RAISE EXCEPTION 'Cannot book outdated timeslot (time has passed).';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE timeslots
+    DROP COLUMN IF EXISTS booked_count,
+    DROP COLUMN IF EXISTS capacity;
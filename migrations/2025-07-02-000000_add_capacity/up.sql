@@ -0,0 +1,26 @@
+ALTER TABLE timeslots
+    ADD COLUMN IF NOT EXISTS capacity INTEGER NOT NULL DEFAULT 1 CHECK (capacity > 0),
+    ADD COLUMN IF NOT EXISTS booked_count INTEGER NOT NULL DEFAULT 0;
+
+-- The trigger of the initial migration rejects any update of booked or past timeslots
+ALTER TABLE timeslots DISABLE TRIGGER prevent_update_on_unavailable_timeslot;
+UPDATE timeslots SET booked_count = 1 WHERE available = false;
+ALTER TABLE timeslots ENABLE TRIGGER prevent_update_on_unavailable_timeslot;
+
+CREATE OR REPLACE FUNCTION check_timeslot_availability()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Only guard updates which book the timeslot. Cancellations reset the booked count.
+    IF NEW.booked_count > OLD.booked_count THEN
+        IF OLD.available = false OR OLD.booked_count >= OLD.capacity THEN
+            RAISE EXCEPTION 'Timeslot is fully booked.';
+        END IF;
+
+        IF OLD.datetime < NOW() THEN
+            RAISE EXCEPTION 'Cannot book outdated timeslot (time has passed).';
+        END IF;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
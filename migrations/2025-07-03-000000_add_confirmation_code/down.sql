@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS timeslots_confirmation_code_idx;
+
+ALTER TABLE timeslots DROP COLUMN IF EXISTS confirmation_code;
@@ -0,0 +1,3 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS confirmation_code VARCHAR;
+
+CREATE UNIQUE INDEX IF NOT EXISTS timeslots_confirmation_code_idx ON timeslots (confirmation_code);
@@ -0,0 +1 @@
+ALTER TABLE timeslots DROP COLUMN IF EXISTS client_email;
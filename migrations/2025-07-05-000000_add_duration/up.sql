@@ -0,0 +1 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS duration_minutes INTEGER NOT NULL DEFAULT 30 CHECK (duration_minutes > 0);
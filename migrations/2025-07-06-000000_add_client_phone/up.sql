@@ -0,0 +1 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS client_phone VARCHAR;
@@ -0,0 +1 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS archived BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS waitlist TEXT[] NOT NULL DEFAULT '{}';
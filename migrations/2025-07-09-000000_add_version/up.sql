@@ -0,0 +1 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+ALTER TABLE timeslots DROP COLUMN IF EXISTS confirmed;
+ALTER TABLE timeslots DROP COLUMN IF EXISTS booked_at;
@@ -0,0 +1,2 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS booked_at TIMESTAMPTZ;
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS confirmed BOOLEAN NOT NULL DEFAULT FALSE;
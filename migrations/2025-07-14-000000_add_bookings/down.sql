@@ -0,0 +1,26 @@
+ALTER TABLE timeslots
+    ADD COLUMN IF NOT EXISTS confirmation_code VARCHAR,
+    ADD COLUMN IF NOT EXISTS client_email VARCHAR,
+    ADD COLUMN IF NOT EXISTS client_phone VARCHAR,
+    ADD COLUMN IF NOT EXISTS booker_notes VARCHAR;
+
+-- The timeslot keeps the confirmation code of its first booking
+UPDATE timeslots SET
+    confirmation_code = bookings -> 0 ->> 'confirmation_code',
+    client_email = (
+        SELECT string_agg(booking ->> 'client_email', ', ' ORDER BY place)
+        FROM jsonb_array_elements(bookings) WITH ORDINALITY AS elements(booking, place)
+    ),
+    client_phone = (
+        SELECT string_agg(booking ->> 'client_phone', ', ' ORDER BY place)
+        FROM jsonb_array_elements(bookings) WITH ORDINALITY AS elements(booking, place)
+    ),
+    booker_notes = (
+        SELECT string_agg(booking ->> 'booker_notes', ', ' ORDER BY place)
+        FROM jsonb_array_elements(bookings) WITH ORDINALITY AS elements(booking, place)
+    )
+WHERE jsonb_array_length(bookings) > 0;
+
+DROP INDEX IF EXISTS timeslots_bookings_idx;
+ALTER TABLE timeslots DROP COLUMN IF EXISTS bookings;
+CREATE UNIQUE INDEX IF NOT EXISTS timeslots_confirmation_code_idx ON timeslots (confirmation_code);
@@ -0,0 +1,41 @@
+-- Every booked place gets its own confirmation code and contact details instead of sharing
+-- those of the timeslot
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS bookings JSONB NOT NULL DEFAULT '[]';
+
+-- The details of all bookers were joined with ', ', leaving out the bookers without one. They
+-- can therefore only be assigned by their position if every booker left one.
+CREATE FUNCTION pg_temp.booker_detail(details VARCHAR, bookers BIGINT, place BIGINT)
+RETURNS VARCHAR AS $$
+    SELECT CASE WHEN cardinality(string_to_array(details, ', ')) = bookers
+        THEN NULLIF(split_part(details, ', ', place::INTEGER), '')
+    END;
+$$ LANGUAGE SQL;
+
+-- The first booker keeps the confirmation code of the timeslot, the others get new ones
+UPDATE timeslots SET bookings = (
+    SELECT jsonb_agg(jsonb_build_object(
+        'booker_name', booker.name,
+        'confirmation_code', CASE WHEN booker.place = 1 AND confirmation_code IS NOT NULL
+            THEN confirmation_code
+            ELSE translate(upper(substr(md5(random()::TEXT), 1, 8)), '0189', 'WXYZ')
+        END,
+        'client_email', pg_temp.booker_detail(client_email, booker.count, booker.place),
+        'client_phone', pg_temp.booker_detail(client_phone, booker.count, booker.place),
+        'booker_notes', pg_temp.booker_detail(booker_notes, booker.count, booker.place)
+    ) ORDER BY booker.place)
+    FROM (
+        SELECT name, place, count(*) OVER () AS count
+        FROM unnest(string_to_array(booker_name, ', ')) WITH ORDINALITY AS names(name, place)
+    ) AS booker
+)
+WHERE booked_count > 0 AND booker_name <> '';
+
+DROP INDEX IF EXISTS timeslots_confirmation_code_idx;
+ALTER TABLE timeslots
+    DROP COLUMN IF EXISTS confirmation_code,
+    DROP COLUMN IF EXISTS client_email,
+    DROP COLUMN IF EXISTS client_phone,
+    DROP COLUMN IF EXISTS booker_notes;
+
+-- Bookings are looked up by their confirmation code
+CREATE INDEX IF NOT EXISTS timeslots_bookings_idx ON timeslots USING GIN (bookings jsonb_path_ops);
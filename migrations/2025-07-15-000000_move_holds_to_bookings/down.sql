@@ -0,0 +1,18 @@
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS booked_at TIMESTAMPTZ;
+ALTER TABLE timeslots ADD COLUMN IF NOT EXISTS confirmed BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- The timeslot is held since its first booking and only confirmed if all bookings are
+UPDATE timeslots SET
+    booked_at = (
+        SELECT min((booking ->> 'booked_at')::TIMESTAMPTZ)
+        FROM jsonb_array_elements(bookings) AS booking
+    ),
+    confirmed = (
+        SELECT bool_and(COALESCE((booking ->> 'confirmed')::BOOLEAN, FALSE))
+        FROM jsonb_array_elements(bookings) AS booking
+    ),
+    bookings = (
+        SELECT jsonb_agg(booking - 'booked_at' - 'confirmed' ORDER BY place)
+        FROM jsonb_array_elements(bookings) WITH ORDINALITY AS elements(booking, place)
+    )
+WHERE jsonb_array_length(bookings) > 0;
@@ -0,0 +1,13 @@
+-- Every booking is held and confirmed on its own instead of sharing the hold of the timeslot
+UPDATE timeslots SET bookings = (
+    SELECT jsonb_agg(
+        booking || jsonb_build_object('booked_at', booked_at, 'confirmed', confirmed)
+        ORDER BY place
+    )
+    FROM jsonb_array_elements(bookings) WITH ORDINALITY AS elements(booking, place)
+)
+WHERE jsonb_array_length(bookings) > 0;
+
+ALTER TABLE timeslots
+    DROP COLUMN IF EXISTS booked_at,
+    DROP COLUMN IF EXISTS confirmed;
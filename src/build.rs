@@ -0,0 +1,30 @@
+use std::{
+    env,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Provides the commit and the time of the build to `GET /version`. Without a git checkout, e.g.
+// in the Docker build, the commit can be passed via the `GIT_HASH` environment variable.
+fn main() {
+    let git_hash = env::var("GIT_HASH")
+        .ok()
+        .filter(|git_hash| !git_hash.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .map(|git_hash| git_hash.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".into());
+    let build_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    println!("cargo:rustc-env=GIT_HASH={git_hash}");
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={build_timestamp}");
+}
@@ -0,0 +1,243 @@
+use crate::backend::BackendError;
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    pub error: String,
+    pub code: String,
+    /// Validation failures by field path, e.g. "slots[1].notes"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<BTreeMap<String, Vec<FieldError>>>,
+}
+
+/// Error returned by the HTTP handlers. Serialized as `{ "error": "...", "code": "..." }`.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    status: StatusCode,
+    body: ApiErrorBody,
+    /// Seconds until the request may be retried, sent as `Retry-After` header
+    retry_after: Option<u64>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &str, error: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ApiErrorBody {
+                error: error.into(),
+                code: code.into(),
+                fields: None,
+            },
+            retry_after: None,
+        }
+    }
+
+    pub fn validation(errors: &ValidationErrors) -> Self {
+        let mut fields = BTreeMap::new();
+        collect_field_errors("", errors, &mut fields);
+        let mut api_error = Self::new(StatusCode::BAD_REQUEST, "invalid_input", "Invalid input");
+        api_error.body.fields = Some(fields);
+        api_error
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Replaces the human readable error, e.g. with a translation
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.body.error = error.into();
+        self
+    }
+
+    /// Validation failure of a single row of an uploaded file, counted from 1
+    pub fn invalid_row(row: usize, errors: &ValidationErrors) -> Self {
+        Self::validation(errors).in_row(row)
+    }
+
+    /// Prefixes the human readable error with the row of an uploaded file it belongs to
+    pub fn in_row(mut self, row: usize) -> Self {
+        self.body.error = format!("Row {row}: {}", self.body.error);
+        self
+    }
+
+    pub fn bad_request(code: &str, error: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, code, error)
+    }
+
+    pub fn backend(error: BackendError) -> Self {
+        match error {
+            BackendError::NotFound(error) => Self::not_found(error),
+            BackendError::AlreadyBooked(error) => {
+                Self::new(StatusCode::CONFLICT, "already_booked", error)
+            }
+            BackendError::Conflict(error) => Self::conflict(error),
+            BackendError::Other(error) => {
+                Self::new(StatusCode::INTERNAL_SERVER_ERROR, "backend_error", error)
+            }
+        }
+    }
+
+    pub fn not_found(error: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", error)
+    }
+
+    pub fn conflict(error: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, "conflict", error)
+    }
+
+    pub fn unauthorized(error: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "unauthorized", error)
+    }
+
+    pub fn too_many_requests() -> Self {
+        Self::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate_limited",
+            "Too many requests",
+        )
+    }
+
+    /// The client failed to authenticate too often and has to wait for the lockout to end
+    pub fn locked_out(retry_after: u64) -> Self {
+        let mut api_error = Self::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "locked_out",
+            "Too many failed authentications. Please try again later",
+        );
+        api_error.retry_after = Some(retry_after);
+        api_error
+    }
+
+    pub fn unavailable(error: String) -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, "unavailable", error)
+    }
+
+    pub fn overloaded() -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "overloaded",
+            "Too many requests in progress. Please try again later",
+        )
+    }
+
+    pub fn read_only() -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "read_only",
+            "Down for maintenance. Bookings and changes are possible again shortly",
+        )
+    }
+}
+
+fn collect_field_errors(
+    prefix: &str,
+    errors: &ValidationErrors,
+    fields: &mut BTreeMap<String, Vec<FieldError>>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{prefix}.{field}")
+        };
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                fields.insert(
+                    path,
+                    field_errors
+                        .iter()
+                        .map(|field_error| FieldError {
+                            code: field_error.code.to_string(),
+                            message: field_error.message.as_ref().map(ToString::to_string),
+                        })
+                        .collect(),
+                );
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(&path, nested, fields),
+            ValidationErrorsKind::List(list) => {
+                for (index, nested) in list {
+                    collect_field_errors(&format!("{path}[{index}]"), nested, fields);
+                }
+            }
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut response = (self.status, Json(self.body)).into_response();
+        if let Some(retry_after) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, retry_after.into());
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Slot {
+        #[validate(length(min = 1, message = "Notes must not be empty"))]
+        notes: String,
+    }
+
+    #[derive(Validate)]
+    struct Request {
+        #[validate(range(min = 1))]
+        count: i32,
+        #[validate(nested)]
+        slots: Vec<Slot>,
+    }
+
+    #[test]
+    fn test_validation_field_paths() {
+        let request = Request {
+            count: 0,
+            slots: vec![
+                Slot {
+                    notes: "Valid".into(),
+                },
+                Slot {
+                    notes: String::new(),
+                },
+            ],
+        };
+        let error = ApiError::validation(&request.validate().unwrap_err());
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.body.code, "invalid_input");
+        let fields = error.body.fields.unwrap();
+        assert_eq!(
+            fields.keys().collect::<Vec<_>>(),
+            vec!["count", "slots[1].notes"]
+        );
+        assert_eq!(fields["count"][0].code, "range");
+        assert_eq!(
+            fields["slots[1].notes"][0],
+            FieldError {
+                code: "length".into(),
+                message: Some("Notes must not be empty".into())
+            }
+        );
+    }
+}
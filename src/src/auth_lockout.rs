@@ -0,0 +1,238 @@
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Forget clients which are neither locked out nor failed within the window once this many are
+// tracked
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+// Wait suggested to clients whose attempts being verified could already lock them out
+const PENDING_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default)]
+struct FailedAttempts {
+    /// Failures within the window, oldest first
+    failures: VecDeque<Instant>,
+    /// Attempts which are still being verified. They count towards the maximum, so concurrent
+    /// guesses can't exceed it.
+    pending: usize,
+    locked_until: Option<Instant>,
+}
+
+/// Locks a client IP out of the admin routes after too many failed authentications within a
+/// window, so passwords can't be guessed by trying.
+#[derive(Clone)]
+pub struct AuthLockout {
+    max_failures: u32,
+    window: Duration,
+    lockout: Duration,
+    clients: Arc<Mutex<HashMap<IpAddr, FailedAttempts>>>,
+}
+
+impl AuthLockout {
+    /// A maximum of 0 failures disables the lockout.
+    pub fn new(max_failures: u32, window: Duration, lockout: Duration) -> Self {
+        Self {
+            max_failures,
+            window,
+            lockout,
+            clients: Arc::default(),
+        }
+    }
+
+    /// Reserves an authentication attempt of the client before its credentials are verified.
+    /// Fails with the time to wait if the client is locked out, or if the attempts already being
+    /// verified could lock it out.
+    pub fn begin_attempt(&self, client_ip: IpAddr) -> Result<AuthAttempt, Duration> {
+        let attempt = AuthAttempt {
+            auth_lockout: self.clone(),
+            client_ip,
+            finished: false,
+        };
+        if self.max_failures == 0 {
+            return Ok(attempt);
+        }
+
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap();
+        if clients.len() >= MAX_TRACKED_CLIENTS {
+            clients.retain(|_, attempts| {
+                attempts.pending > 0
+                    || attempts
+                        .locked_until
+                        .is_some_and(|locked_until| now < locked_until)
+                    || attempts
+                        .failures
+                        .back()
+                        .is_some_and(|latest| now.duration_since(*latest) < self.window)
+            });
+        }
+
+        let attempts = clients.entry(client_ip).or_default();
+        if let Some(remaining) = attempts
+            .locked_until
+            .and_then(|locked_until| locked_until.checked_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+        {
+            return Err(remaining);
+        }
+        self.forget_outdated(attempts, now);
+        if attempts.failures.len() + attempts.pending >= self.max_failures as usize {
+            return Err(PENDING_RETRY_DELAY);
+        }
+        attempts.pending += 1;
+        Ok(attempt)
+    }
+
+    fn finish_attempt(&self, client_ip: IpAddr, success: bool) -> bool {
+        if self.max_failures == 0 {
+            return false;
+        }
+
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap();
+        let attempts = clients.entry(client_ip).or_default();
+        attempts.pending = attempts.pending.saturating_sub(1);
+        if success {
+            attempts.failures.clear();
+            attempts.locked_until = None;
+            if attempts.pending == 0 {
+                clients.remove(&client_ip);
+            }
+            return false;
+        }
+
+        self.forget_outdated(attempts, now);
+        attempts.failures.push_back(now);
+        if attempts.failures.len() < self.max_failures as usize {
+            return false;
+        }
+        attempts.failures.clear();
+        attempts.locked_until = Some(now + self.lockout);
+        true
+    }
+
+    fn forget_outdated(&self, attempts: &mut FailedAttempts, now: Instant) {
+        while attempts
+            .failures
+            .front()
+            .is_some_and(|oldest| now.duration_since(*oldest) >= self.window)
+        {
+            attempts.failures.pop_front();
+        }
+    }
+}
+
+/// An authentication attempt reserved by `AuthLockout::begin_attempt`. It counts as failed
+/// unless it succeeds, also if the request is aborted during the verification.
+pub struct AuthAttempt {
+    auth_lockout: AuthLockout,
+    client_ip: IpAddr,
+    finished: bool,
+}
+
+impl AuthAttempt {
+    /// Records the failure and returns whether the client is locked out now.
+    pub fn fail(mut self) -> bool {
+        self.finished = true;
+        self.auth_lockout.finish_attempt(self.client_ip, false)
+    }
+
+    /// Forgets the failed authentications of the client.
+    pub fn succeed(mut self) {
+        self.finished = true;
+        self.auth_lockout.finish_attempt(self.client_ip, true);
+    }
+}
+
+impl Drop for AuthAttempt {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.auth_lockout.finish_attempt(self.client_ip, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    const FIRST_CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+    const SECOND_CLIENT: IpAddr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+    fn fail(auth_lockout: &AuthLockout, client_ip: IpAddr) -> bool {
+        auth_lockout.begin_attempt(client_ip).unwrap().fail()
+    }
+
+    #[test]
+    fn test_lockout_per_client() {
+        let auth_lockout = AuthLockout::new(3, Duration::from_secs(60), Duration::from_secs(60));
+
+        assert!(!fail(&auth_lockout, FIRST_CLIENT));
+        assert!(!fail(&auth_lockout, FIRST_CLIENT));
+        assert!(fail(&auth_lockout, FIRST_CLIENT));
+
+        let remaining = auth_lockout.begin_attempt(FIRST_CLIENT).err().unwrap();
+        assert!(remaining <= Duration::from_secs(60));
+        assert!(auth_lockout.begin_attempt(SECOND_CLIENT).is_ok());
+    }
+
+    #[test]
+    fn test_lockout_expiry() {
+        let auth_lockout = AuthLockout::new(1, Duration::from_secs(60), Duration::from_millis(20));
+
+        assert!(fail(&auth_lockout, FIRST_CLIENT));
+        assert!(auth_lockout.begin_attempt(FIRST_CLIENT).is_err());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(auth_lockout.begin_attempt(FIRST_CLIENT).is_ok());
+    }
+
+    #[test]
+    fn test_failures_outside_window() {
+        let auth_lockout = AuthLockout::new(2, Duration::from_millis(20), Duration::from_secs(60));
+
+        assert!(!fail(&auth_lockout, FIRST_CLIENT));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!fail(&auth_lockout, FIRST_CLIENT));
+        assert!(auth_lockout.begin_attempt(FIRST_CLIENT).is_ok());
+    }
+
+    #[test]
+    fn test_success_clears_failures() {
+        let auth_lockout = AuthLockout::new(2, Duration::from_secs(60), Duration::from_secs(60));
+
+        assert!(!fail(&auth_lockout, FIRST_CLIENT));
+        auth_lockout.begin_attempt(FIRST_CLIENT).unwrap().succeed();
+        assert!(!fail(&auth_lockout, FIRST_CLIENT));
+        assert!(auth_lockout.begin_attempt(FIRST_CLIENT).is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_attempts() {
+        let auth_lockout = AuthLockout::new(3, Duration::from_secs(60), Duration::from_secs(60));
+
+        // Attempts being verified count, so no more than the maximum can be guessed at once
+        let attempts: Vec<AuthAttempt> = (0..3)
+            .map(|_| auth_lockout.begin_attempt(FIRST_CLIENT).unwrap())
+            .collect();
+        assert_eq!(
+            auth_lockout.begin_attempt(FIRST_CLIENT).err(),
+            Some(PENDING_RETRY_DELAY)
+        );
+        assert!(auth_lockout.begin_attempt(SECOND_CLIENT).is_ok());
+
+        // Aborted attempts count as failed
+        drop(attempts);
+        let remaining = auth_lockout.begin_attempt(FIRST_CLIENT).err().unwrap();
+        assert!(remaining > PENDING_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_disabled_lockout() {
+        let auth_lockout = AuthLockout::new(0, Duration::from_secs(60), Duration::from_secs(60));
+
+        assert!((0..100).all(|_| !fail(&auth_lockout, FIRST_CLIENT)));
+        assert!(auth_lockout.begin_attempt(FIRST_CLIENT).is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use std::fmt;
+use std::str::FromStr;
+use subtle::ConstantTimeEq;
+use tracing::error;
+use uuid::Uuid;
+
+/// A password, hash or token which must not end up in the logs. `Debug` and `Display` print `***`,
+/// the value itself is only available via `expose`.
+#[derive(Clone)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("***")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("***")
+    }
+}
+
+/// Admin password as provided by the configuration.
+#[derive(Clone, Debug)]
+pub enum AdminPassword {
+    Plaintext(Secret),
+    /// Argon2 hash in PHC string format, e.g. "$argon2id$v=19$m=19456,t=2,p=1$...".
+    Hash(Secret),
+}
+
+impl AdminPassword {
+    pub fn verify(&self, provided: &Secret) -> bool {
+        let provided = provided.expose();
+        match self {
+            AdminPassword::Plaintext(password) => constant_time_eq(provided, password.expose()),
+            AdminPassword::Hash(hash) => match PasswordHash::new(hash.expose()) {
+                Ok(hash) => verify_password(provided, &hash),
+                Err(err) => {
+                    error!(?err, "Invalid admin password hash");
+                    false
+                }
+            },
+        }
+    }
+}
+
+/// Name of the admin which authenticates with the configured password
+pub const DEFAULT_ADMIN_NAME: &str = "admin";
+
+/// Additional admin with its own password, so the logs show who performed an action.
+#[derive(Clone, Debug)]
+pub struct Admin {
+    pub name: String,
+    pub password: AdminPassword,
+}
+
+impl FromStr for Admin {
+    type Err = String;
+
+    /// Parses "name:password". Passwords starting with "$argon2" are treated as argon2 hashes.
+    fn from_str(admin: &str) -> Result<Self, Self::Err> {
+        // The password itself must not end up in the error message
+        let (name, password) = admin
+            .split_once(':')
+            .ok_or("Invalid admin. Expected \"name:password\"")?;
+        let name = name.trim();
+        if name.is_empty() || password.is_empty() {
+            return Err("Invalid admin. Name and password must not be empty".into());
+        }
+        let password = if password.starts_with("$argon2") {
+            PasswordHash::new(password)
+                .map_err(|_| format!("Invalid password hash of admin \"{name}\""))?;
+            AdminPassword::Hash(password.into())
+        } else {
+            AdminPassword::Plaintext(password.into())
+        };
+        Ok(Self {
+            name: name.to_string(),
+            password,
+        })
+    }
+}
+
+/// Generates a random key of 64 hex characters, e.g. to replace the admin password.
+pub fn generate_key() -> String {
+    // Version 4 UUIDs are generated by a cryptographically secure random number generator
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// Compares both strings without short-circuiting on the first mismatching byte. Only the
+/// length of the expected string can be derived from the response time.
+pub fn constant_time_eq(provided: &str, expected: &str) -> bool {
+    provided.as_bytes().ct_eq(expected.as_bytes()).into()
+}
+
+/// Verifies the provided password against an argon2 hash. The hash comparison is constant-time.
+pub fn verify_password(provided: &str, stored: &PasswordHash) -> bool {
+    Argon2::default()
+        .verify_password(provided.as_bytes(), stored)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use argon2::password_hash::SaltString;
+    use argon2::PasswordHasher;
+
+    #[test]
+    fn test_generate_key() {
+        let key = generate_key();
+        assert_eq!(key.len(), 64);
+        assert!(key.chars().all(|character| character.is_ascii_hexdigit()));
+        assert_ne!(key, generate_key());
+    }
+
+    fn hash(password: &str) -> String {
+        let salt = SaltString::from_b64("c29tZXNhbHRzb21lc2FsdA").unwrap();
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test_case::test_case("password", "password", true)]
+    #[test_case::test_case("password", "passwort", false)]
+    #[test_case::test_case("Password", "password", false)]
+    #[test_case::test_case("pass", "password", false)]
+    #[test_case::test_case("password123", "password", false)]
+    #[test_case::test_case("", "password", false)]
+    #[test_case::test_case("", "", true)]
+    fn test_constant_time_eq(provided: &str, expected: &str, equal: bool) {
+        assert_eq!(constant_time_eq(provided, expected), equal);
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let stored = hash("password");
+        let stored = PasswordHash::new(&stored).unwrap();
+
+        assert!(verify_password("password", &stored));
+        assert!(!verify_password("Password", &stored));
+        assert!(!verify_password("password ", &stored));
+        assert!(!verify_password("", &stored));
+    }
+
+    #[test_case::test_case("alice:secret", "alice", "secret" ; "plaintext")]
+    #[test_case::test_case(" bob :pass:word", "bob", "pass:word" ; "colon in password")]
+    fn test_parse_admin(admin: &str, name: &str, password: &str) {
+        let admin = Admin::from_str(admin).unwrap();
+        assert_eq!(admin.name, name);
+        assert!(matches!(admin.password, AdminPassword::Plaintext(_)));
+        assert!(admin.password.verify(&password.into()));
+    }
+
+    #[test]
+    fn test_parse_admin_with_hash() {
+        let admin = Admin::from_str(&format!("alice:{}", hash("secret"))).unwrap();
+        assert!(matches!(admin.password, AdminPassword::Hash(_)));
+        assert!(admin.password.verify(&"secret".into()));
+    }
+
+    #[test_case::test_case("alice" ; "missing password")]
+    #[test_case::test_case(":secret" ; "empty name")]
+    #[test_case::test_case("alice:" ; "empty password")]
+    #[test_case::test_case("alice:$argon2id$v=19$m=19456,t=2,p=1$secret!$secret!" ; "invalid hash")]
+    fn test_parse_invalid_admin(admin: &str) {
+        let err = Admin::from_str(admin).unwrap_err();
+        assert!(!err.contains("secret"));
+    }
+
+    #[test_case::test_case (AdminPassword::Plaintext("password".into()), "password", true)]
+    #[test_case::test_case (AdminPassword::Plaintext("password".into()), "passwor", false)]
+    #[test_case::test_case(AdminPassword::Hash(hash("password").into()), "password", true)]
+    #[test_case::test_case(AdminPassword::Hash(hash("password").into()), "passwort", false)]
+    #[test_case::test_case (AdminPassword::Hash("password".into()), "password", false ; "invalid hash")]
+    fn test_admin_password_verify(admin_password: AdminPassword, provided: &str, expected: bool) {
+        assert_eq!(admin_password.verify(&provided.into()), expected);
+    }
+
+    #[test]
+    fn test_secret_redacted() {
+        let secret = Secret::from("password");
+        assert_eq!(format!("{secret:?}"), "***");
+        assert_eq!(secret.to_string(), "***");
+        assert_eq!(secret.expose(), "password");
+
+        let admin = Admin::from_str("alice:password").unwrap();
+        let debug = format!("{admin:?}");
+        assert!(debug.contains("alice"));
+        assert!(!debug.contains("password\""));
+        assert!(debug.contains("***"));
+    }
+}
@@ -1,12 +1,297 @@
-use crate::types::Timeslot;
+use crate::types::{Timeslot, TimeslotDraft, TimeslotEvent, TimeslotEventKind};
 use chrono::{DateTime, Utc};
-use tokio_stream::wrappers::WatchStream;
+use std::collections::HashMap;
+use std::fmt;
+use tokio::sync::{broadcast, watch};
+use tokio_stream::wrappers::{BroadcastStream, WatchStream};
+use tracing::{error, Span};
 use uuid::Uuid;
 
+pub const TIMESLOT_LIMIT_REACHED: &str = "Timeslot limit reached";
+pub const NOTHING_TO_RESTORE: &str = "Nothing to restore";
+pub const TIMESLOT_AVAILABLE: &str = "Timeslot is still available";
+pub const VERSION_CONFLICT: &str = "Timeslot was changed in the meantime";
+pub const NO_TIMESLOT_AVAILABLE: &str = "No timeslot available in the time window";
+pub const SWAP_WITH_ITSELF: &str = "A timeslot can't swap bookings with itself";
+
+// Events a slow event stream may fall behind before it misses some
+const EVENT_CAPACITY: usize = 256;
+
+/// Error of a backend call. The HTTP layer picks the status code by its kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendError {
+    /// The timeslot does not exist (anymore)
+    NotFound(String),
+    /// The timeslot has no free place left
+    AlreadyBooked(String),
+    /// The change doesn't fit the current state of the timeslots, e.g. bookings exceeding the
+    /// capacity of another timeslot
+    Conflict(String),
+    Other(String),
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(message)
+            | Self::AlreadyBooked(message)
+            | Self::Conflict(message)
+            | Self::Other(message) => formatter.write_str(message),
+        }
+    }
+}
+
+impl From<String> for BackendError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for BackendError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.into())
+    }
+}
+
+/// Whether `additional` timeslots may be added next to the `current` ones.
+pub fn within_timeslot_limit(
+    max_timeslots: Option<usize>,
+    current: usize,
+    additional: usize,
+) -> bool {
+    max_timeslots.is_none_or(|max_timeslots| current + additional <= max_timeslots)
+}
+
+/// Runs a backend call on the blocking thread pool. The backends are synchronous (e.g. a database
+/// query waits for the database), so calling them directly would stall a worker thread of the
+/// async runtime and with it all requests scheduled on it.
+pub async fn run_blocking<T, R, F>(backend: &T, call: F) -> Result<R, BackendError>
+where
+    T: TimeslotBackend,
+    R: Send + 'static,
+    F: FnOnce(&T) -> Result<R, BackendError> + Send + 'static,
+{
+    let backend = backend.clone();
+    // Keeps the request id in the log lines of the backend
+    let span = Span::current();
+    tokio::task::spawn_blocking(move || span.in_scope(|| call(&backend)))
+        .await
+        .unwrap_or_else(|err| {
+            error!(?err, "Backend call panicked");
+            Err("Backend call failed".into())
+        })
+}
+
+/// Channel of the event streams, see `TimeslotBackend::event_stream`.
+pub fn event_channel() -> broadcast::Sender<TimeslotEvent> {
+    broadcast::channel(EVENT_CAPACITY).0
+}
+
+/// Publishes the timeslots to the timeslot streams. Receivers are only notified if they differ from
+/// the last published ones, so an operation which changed nothing doesn't cause a redundant event.
+/// New streams receive the current timeslots regardless.
+///
+/// The differences to the last published timeslots are published to the event streams as well.
+pub fn publish_timeslots(
+    sender: &watch::Sender<Vec<Timeslot>>,
+    event_sender: &broadcast::Sender<TimeslotEvent>,
+    timeslots: Vec<Timeslot>,
+) {
+    let mut events = vec![];
+    sender.send_if_modified(|published| {
+        if *published == timeslots {
+            return false;
+        }
+        if event_sender.receiver_count() > 0 {
+            events = timeslot_events(published, &timeslots);
+        }
+        *published = timeslots;
+        true
+    });
+    for event in events {
+        // Fails only if the last event stream closed in the meantime
+        let _ = event_sender.send(event);
+    }
+}
+
+/// The events which lead from the previous to the current timeslots. A booking handed to the
+/// first client of the waitlist keeps the number of bookings, but changes the confirmation codes.
+fn timeslot_events(previous: &[Timeslot], current: &[Timeslot]) -> Vec<TimeslotEvent> {
+    let event = |kind, timeslot: &Timeslot| TimeslotEvent {
+        kind,
+        timeslot: timeslot.clone(),
+    };
+    let mut previous: HashMap<_, _> = previous
+        .iter()
+        .map(|timeslot| (timeslot.id, timeslot))
+        .collect();
+
+    let mut events = vec![];
+    for timeslot in current {
+        let Some(before) = previous.remove(&timeslot.id) else {
+            events.push(event(TimeslotEventKind::Added, timeslot));
+            continue;
+        };
+        if timeslot.booked_count > before.booked_count {
+            events.push(event(TimeslotEventKind::Booked, timeslot));
+        } else if timeslot.booked_count < before.booked_count {
+            events.push(event(TimeslotEventKind::Cancelled, timeslot));
+        } else if timeslot.booked_count > 0
+            && timeslot
+                .bookings
+                .iter()
+                .map(|booking| &booking.confirmation_code)
+                .ne(before
+                    .bookings
+                    .iter()
+                    .map(|booking| &booking.confirmation_code))
+        {
+            events.push(event(TimeslotEventKind::Cancelled, before));
+            events.push(event(TimeslotEventKind::Booked, timeslot));
+        }
+    }
+    let mut removed: Vec<_> = previous.into_values().collect();
+    removed.sort_unstable_by_key(|timeslot| timeslot.datetime);
+    events.extend(
+        removed
+            .into_iter()
+            .map(|timeslot| event(TimeslotEventKind::Removed, timeslot)),
+    );
+    events
+}
+
+/// Whether any of the drafts overlaps with another one of them.
+pub fn drafts_overlap(drafts: &[TimeslotDraft]) -> bool {
+    drafts.iter().enumerate().any(|(index, draft)| {
+        drafts[..index]
+            .iter()
+            .any(|other| other.datetime < draft.end() && draft.datetime < other.end())
+    })
+}
+
+/// Storage of the timeslots. Except for `timeslot_stream`, the methods may block (e.g. on a
+/// database query), so async code calls them via `run_blocking`.
 pub trait TimeslotBackend: Clone + Send + Sync + 'static {
     fn timeslot_stream(&self) -> WatchStream<Vec<Timeslot>>;
-    fn book_timeslot(&self, id: Uuid, booker_name: String) -> Result<(), String>;
-    fn add_timeslot(&self, datetime: DateTime<Utc>, notes: String) -> Result<(), String>;
-    fn remove_timeslot(&self, id: Uuid) -> Result<(), String>;
-    fn remove_all_timeslot(&self) -> Result<(), String>;
+    /// Stream of the changes of single timeslots (bookings, cancellations, additions and
+    /// removals), unlike `timeslot_stream` without the unchanged timeslots.
+    fn event_stream(&self) -> BroadcastStream<TimeslotEvent>;
+    fn current_timeslots(&self) -> Result<Vec<Timeslot>, BackendError>;
+    /// The current timeslots starting from `from` to `to` (both inclusive), sorted by datetime.
+    fn timeslots_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Timeslot>, BackendError>;
+    /// Number of the current timeslots, optionally only of those which can still be booked.
+    fn count_timeslots(&self, available_only: bool) -> Result<i64, BackendError>;
+    /// Books a place of the timeslot and returns the booked timeslot, whose confirmation code
+    /// identifies the booking.
+    fn book_timeslot(
+        &self,
+        id: Uuid,
+        booker_name: String,
+        client_email: Option<String>,
+        client_phone: Option<String>,
+        booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError>;
+    /// Books a place of the earliest timeslot from `from` to `to` (both inclusive) which can still
+    /// be booked and hasn't started yet. Fails with `NotFound` if there is none. The timeslot is
+    /// picked and booked atomically, so concurrent calls never pick the same last place.
+    fn book_first_available(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        booker_name: String,
+    ) -> Result<Timeslot, BackendError>;
+    /// Number of places booked under the client name in the current timeslots, see
+    /// `Timeslot::bookings_of`.
+    fn count_bookings(&self, client_name: &str) -> Result<usize, BackendError>;
+    /// Removes the timeslots which ended longer than the cleanup retention ago and returns how
+    /// many were removed. Runs periodically, see `cleanup::remove_outdated_timeslots`.
+    fn remove_outdated_timeslots(&self) -> Result<usize, BackendError>;
+    /// Current booked timeslots sorted by start, with a booker whose name contains the search
+    /// term. Case is ignored, see `Timeslot::booker_name_contains`.
+    fn search_bookings(&self, search: &str) -> Result<Vec<Timeslot>, BackendError>;
+    /// Cancels all bookings of the timeslot. If clients are on the waitlist, they are booked
+    /// instead and the confirmation codes of these bookings are returned, see
+    /// `Timeslot::cancel_booking`. Fails with `NotFound` if the timeslot doesn't exist and with
+    /// `Conflict` if it is not booked.
+    fn cancel_booking(&self, id: Uuid) -> Result<Vec<String>, BackendError>;
+    /// Cancels only the booking with the confirmation code, see `Timeslot::cancel_booking_of`.
+    /// Fails with `NotFound` if no booking has the code.
+    fn cancel_own_booking(&self, confirmation_code: &str) -> Result<Vec<String>, BackendError>;
+    /// Exchanges the bookings of both timeslots in one step, see `Timeslot::swap_bookings`. Fails
+    /// with `NotFound` if either of them doesn't exist and with `Conflict` if the bookings don't
+    /// fit or the ids are the same.
+    fn swap_bookings(&self, first_id: Uuid, second_id: Uuid) -> Result<(), BackendError>;
+    /// Confirms the current bookings of the timeslot, so their holds don't expire, see
+    /// `Timeslot::confirm_bookings`. Fails with `NotFound` if the timeslot doesn't exist and with
+    /// `Conflict` if it is not booked.
+    fn confirm_booking(&self, id: Uuid) -> Result<(), BackendError>;
+    /// Releases the places of the bookings which are unconfirmed since before `cutoff`, see
+    /// `Timeslot::release_expired_holds`, and returns how many were released.
+    fn release_expired_holds(&self, cutoff: DateTime<Utc>) -> Result<usize, BackendError>;
+    /// Appends the client to the waitlist of a fully booked timeslot and returns the position on
+    /// the waitlist, starting at 1.
+    fn join_waitlist(&self, id: Uuid, client_name: String) -> Result<usize, BackendError>;
+    fn add_timeslot(
+        &self,
+        datetime: DateTime<Utc>,
+        notes: String,
+        capacity: i32,
+        duration_minutes: i32,
+        internal_notes: Option<String>,
+        category: Option<String>,
+    ) -> Result<(), BackendError>;
+    /// Adds all timeslots and returns how many were added. Backends which support transactions
+    /// should override this to add either all or none of them.
+    fn add_timeslots_bulk(&self, drafts: Vec<TimeslotDraft>) -> Result<usize, BackendError> {
+        let count = drafts.len();
+        for draft in drafts {
+            self.add_timeslot(
+                draft.datetime,
+                draft.notes,
+                draft.capacity,
+                draft.duration_minutes,
+                draft.internal_notes,
+                draft.category,
+            )?;
+        }
+        Ok(count)
+    }
+    /// Runs the checks of `add_timeslots_bulk` (timeslot limit and overlaps) without adding
+    /// anything, e.g. to preview a schedule.
+    fn check_timeslots_bulk(&self, drafts: &[TimeslotDraft]) -> Result<(), BackendError>;
+    /// Only edits the timeslot if `version` matches its current version, which is incremented
+    /// then. Otherwise fails with `Conflict`, just like if the new start overlaps with another
+    /// timeslot.
+    fn edit_timeslot(
+        &self,
+        id: Uuid,
+        datetime: DateTime<Utc>,
+        notes: String,
+        internal_notes: Option<String>,
+        version: i32,
+    ) -> Result<(), BackendError>;
+    /// Replaces only the notes of the timeslot, so its start can't be changed by accident. Like
+    /// an edit, it increments the version, so edits based on the previous notes are rejected.
+    fn update_notes(&self, id: Uuid, notes: String) -> Result<(), BackendError>;
+    /// Moves a booked timeslot to another start, keeping its bookings. Fails if the timeslot
+    /// would overlap with another one (unless overlaps are allowed).
+    fn reschedule(&self, id: Uuid, datetime: DateTime<Utc>) -> Result<(), BackendError>;
+    /// Archives the timeslot, so it disappears from the timeslot stream but is still listed by
+    /// `archived_timeslots`. Backends configured for hard delete delete it instead.
+    fn remove_timeslot(&self, id: Uuid) -> Result<(), BackendError>;
+    fn archived_timeslots(&self) -> Result<Vec<Timeslot>, BackendError>;
+    /// Removes all timeslots. Unless there were none, they are kept until the next call, so the
+    /// removal can be undone with `restore_last`.
+    fn remove_all_timeslot(&self) -> Result<(), BackendError>;
+    /// Adds the timeslots removed by the most recent `remove_all_timeslot` again. Each removal can
+    /// only be restored once, afterwards it fails with `Conflict`.
+    fn restore_last(&self) -> Result<(), BackendError>;
+    /// The timeslot with the id, unless it doesn't exist or is archived.
+    fn find_timeslot(&self, id: Uuid) -> Result<Option<Timeslot>, BackendError>;
+    fn find_by_confirmation(&self, code: &str) -> Result<Option<Timeslot>, BackendError>;
+    fn health_check(&self) -> Result<(), BackendError>;
 }
@@ -0,0 +1,122 @@
+use crate::types::Timeslot;
+use chrono::{DateTime, Utc};
+
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Serializes the timeslots into an iCalendar (RFC 5545) feed with one event per timeslot.
+pub fn to_icalendar(timeslots: &[Timeslot]) -> String {
+    let now = format_datetime(Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//booking-manager//Timeslot Booking Manager//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for timeslot in timeslots {
+        lines.push("BEGIN:VEVENT".into());
+        lines.push(format!("UID:{}@booking-manager", timeslot.id));
+        lines.push(format!("DTSTAMP:{now}"));
+        lines.push(format!("DTSTART:{}", format_datetime(timeslot.datetime)));
+        lines.push(format!("DTEND:{}", format_datetime(timeslot.end())));
+        lines.push(format!("SUMMARY:{}", escape_text(&timeslot.notes)));
+        if timeslot.booked_count > 0 {
+            lines.push(format!(
+                "DESCRIPTION:{}",
+                escape_text(&format!("Booked by {}", timeslot.booker_name))
+            ));
+        }
+        lines.push("END:VEVENT".into());
+    }
+    lines.push("END:VCALENDAR".into());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn format_datetime(datetime: DateTime<Utc>) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than 75 octets into continuation lines starting with a space.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_length = 0;
+    for character in line.chars() {
+        if line_length + character.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+        folded.push(character);
+        line_length += character.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn timeslot(notes: &str, booker_name: &str, booked_count: i32) -> Timeslot {
+        Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc.with_ymd_and_hms(2025, 7, 1, 9, 30, 0).unwrap(),
+            available: booked_count == 0,
+            booker_name: booker_name.into(),
+            notes: notes.into(),
+            capacity: 1,
+            booked_count,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_to_icalendar() {
+        let timeslots = vec![
+            timeslot("Workshop; Rust, part 1", "", 0),
+            timeslot("Workshop", "Stefan", 1),
+        ];
+        let calendar = to_icalendar(&timeslots);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+        assert_eq!(calendar.matches("END:VEVENT").count(), 2);
+        assert!(calendar.contains("DTSTART:20250701T093000Z\r\nDTEND:20250701T100000Z\r\n"));
+        assert!(calendar.contains("SUMMARY:Workshop\\; Rust\\, part 1\r\n"));
+        assert!(calendar.contains("DESCRIPTION:Booked by Stefan\r\n"));
+        assert_eq!(calendar.matches("DESCRIPTION").count(), 1);
+        assert!(calendar.contains(&format!("UID:{}@booking-manager", timeslots[0].id)));
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("SUMMARY:{}", "ä".repeat(50));
+        let folded = fold_line(&line);
+
+        assert!(folded
+            .split("\r\n")
+            .all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+        assert_eq!(fold_line("SUMMARY:short"), "SUMMARY:short");
+    }
+}
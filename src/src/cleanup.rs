@@ -0,0 +1,68 @@
+use crate::backend::{run_blocking, TimeslotBackend};
+use tracing::{error, info};
+
+/// Periodically removes the outdated timeslots, so they are neither kept on a quiet server nor
+/// removed on every read of a busy one. Runs until the task is dropped, e.g. next to the server.
+pub async fn remove_outdated_timeslots<T: TimeslotBackend>(
+    backend: T,
+    cleanup_interval: std::time::Duration,
+) {
+    let mut interval = tokio::time::interval(cleanup_interval);
+    loop {
+        interval.tick().await;
+        match run_blocking(&backend, |backend| backend.remove_outdated_timeslots()).await {
+            Ok(0) => {}
+            Ok(removed) => info!(removed, "Removed outdated timeslots"),
+            Err(err) => error!(?err, "Failed to remove outdated timeslots"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{local_timeslots::LocalTimeslots, testutils::read_from_timeslot_stream};
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_remove_outdated_timeslots() {
+        let backend = LocalTimeslots::new(Duration::hours(1), true, None, false);
+        backend
+            .add_timeslot(
+                Utc::now() - Duration::hours(2),
+                "Stale".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        backend
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Upcoming".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let mut timeslot_stream = backend.timeslot_stream();
+        assert_eq!(
+            read_from_timeslot_stream(&mut timeslot_stream).await.len(),
+            2
+        );
+
+        let task = tokio::spawn(remove_outdated_timeslots(
+            backend.clone(),
+            std::time::Duration::from_millis(50),
+        ));
+
+        // Pushed by the task without any read
+        let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].notes, "Upcoming");
+
+        task.abort();
+    }
+}
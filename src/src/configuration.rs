@@ -1,9 +1,303 @@
+use crate::authentication::{Admin, AdminPassword};
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Weekday};
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::Level;
+
+pub const SQLITE_URL_PREFIX: &str = "sqlite://";
+pub const FILE_URL_PREFIX: &str = "file://";
 
 pub trait Configuration: Clone + Send + Sync + 'static {
     fn website_title(&self) -> String;
-    fn password(&self) -> String;
+    fn name_max_len(&self) -> usize;
+    fn name_pattern(&self) -> Option<String>;
+    fn notes_max_len(&self) -> usize;
+    fn notes_pattern(&self) -> Option<String>;
+    /// Notes of timeslots added with empty notes
+    fn default_notes(&self) -> String;
+    fn display_timezone(&self) -> String;
+    /// Message sent with the snapshot if there are no timeslots, e.g. "No slots yet"
+    fn empty_schedule_message(&self) -> Option<String>;
+    /// Window new timeslots must start in, based on the display timezone. Any start is accepted
+    /// if not set
+    fn business_hours(&self) -> Option<BusinessHours>;
+    /// Added timeslots must start at a multiple of these minutes (in the display timezone)
+    fn slot_granularity_minutes(&self) -> Option<i64>;
+    /// Whether timeslots not starting at the granularity are rejected instead of rounded
+    fn reject_unaligned_slots(&self) -> bool;
+    /// Password of the default admin, see `DEFAULT_ADMIN_NAME`
+    fn password(&self) -> AdminPassword;
+    /// Additional named admins
+    fn admins(&self) -> Vec<Admin>;
+    /// Replaces the password of the given admin until the next restart
+    fn set_password(&self, admin_name: &str, password: AdminPassword);
+    fn auth_header_name(&self) -> String;
+    /// Failed admin authentications of a client IP within the lockout window which lock it out of
+    /// the admin routes. 0 disables the lockout
+    fn auth_lockout_attempts(&self) -> u32;
+    fn auth_lockout_window_seconds(&self) -> u64;
+    /// Duration of a lockout
+    fn auth_lockout_seconds(&self) -> u64;
+    /// Prefix of all routes except the probes, e.g. "/booking" behind a reverse proxy. Empty to
+    /// serve them at the root
+    fn base_path(&self) -> String;
     fn frontend_path(&self) -> PathBuf;
     fn database_url(&self) -> Option<String>;
+    fn database_pool_size(&self) -> Option<u32>;
+    fn skip_migrations(&self) -> bool;
+    fn allow_overlap(&self) -> bool;
+    fn max_timeslots(&self) -> Option<usize>;
+    /// Minutes until an unconfirmed booking is released. Bookings are kept if not set
+    fn booking_hold_minutes(&self) -> Option<u32>;
+    /// Maximum number of current bookings under the same client name. Unlimited if not set
+    fn max_bookings_per_client(&self) -> Option<u32>;
+    /// Timeslots can only be booked until this many minutes before they start
+    fn min_booking_lead_minutes(&self) -> i64;
+    fn hard_delete(&self) -> bool;
+    /// Number of example timeslots added on startup if no database is used
+    fn seed_examples(&self) -> u32;
+    fn bind_address(&self) -> String;
     fn port(&self) -> String;
+    fn tls_cert_path(&self) -> Option<PathBuf>;
+    fn tls_key_path(&self) -> Option<PathBuf>;
+    fn cleanup_retention_hours(&self) -> i64;
+    /// Seconds between two removals of outdated timeslots
+    fn cleanup_interval_seconds(&self) -> u64;
+    fn past_grace_minutes(&self) -> i64;
+    /// Added timeslots must not start more than these days in the future. Unlimited if not set
+    fn max_future_days(&self) -> Option<i64>;
+    /// Categories timeslots may have. Any category is allowed if empty
+    fn allowed_categories(&self) -> Vec<String>;
+    fn rate_limit_per_minute(&self) -> u32;
+    fn webhook_url(&self) -> Option<String>;
+    /// Number of attempts to deliver a webhook notification, including the first one
+    fn webhook_max_attempts(&self) -> u32;
+    /// Delay before the first retry of a failed webhook delivery, doubled for every further retry
+    fn webhook_retry_delay_ms(&self) -> u64;
+    /// Time after which a webhook delivery attempt is aborted, so hanging receivers don't keep
+    /// the delivery alive
+    fn webhook_timeout_ms(&self) -> u64;
+    fn allowed_origins(&self) -> Vec<String>;
+    fn sse_keep_alive_seconds(&self) -> u64;
+    /// Requests to the mutating routes with a larger body are rejected with 413
+    fn max_body_bytes(&self) -> usize;
+    /// Requests beyond this number of requests in progress are rejected with 503
+    fn max_concurrent_requests(&self) -> usize;
+    /// Level of the log line written for every request
+    fn access_log_level(&self) -> Level;
+    /// Whether bookings and changes of timeslots are rejected, e.g. during a migration
+    fn read_only(&self) -> bool;
+    /// Switches the read-only mode on or off until the next restart
+    fn set_read_only(&self, read_only: bool);
+}
+
+/// Format of the log lines written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, e.g. for log aggregators
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(log_format: &str) -> Result<Self, Self::Err> {
+        match log_format.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Invalid log format \"{log_format}\". Expected \"pretty\" or \"json\""
+            )),
+        }
+    }
+}
+
+/// Opening hours on the given weekdays, e.g. 08:00 to 20:00 from Monday to Friday
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusinessHours {
+    pub opening: NaiveTime,
+    /// Exclusive, a timeslot starting at closing time is rejected
+    pub closing: NaiveTime,
+    pub weekdays: Vec<Weekday>,
+}
+
+impl BusinessHours {
+    /// The whole day on all weekdays
+    pub fn always() -> Self {
+        Self {
+            opening: NaiveTime::MIN,
+            closing: NaiveTime::from_hms_nano_opt(23, 59, 59, 1_999_999_999).unwrap(),
+            weekdays: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ],
+        }
+    }
+
+    /// Whether the local start time lies within the business hours
+    pub fn contains(&self, start: NaiveDateTime) -> bool {
+        self.weekdays.contains(&start.weekday())
+            && self.opening <= start.time()
+            && start.time() < self.closing
+    }
+}
+
+/// Parses opening hours like "08:00-20:00"
+pub fn parse_opening_hours(opening_hours: &str) -> Result<(NaiveTime, NaiveTime), String> {
+    let invalid =
+        || format!("Invalid opening hours \"{opening_hours}\". Expected e.g. 08:00-20:00");
+    let (opening, closing) = opening_hours.split_once('-').ok_or_else(invalid)?;
+    let opening = NaiveTime::parse_from_str(opening.trim(), "%H:%M").map_err(|_| invalid())?;
+    let closing = NaiveTime::parse_from_str(closing.trim(), "%H:%M").map_err(|_| invalid())?;
+    if opening >= closing {
+        return Err(format!(
+            "Invalid opening hours \"{opening_hours}\". Opening must be before closing"
+        ));
+    }
+    Ok((opening, closing))
+}
+
+/// Parses comma separated weekdays like "mon,tue,wed"
+pub fn parse_weekdays(weekdays: &str) -> Result<Vec<Weekday>, String> {
+    weekdays
+        .split(',')
+        .map(|weekday| {
+            weekday
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid weekday \"{}\". Expected e.g. mon", weekday.trim()))
+        })
+        .collect()
+}
+
+/// Normalizes a base path like "booking/" to "/booking". The root path results in an empty one.
+pub fn parse_base_path(base_path: &str) -> Result<String, String> {
+    let trimmed = base_path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    let valid = trimmed.split('/').all(|segment| {
+        !segment.is_empty()
+            && segment.chars().all(|character| {
+                character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '.')
+            })
+    });
+    if !valid {
+        return Err(format!(
+            "Invalid base path \"{base_path}\". Expected e.g. /booking"
+        ));
+    }
+    Ok(format!("/{trimmed}"))
+}
+
+/// Combines the bind address and the port to the address the server listens on. IPv6 addresses
+/// are given without brackets, e.g. "::".
+pub fn socket_address(bind_address: &str, port: &str) -> Result<SocketAddr, String> {
+    let ip_address: IpAddr = bind_address.parse().map_err(|_| {
+        format!(
+            "Invalid bind address \"{bind_address}\". Expected an IP address like 0.0.0.0 or ::"
+        )
+    })?;
+    let port: u16 = port
+        .parse()
+        .map_err(|_| format!("Invalid port \"{port}\". Expected a number between 0 and 65535"))?;
+    Ok(SocketAddr::new(ip_address, port))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("0.0.0.0", "8080", "0.0.0.0:8080" ; "ipv4 any")]
+    #[test_case("127.0.0.1", "3000", "127.0.0.1:3000" ; "ipv4 loopback")]
+    #[test_case("::", "8080", "[::]:8080" ; "ipv6 any")]
+    #[test_case("::1", "443", "[::1]:443" ; "ipv6 loopback")]
+    fn test_socket_address(bind_address: &str, port: &str, expected: &str) {
+        assert_eq!(
+            socket_address(bind_address, port).unwrap(),
+            expected.parse::<SocketAddr>().unwrap()
+        );
+    }
+
+    #[test_case("localhost", "8080" ; "hostname")]
+    #[test_case("0.0.0.0:8080", "8080" ; "address with port")]
+    #[test_case("[::]", "8080" ; "ipv6 with brackets")]
+    #[test_case("0.0.0.0", "65536" ; "port out of range")]
+    #[test_case("0.0.0.0", "http" ; "port not a number")]
+    fn test_invalid_socket_address(bind_address: &str, port: &str) {
+        assert!(socket_address(bind_address, port).is_err());
+    }
+
+    #[test_case("pretty", LogFormat::Pretty ; "pretty")]
+    #[test_case("json", LogFormat::Json ; "json")]
+    #[test_case("JSON", LogFormat::Json ; "case insensitive")]
+    fn test_log_format(log_format: &str, expected: LogFormat) {
+        assert_eq!(LogFormat::from_str(log_format).unwrap(), expected);
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case("text" ; "unknown format")]
+    fn test_invalid_log_format(log_format: &str) {
+        assert!(LogFormat::from_str(log_format).is_err());
+    }
+
+    #[test_case("2030-01-28T08:00:00", true ; "at opening time")]
+    #[test_case("2030-01-28T19:59:00", true ; "before closing time")]
+    #[test_case("2030-01-28T20:00:00", false ; "at closing time")]
+    #[test_case("2030-01-28T02:00:00", false ; "at night")]
+    #[test_case("2030-01-27T10:00:00", false ; "on sunday")]
+    fn test_business_hours(start: &str, expected: bool) {
+        let (opening, closing) = parse_opening_hours("08:00-20:00").unwrap();
+        let business_hours = BusinessHours {
+            opening,
+            closing,
+            weekdays: parse_weekdays("mon, tue,wed,thu,fri").unwrap(),
+        };
+        assert_eq!(business_hours.contains(start.parse().unwrap()), expected);
+    }
+
+    #[test]
+    fn test_business_hours_always() {
+        let business_hours = BusinessHours::always();
+        assert!(business_hours.contains("2030-01-27T00:00:00".parse().unwrap()));
+        assert!(business_hours.contains("2030-01-27T23:59:59.999".parse().unwrap()));
+    }
+
+    #[test_case("08:00" ; "missing closing")]
+    #[test_case("20:00-08:00" ; "closing before opening")]
+    #[test_case("8am-8pm" ; "not a time")]
+    fn test_invalid_opening_hours(opening_hours: &str) {
+        assert!(parse_opening_hours(opening_hours).is_err());
+    }
+
+    #[test_case("", "" ; "empty")]
+    #[test_case("/", "" ; "root")]
+    #[test_case("/booking", "/booking" ; "leading slash")]
+    #[test_case("booking/", "/booking" ; "trailing slash")]
+    #[test_case("/apps/booking", "/apps/booking" ; "nested")]
+    fn test_parse_base_path(base_path: &str, expected: &str) {
+        assert_eq!(parse_base_path(base_path).unwrap(), expected);
+    }
+
+    #[test_case("/apps//booking" ; "empty segment")]
+    #[test_case("/:booking" ; "path parameter")]
+    #[test_case("/book ing" ; "whitespace")]
+    fn test_invalid_base_path(base_path: &str) {
+        assert!(parse_base_path(base_path).is_err());
+    }
+
+    #[test]
+    fn test_invalid_weekdays() {
+        assert!(parse_weekdays("mon,someday").is_err());
+    }
 }
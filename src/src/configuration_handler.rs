@@ -1,9 +1,39 @@
-use crate::configuration::Configuration;
+use crate::authentication::{Admin, AdminPassword, DEFAULT_ADMIN_NAME};
+use crate::configuration::{
+    parse_base_path, parse_opening_hours, parse_weekdays, BusinessHours, Configuration, LogFormat,
+    FILE_URL_PREFIX, SQLITE_URL_PREFIX,
+};
+use argon2::PasswordHash;
+use axum::http::{HeaderName, HeaderValue};
+use chrono_tz::Tz;
 use clap::Parser;
 use dotenvy::dotenv;
+use regex::Regex;
 use std::env;
 use std::path::PathBuf;
-use tracing::info;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+const DEFAULT_CLEANUP_RETENTION_HOURS: i64 = 24;
+const DEFAULT_CLEANUP_INTERVAL_SECONDS: u64 = 60;
+const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 60;
+const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_WEBHOOK_RETRY_DELAY_MS: u64 = 1000;
+const DEFAULT_WEBHOOK_TIMEOUT_MS: u64 = 10000;
+const DEFAULT_AUTH_HEADER_NAME: &str = "x-admin-password";
+const DEFAULT_AUTH_LOCKOUT_ATTEMPTS: u32 = 5;
+const DEFAULT_AUTH_LOCKOUT_WINDOW_SECONDS: u64 = 300;
+const DEFAULT_AUTH_LOCKOUT_SECONDS: u64 = 900;
+const DEFAULT_SSE_KEEP_ALIVE_SECONDS: u64 = 15;
+const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 512;
+const DEFAULT_PAST_GRACE_MINUTES: i64 = 5;
+const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0";
+const DEFAULT_DISPLAY_TIMEZONE: &str = "UTC";
+const DEFAULT_NAME_MAX_LEN: usize = 20;
+const DEFAULT_NOTES_MAX_LEN: usize = 60;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
@@ -11,12 +41,139 @@ struct Cli {
     #[arg(short = 't', long = "title", help = "Website Title")]
     website_title: Option<String>,
 
+    #[arg(
+        long = "name-max-len",
+        help = "Maximum number of characters of a client name. Defaults to 20"
+    )]
+    name_max_len: Option<usize>,
+
+    #[arg(
+        long = "name-pattern",
+        help = "Regular expression a client name must match. Defaults to letters, digits and basic punctuation"
+    )]
+    name_pattern: Option<String>,
+
+    #[arg(
+        long = "notes-max-len",
+        help = "Maximum number of characters of the timeslot notes. Defaults to 60"
+    )]
+    notes_max_len: Option<usize>,
+
+    #[arg(
+        long = "notes-pattern",
+        help = "Regular expression the timeslot notes must match. Defaults to letters, digits, punctuation and line breaks"
+    )]
+    notes_pattern: Option<String>,
+
+    #[arg(
+        long = "default-notes",
+        help = "Notes of added timeslots which are added without notes. Defaults to empty notes"
+    )]
+    default_notes: Option<String>,
+
+    #[arg(
+        long = "timezone",
+        help = "IANA timezone the frontend displays the timeslots in, e.g. Europe/Vienna. Defaults to UTC"
+    )]
+    display_timezone: Option<String>,
+
+    #[arg(
+        long = "empty-schedule-message",
+        help = "Message the timeslot snapshot includes if there are no timeslots, e.g. \"No slots yet\""
+    )]
+    empty_schedule_message: Option<String>,
+
+    #[arg(
+        long = "business-hours",
+        help = "Time window new timeslots must start in, e.g. 08:00-20:00, in the display timezone. Any time is accepted if not set"
+    )]
+    business_hours: Option<String>,
+
+    #[arg(
+        long = "business-days",
+        help = "Comma separated weekdays new timeslots must start on, e.g. mon,tue,wed,thu,fri. Any day is accepted if not set"
+    )]
+    business_days: Option<String>,
+
+    #[arg(
+        long = "slot-granularity",
+        help = "Minutes added timeslots are aligned to, e.g. 15 to round 10:07 to 10:00. Timeslots are not aligned if not set"
+    )]
+    slot_granularity_minutes: Option<i64>,
+
+    #[arg(
+        long = "reject-unaligned",
+        help = "Reject timeslots which don't start at the slot granularity instead of rounding them"
+    )]
+    reject_unaligned_slots: bool,
+
     #[arg(short = 'k', long = "key", help = "Authentication key for API access")]
     password: Option<String>,
 
+    #[arg(
+        long = "key-hash",
+        help = "Argon2 hash of the authentication key. Takes precedence over the plaintext key"
+    )]
+    password_hash: Option<String>,
+
+    #[arg(
+        long = "admin",
+        value_parser = Admin::from_str,
+        help = "Additional admin with its own key as NAME:KEY, the key can also be an argon2 hash. Can be repeated"
+    )]
+    admins: Vec<Admin>,
+
+    #[arg(
+        long = "auth-header",
+        help = "Name of the header which carries the authentication key. Defaults to x-admin-password"
+    )]
+    auth_header_name: Option<String>,
+
+    #[arg(
+        long = "auth-lockout-attempts",
+        help = "Failed admin authentications of a client IP within the lockout window which lock it out. 0 disables the lockout"
+    )]
+    auth_lockout_attempts: Option<u32>,
+
+    #[arg(
+        long = "auth-lockout-window",
+        help = "Seconds within which failed admin authentications are counted towards the lockout"
+    )]
+    auth_lockout_window_seconds: Option<u64>,
+
+    #[arg(
+        long = "auth-lockout-duration",
+        help = "Seconds a client IP is locked out of the admin routes after too many failed authentications"
+    )]
+    auth_lockout_seconds: Option<u64>,
+
+    #[arg(
+        long = "base-path",
+        help = "Prefix of all routes except the health checks, e.g. /booking behind a reverse proxy. Defaults to none"
+    )]
+    base_path: Option<String>,
+
+    #[arg(
+        long = "bind",
+        help = "IP address the HTTP server listens on, e.g. 127.0.0.1 or ::. Defaults to 0.0.0.0"
+    )]
+    bind_address: Option<String>,
+
     #[arg(short = 'p', long = "port", help = "Port number for the HTTP server")]
     port: Option<String>,
 
+    #[arg(
+        long = "tls-cert",
+        help = "Path to a PEM encoded TLS certificate chain. Serves HTTPS together with --tls-key"
+    )]
+    tls_cert_path: Option<PathBuf>,
+
+    #[arg(
+        long = "tls-key",
+        help = "Path to the PEM encoded private key of the TLS certificate"
+    )]
+    tls_key_path: Option<PathBuf>,
+
     #[arg(
         short = 'd',
         long = "database", 
@@ -25,18 +182,237 @@ struct Cli {
         help = "Database connection. Without this argument the timeslots are not stored persistently",
     )]
     database_url: Option<String>,
+
+    #[arg(
+        long = "pool-size",
+        help = "Maximum number of database connections. Defaults to 10"
+    )]
+    database_pool_size: Option<u32>,
+
+    #[arg(
+        long = "skip-migrations",
+        help = "Don't apply pending database migrations on startup"
+    )]
+    skip_migrations: bool,
+
+    #[arg(
+        long = "allow-overlap",
+        help = "Allow adding timeslots which overlap with existing timeslots"
+    )]
+    allow_overlap: bool,
+
+    #[arg(
+        long = "max-timeslots",
+        help = "Maximum number of stored timeslots. Unlimited if not set"
+    )]
+    max_timeslots: Option<usize>,
+
+    #[arg(
+        long = "hold-minutes",
+        help = "Minutes a booking is held until it must be confirmed, otherwise it is released. Bookings are not released if not set"
+    )]
+    booking_hold_minutes: Option<u32>,
+
+    #[arg(
+        long = "max-bookings-per-client",
+        help = "Maximum number of current bookings under the same client name. Unlimited if not set"
+    )]
+    max_bookings_per_client: Option<u32>,
+
+    #[arg(
+        long = "min-booking-lead",
+        help = "Minutes before its start a timeslot can be booked at the latest. Defaults to 0"
+    )]
+    min_booking_lead_minutes: Option<i64>,
+
+    #[arg(
+        long = "hard-delete",
+        help = "Delete removed timeslots instead of archiving them"
+    )]
+    hard_delete: bool,
+
+    #[arg(
+        long = "read-only",
+        help = "Start in read-only mode, which rejects bookings and changes of timeslots"
+    )]
+    read_only: bool,
+
+    #[arg(
+        long = "seed-examples",
+        help = "Number of example timeslots added on startup without a database, e.g. for demos"
+    )]
+    seed_examples: Option<u32>,
+
+    #[arg(
+        long = "retention-hours",
+        help = "Hours an outdated timeslot is kept before it gets removed"
+    )]
+    cleanup_retention_hours: Option<i64>,
+
+    #[arg(
+        long = "cleanup-interval",
+        help = "Seconds between two removals of outdated timeslots. Defaults to 60"
+    )]
+    cleanup_interval_seconds: Option<u64>,
+
+    #[arg(
+        long = "past-grace-minutes",
+        help = "Minutes a new timeslot may start in the past to tolerate clock skew. Defaults to 5"
+    )]
+    past_grace_minutes: Option<i64>,
+
+    #[arg(
+        long = "max-future-days",
+        help = "Days in the future a new timeslot may start at most. Unlimited if not set"
+    )]
+    max_future_days: Option<i64>,
+
+    #[arg(
+        long = "category",
+        help = "Category timeslots may have, e.g. haircut. Can be repeated. Any category is allowed if none is given"
+    )]
+    allowed_categories: Vec<String>,
+
+    #[arg(
+        long = "rate-limit",
+        help = "Maximum number of requests per minute a client IP may send to the public routes. 0 disables the limit"
+    )]
+    rate_limit_per_minute: Option<u32>,
+
+    #[arg(
+        long = "webhook-url",
+        help = "URL which receives a POST request with the timeslot whenever a timeslot is booked"
+    )]
+    webhook_url: Option<String>,
+
+    #[arg(
+        long = "webhook-max-attempts",
+        help = "Number of attempts to deliver a webhook notification before it is given up"
+    )]
+    webhook_max_attempts: Option<u32>,
+
+    #[arg(
+        long = "webhook-retry-delay-ms",
+        help = "Milliseconds before the first retry of a failed webhook delivery. Doubled for every further retry"
+    )]
+    webhook_retry_delay_ms: Option<u64>,
+
+    #[arg(
+        long = "webhook-timeout-ms",
+        help = "Milliseconds after which a webhook delivery attempt is aborted and counted as failed"
+    )]
+    webhook_timeout_ms: Option<u64>,
+
+    #[arg(
+        long = "cors-origin",
+        help = "Origin which may access the API from a browser, e.g. https://example.com. Can be repeated. Any origin is allowed if none is given"
+    )]
+    allowed_origins: Vec<String>,
+
+    #[arg(
+        long = "keep-alive",
+        help = "Seconds between keep-alive messages on the timeslot stream. Defaults to 15"
+    )]
+    sse_keep_alive_seconds: Option<u64>,
+
+    #[arg(
+        long = "max-body-bytes",
+        help = "Maximum size of a request body in bytes, e.g. of an imported CSV file. Defaults to 1 MiB"
+    )]
+    max_body_bytes: Option<usize>,
+
+    #[arg(
+        long = "max-concurrent-requests",
+        help = "Maximum number of requests handled at the same time. Further requests are rejected. Defaults to 512"
+    )]
+    max_concurrent_requests: Option<usize>,
+
+    #[arg(
+        long = "access-log-level",
+        value_parser = Level::from_str,
+        help = "Level of the log line written for every request, e.g. debug. Defaults to info"
+    )]
+    access_log_level: Option<Level>,
+
+    #[arg(
+        long = "log-format",
+        value_parser = LogFormat::from_str,
+        help = "Format of the log lines, either pretty or json. Defaults to pretty"
+    )]
+    log_format: Option<LogFormat>,
 }
 
 #[derive(Clone, Debug)]
 pub struct ConfigurationHandler {
     website_title: String,
-    password: String,
+    name_max_len: usize,
+    name_pattern: Option<String>,
+    notes_max_len: usize,
+    notes_pattern: Option<String>,
+    default_notes: String,
+    display_timezone: String,
+    empty_schedule_message: Option<String>,
+    business_hours: Option<BusinessHours>,
+    slot_granularity_minutes: Option<i64>,
+    reject_unaligned_slots: bool,
+    // Shared by all clones, so a rotated password applies to every request
+    password: Arc<Mutex<AdminPassword>>,
+    admins: Arc<Mutex<Vec<Admin>>>,
+    auth_header_name: String,
+    auth_lockout_attempts: u32,
+    auth_lockout_window_seconds: u64,
+    auth_lockout_seconds: u64,
+    base_path: String,
     frontend_path: PathBuf,
     database_url: Option<String>,
+    database_pool_size: Option<u32>,
+    skip_migrations: bool,
+    allow_overlap: bool,
+    max_timeslots: Option<usize>,
+    booking_hold_minutes: Option<u32>,
+    max_bookings_per_client: Option<u32>,
+    min_booking_lead_minutes: i64,
+    hard_delete: bool,
+    seed_examples: u32,
+    bind_address: String,
     port: String,
+    tls_cert_path: Option<PathBuf>,
+    tls_key_path: Option<PathBuf>,
+    cleanup_retention_hours: i64,
+    cleanup_interval_seconds: u64,
+    past_grace_minutes: i64,
+    max_future_days: Option<i64>,
+    allowed_categories: Vec<String>,
+    rate_limit_per_minute: u32,
+    webhook_url: Option<String>,
+    webhook_max_attempts: u32,
+    webhook_retry_delay_ms: u64,
+    webhook_timeout_ms: u64,
+    allowed_origins: Vec<String>,
+    sse_keep_alive_seconds: u64,
+    max_body_bytes: usize,
+    max_concurrent_requests: usize,
+    access_log_level: Level,
+    // Shared by all clones, so toggling it applies to every request
+    read_only: Arc<AtomicBool>,
 }
 
 impl ConfigurationHandler {
+    /// Determines the log format. Unlike the other options it is needed before the logger is
+    /// initialized and is therefore resolved on its own, without logging.
+    pub fn log_format() -> LogFormat {
+        let args = Cli::parse();
+
+        dotenv().expect("Failed to load .env file");
+        if let Some(log_format) = args.log_format {
+            log_format
+        } else if let Ok(log_format) = env::var("LOG_FORMAT") {
+            LogFormat::from_str(&log_format).unwrap_or_else(|err| panic!("{err}"))
+        } else {
+            LogFormat::default()
+        }
+    }
+
     pub fn parse_arguments() -> Self {
         let args = Cli::parse();
 
@@ -49,12 +425,251 @@ impl ConfigurationHandler {
             env::var("WEBSITE_TITLE").expect("WEBSITE_TITLE must be set in .env file")
         };
 
-        let password = if let Some(password) = args.password {
+        let name_max_len = if let Some(name_max_len) = args.name_max_len {
+            info!("Maximum name length provided as argument");
+            name_max_len
+        } else if let Ok(name_max_len) = env::var("NAME_MAX_LEN") {
+            info!("Maximum name length not provided as argument. Using NAME_MAX_LEN specified in \".env\" file");
+            name_max_len
+                .parse()
+                .expect("NAME_MAX_LEN must be a positive number")
+        } else {
+            info!("No maximum name length provided. Using default of {DEFAULT_NAME_MAX_LEN} characters");
+            DEFAULT_NAME_MAX_LEN
+        };
+
+        let name_pattern = if let Some(name_pattern) = args.name_pattern {
+            info!("Name pattern provided as argument");
+            Some(name_pattern)
+        } else if let Ok(name_pattern) = env::var("NAME_PATTERN") {
+            info!("Name pattern not provided as argument. Using NAME_PATTERN specified in \".env\" file");
+            Some(name_pattern)
+        } else {
+            None
+        };
+        if let Some(name_pattern) = &name_pattern {
+            Regex::new(name_pattern).expect("Name pattern must be a valid regular expression");
+        }
+
+        let notes_max_len = if let Some(notes_max_len) = args.notes_max_len {
+            info!("Maximum notes length provided as argument");
+            notes_max_len
+        } else if let Ok(notes_max_len) = env::var("NOTES_MAX_LEN") {
+            info!("Maximum notes length not provided as argument. Using NOTES_MAX_LEN specified in \".env\" file");
+            notes_max_len
+                .parse()
+                .expect("NOTES_MAX_LEN must be a positive number")
+        } else {
+            info!("No maximum notes length provided. Using default of {DEFAULT_NOTES_MAX_LEN} characters");
+            DEFAULT_NOTES_MAX_LEN
+        };
+
+        let notes_pattern = if let Some(notes_pattern) = args.notes_pattern {
+            info!("Notes pattern provided as argument");
+            Some(notes_pattern)
+        } else if let Ok(notes_pattern) = env::var("NOTES_PATTERN") {
+            info!("Notes pattern not provided as argument. Using NOTES_PATTERN specified in \".env\" file");
+            Some(notes_pattern)
+        } else {
+            None
+        };
+        if let Some(notes_pattern) = &notes_pattern {
+            Regex::new(notes_pattern).expect("Notes pattern must be a valid regular expression");
+        }
+
+        let default_notes = if let Some(default_notes) = args.default_notes {
+            info!("Default notes provided as argument");
+            default_notes
+        } else if let Ok(default_notes) = env::var("DEFAULT_NOTES") {
+            info!("Default notes not provided as argument. Using DEFAULT_NOTES specified in \".env\" file");
+            default_notes
+        } else {
+            String::new()
+        };
+
+        let display_timezone = if let Some(display_timezone) = args.display_timezone {
+            info!("Display timezone provided as argument");
+            display_timezone
+        } else if let Ok(display_timezone) = env::var("DISPLAY_TIMEZONE") {
+            info!("Display timezone not provided as argument. Using DISPLAY_TIMEZONE specified in \".env\" file");
+            display_timezone
+        } else {
+            info!("No display timezone provided. Using default \"{DEFAULT_DISPLAY_TIMEZONE}\"");
+            DEFAULT_DISPLAY_TIMEZONE.into()
+        };
+        Tz::from_str(&display_timezone)
+            .expect("Display timezone must be an IANA timezone, e.g. Europe/Vienna");
+
+        let empty_schedule_message = if let Some(empty_schedule_message) =
+            args.empty_schedule_message
+        {
+            info!("Empty schedule message provided as argument");
+            Some(empty_schedule_message)
+        } else if let Ok(empty_schedule_message) = env::var("EMPTY_SCHEDULE_MESSAGE") {
+            info!("Empty schedule message not provided as argument. Using EMPTY_SCHEDULE_MESSAGE specified in \".env\" file");
+            Some(empty_schedule_message)
+        } else {
+            info!("No empty schedule message provided. Empty snapshots carry no message");
+            None
+        };
+
+        let opening_hours = if let Some(business_hours) = args.business_hours {
+            info!("Business hours provided as argument");
+            Some(business_hours)
+        } else if let Ok(business_hours) = env::var("BUSINESS_HOURS") {
+            info!("Business hours not provided as argument. Using BUSINESS_HOURS specified in \".env\" file");
+            Some(business_hours)
+        } else {
+            None
+        }
+        .map(|opening_hours| parse_opening_hours(&opening_hours).unwrap_or_else(|err| panic!("{err}")));
+        let weekdays = if let Some(business_days) = args.business_days {
+            info!("Business days provided as argument");
+            Some(business_days)
+        } else if let Ok(business_days) = env::var("BUSINESS_DAYS") {
+            info!("Business days not provided as argument. Using BUSINESS_DAYS specified in \".env\" file");
+            Some(business_days)
+        } else {
+            None
+        }
+        .map(|weekdays| parse_weekdays(&weekdays).unwrap_or_else(|err| panic!("{err}")));
+        let business_hours = if opening_hours.is_none() && weekdays.is_none() {
+            info!("No business hours provided. Timeslots may start at any time");
+            None
+        } else {
+            let mut business_hours = BusinessHours::always();
+            if let Some((opening, closing)) = opening_hours {
+                business_hours.opening = opening;
+                business_hours.closing = closing;
+            }
+            if let Some(weekdays) = weekdays {
+                business_hours.weekdays = weekdays;
+            }
+            Some(business_hours)
+        };
+
+        let password = if let Some(password_hash) = args.password_hash {
+            info!("Password hash provided as argument");
+            AdminPassword::Hash(password_hash.into())
+        } else if let Some(password) = args.password {
             info!("Password provided as argument");
-            password
+            AdminPassword::Plaintext(password.into())
+        } else if let Ok(password_hash) = env::var("HTTP_PASSWORD_HASH") {
+            info!("Password not provided as argument. Using HTTP_PASSWORD_HASH specified in \".env\".");
+            AdminPassword::Hash(password_hash.into())
         } else {
             info!("Password not provided as argument. Using HTTP_PASSWORD specified in \".env\".");
-            env::var("HTTP_PASSWORD").expect("HTTP_PASSWORD must be set in .env file")
+            AdminPassword::Plaintext(
+                env::var("HTTP_PASSWORD")
+                    .expect("Either HTTP_PASSWORD or HTTP_PASSWORD_HASH must be set in .env file")
+                    .into(),
+            )
+        };
+        if let AdminPassword::Hash(password_hash) = &password {
+            PasswordHash::new(password_hash.expose())
+                .expect("Password hash must be a valid argon2 hash");
+        }
+
+        let admins = if !args.admins.is_empty() {
+            info!("Admins provided as argument");
+            args.admins
+        } else if let Ok(admins) = env::var("ADMINS") {
+            info!("Admins not provided as argument. Using ADMINS specified in \".env\" file");
+            // Separated by semicolons, as argon2 hashes contain commas
+            admins
+                .split(';')
+                .map(str::trim)
+                .filter(|admin| !admin.is_empty())
+                .map(|admin| Admin::from_str(admin).expect("ADMINS must be a list of NAME:KEY"))
+                .collect()
+        } else {
+            info!("No additional admins provided");
+            vec![]
+        };
+        for (index, admin) in admins.iter().enumerate() {
+            assert!(
+                admin.name != DEFAULT_ADMIN_NAME
+                    && admins[..index].iter().all(|other| other.name != admin.name),
+                "Admin names must be unique and not \"{DEFAULT_ADMIN_NAME}\""
+            );
+        }
+
+        let auth_header_name = if let Some(auth_header_name) = args.auth_header_name {
+            info!("Auth header name provided as argument");
+            auth_header_name
+        } else if let Ok(auth_header_name) = env::var("AUTH_HEADER_NAME") {
+            info!("Auth header name not provided as argument. Using AUTH_HEADER_NAME specified in \".env\" file");
+            auth_header_name
+        } else {
+            info!("No auth header name provided. Using default \"{DEFAULT_AUTH_HEADER_NAME}\"");
+            DEFAULT_AUTH_HEADER_NAME.into()
+        };
+        HeaderName::from_str(&auth_header_name)
+            .expect("Auth header name must be a valid header name");
+
+        let auth_lockout_attempts = if let Some(auth_lockout_attempts) = args.auth_lockout_attempts
+        {
+            info!("Auth lockout attempts provided as argument");
+            auth_lockout_attempts
+        } else if let Ok(auth_lockout_attempts) = env::var("AUTH_LOCKOUT_ATTEMPTS") {
+            info!("Auth lockout attempts not provided as argument. Using AUTH_LOCKOUT_ATTEMPTS specified in \".env\" file");
+            auth_lockout_attempts
+                .parse()
+                .expect("AUTH_LOCKOUT_ATTEMPTS must be a number")
+        } else {
+            info!("No auth lockout attempts provided. Using default of {DEFAULT_AUTH_LOCKOUT_ATTEMPTS}");
+            DEFAULT_AUTH_LOCKOUT_ATTEMPTS
+        };
+
+        let auth_lockout_window_seconds = if let Some(auth_lockout_window_seconds) =
+            args.auth_lockout_window_seconds
+        {
+            info!("Auth lockout window provided as argument");
+            auth_lockout_window_seconds
+        } else if let Ok(auth_lockout_window_seconds) = env::var("AUTH_LOCKOUT_WINDOW_SECONDS") {
+            info!("Auth lockout window not provided as argument. Using AUTH_LOCKOUT_WINDOW_SECONDS specified in \".env\" file");
+            auth_lockout_window_seconds
+                .parse()
+                .expect("AUTH_LOCKOUT_WINDOW_SECONDS must be a number")
+        } else {
+            info!("No auth lockout window provided. Using default of {DEFAULT_AUTH_LOCKOUT_WINDOW_SECONDS} seconds");
+            DEFAULT_AUTH_LOCKOUT_WINDOW_SECONDS
+        };
+
+        let auth_lockout_seconds = if let Some(auth_lockout_seconds) = args.auth_lockout_seconds {
+            info!("Auth lockout duration provided as argument");
+            auth_lockout_seconds
+        } else if let Ok(auth_lockout_seconds) = env::var("AUTH_LOCKOUT_SECONDS") {
+            info!("Auth lockout duration not provided as argument. Using AUTH_LOCKOUT_SECONDS specified in \".env\" file");
+            auth_lockout_seconds
+                .parse()
+                .expect("AUTH_LOCKOUT_SECONDS must be a number")
+        } else {
+            info!("No auth lockout duration provided. Using default of {DEFAULT_AUTH_LOCKOUT_SECONDS} seconds");
+            DEFAULT_AUTH_LOCKOUT_SECONDS
+        };
+
+        let base_path = if let Some(base_path) = args.base_path {
+            info!("Base path provided as argument");
+            base_path
+        } else if let Ok(base_path) = env::var("BASE_PATH") {
+            info!("Base path not provided as argument. Using BASE_PATH specified in \".env\" file");
+            base_path
+        } else {
+            info!("No base path provided. Serving the routes at the root");
+            String::new()
+        };
+        let base_path = parse_base_path(&base_path).unwrap_or_else(|err| panic!("{err}"));
+
+        let bind_address = if let Some(bind_address) = args.bind_address {
+            info!("Bind address provided as argument");
+            bind_address
+        } else if let Ok(bind_address) = env::var("BIND_ADDRESS") {
+            info!("Bind address not provided as argument. Using BIND_ADDRESS specified in \".env\" file");
+            bind_address
+        } else {
+            info!("No bind address provided. Using default \"{DEFAULT_BIND_ADDRESS}\"");
+            DEFAULT_BIND_ADDRESS.into()
         };
 
         let port = if let Some(port) = args.port {
@@ -65,6 +680,35 @@ impl ConfigurationHandler {
             env::var("PORT").expect("PORT must be set in .env file")
         };
 
+        let tls_cert_path = if let Some(tls_cert_path) = args.tls_cert_path {
+            info!("TLS certificate provided as argument");
+            Some(tls_cert_path)
+        } else if let Ok(tls_cert_path) = env::var("TLS_CERT_PATH") {
+            info!("TLS certificate not provided as argument. Using TLS_CERT_PATH specified in \".env\" file");
+            Some(PathBuf::from(tls_cert_path))
+        } else {
+            None
+        };
+
+        let tls_key_path = if let Some(tls_key_path) = args.tls_key_path {
+            info!("TLS key provided as argument");
+            Some(tls_key_path)
+        } else if let Ok(tls_key_path) = env::var("TLS_KEY_PATH") {
+            info!(
+                "TLS key not provided as argument. Using TLS_KEY_PATH specified in \".env\" file"
+            );
+            Some(PathBuf::from(tls_key_path))
+        } else {
+            None
+        };
+        assert!(
+            tls_cert_path.is_some() == tls_key_path.is_some(),
+            "TLS certificate and key must be provided together. Provide both to serve HTTPS or neither to serve HTTP"
+        );
+        if tls_cert_path.is_none() {
+            info!("No TLS certificate provided. Serving HTTP");
+        }
+
         let database_url = if let Some(database_url) = args.database_url {
             if database_url.is_empty() {
                 info!("Run with database. No database url provided as argument. Using DATABASE_URL specified in \".env\" file");
@@ -77,13 +721,485 @@ impl ConfigurationHandler {
             info!("Run without database");
             None
         };
+        if database_url
+            .as_deref()
+            .is_some_and(|database_url| database_url.starts_with(SQLITE_URL_PREFIX))
+        {
+            info!("Database url has \"{SQLITE_URL_PREFIX}\" prefix. Using SQLite database");
+        }
+        if database_url
+            .as_deref()
+            .is_some_and(|database_url| database_url.starts_with(FILE_URL_PREFIX))
+        {
+            info!("Database url has \"{FILE_URL_PREFIX}\" prefix. Using JSON file");
+        }
+
+        let database_pool_size = if let Some(database_pool_size) = args.database_pool_size {
+            info!("Database pool size provided as argument");
+            Some(database_pool_size)
+        } else if let Ok(database_pool_size) = env::var("DATABASE_POOL_SIZE") {
+            info!("Database pool size not provided as argument. Using DATABASE_POOL_SIZE specified in \".env\" file");
+            Some(
+                database_pool_size
+                    .parse()
+                    .expect("DATABASE_POOL_SIZE must be a number"),
+            )
+        } else {
+            None
+        };
+
+        let skip_migrations = if args.skip_migrations {
+            info!("Skip migrations provided as argument");
+            true
+        } else if let Ok(skip_migrations) = env::var("SKIP_MIGRATIONS") {
+            info!("Skip migrations not provided as argument. Using SKIP_MIGRATIONS specified in \".env\" file");
+            skip_migrations
+                .parse()
+                .expect("SKIP_MIGRATIONS must be either true or false")
+        } else {
+            false
+        };
+
+        let allow_overlap = if args.allow_overlap {
+            info!("Allow overlap provided as argument");
+            true
+        } else if let Ok(allow_overlap) = env::var("ALLOW_OVERLAP") {
+            info!("Allow overlap not provided as argument. Using ALLOW_OVERLAP specified in \".env\" file");
+            allow_overlap
+                .parse()
+                .expect("ALLOW_OVERLAP must be either true or false")
+        } else {
+            false
+        };
+
+        let max_timeslots = if let Some(max_timeslots) = args.max_timeslots {
+            info!("Maximum number of timeslots provided as argument");
+            Some(max_timeslots)
+        } else if let Ok(max_timeslots) = env::var("MAX_TIMESLOTS") {
+            info!("Maximum number of timeslots not provided as argument. Using MAX_TIMESLOTS specified in \".env\" file");
+            Some(
+                max_timeslots
+                    .parse()
+                    .expect("MAX_TIMESLOTS must be a number"),
+            )
+        } else {
+            info!("No maximum number of timeslots provided. The number of timeslots is unlimited");
+            None
+        };
+
+        let booking_hold_minutes = if let Some(booking_hold_minutes) = args.booking_hold_minutes {
+            info!("Booking hold provided as argument");
+            Some(booking_hold_minutes)
+        } else if let Ok(booking_hold_minutes) = env::var("BOOKING_HOLD_MINUTES") {
+            info!("Booking hold not provided as argument. Using BOOKING_HOLD_MINUTES specified in \".env\" file");
+            Some(
+                booking_hold_minutes
+                    .parse()
+                    .expect("BOOKING_HOLD_MINUTES must be a number"),
+            )
+        } else {
+            info!("No booking hold provided. Unconfirmed bookings are kept");
+            None
+        };
+
+        let max_bookings_per_client = if let Some(max_bookings_per_client) =
+            args.max_bookings_per_client
+        {
+            info!("Maximum number of bookings per client provided as argument");
+            Some(max_bookings_per_client)
+        } else if let Ok(max_bookings_per_client) = env::var("MAX_BOOKINGS_PER_CLIENT") {
+            info!("Maximum number of bookings per client not provided as argument. Using MAX_BOOKINGS_PER_CLIENT specified in \".env\" file");
+            Some(
+                max_bookings_per_client
+                    .parse()
+                    .expect("MAX_BOOKINGS_PER_CLIENT must be a number"),
+            )
+        } else {
+            info!("No maximum number of bookings per client provided. Clients may book any number of timeslots");
+            None
+        };
+
+        let min_booking_lead_minutes = if let Some(min_booking_lead_minutes) =
+            args.min_booking_lead_minutes
+        {
+            info!("Minimum booking lead time provided as argument");
+            min_booking_lead_minutes
+        } else if let Ok(min_booking_lead_minutes) = env::var("MIN_BOOKING_LEAD_MINUTES") {
+            info!("Minimum booking lead time not provided as argument. Using MIN_BOOKING_LEAD_MINUTES specified in \".env\" file");
+            min_booking_lead_minutes
+                .parse()
+                .expect("MIN_BOOKING_LEAD_MINUTES must be a number")
+        } else {
+            info!(
+                "No minimum booking lead time provided. Timeslots can be booked until they start"
+            );
+            0
+        };
+        assert!(
+            min_booking_lead_minutes >= 0,
+            "Minimum booking lead time must not be negative"
+        );
+
+        let seed_examples = if let Some(seed_examples) = args.seed_examples {
+            info!("Number of example timeslots provided as argument");
+            seed_examples
+        } else if let Ok(seed_examples) = env::var("SEED_EXAMPLES") {
+            info!("Number of example timeslots not provided as argument. Using SEED_EXAMPLES specified in \".env\" file");
+            seed_examples
+                .parse()
+                .expect("SEED_EXAMPLES must be a positive number")
+        } else {
+            info!("No number of example timeslots provided. Starting without examples");
+            0
+        };
+
+        let slot_granularity_minutes = if let Some(slot_granularity_minutes) =
+            args.slot_granularity_minutes
+        {
+            info!("Slot granularity provided as argument");
+            Some(slot_granularity_minutes)
+        } else if let Ok(slot_granularity_minutes) = env::var("SLOT_GRANULARITY_MINUTES") {
+            info!("Slot granularity not provided as argument. Using SLOT_GRANULARITY_MINUTES specified in \".env\" file");
+            Some(
+                slot_granularity_minutes
+                    .parse()
+                    .expect("SLOT_GRANULARITY_MINUTES must be a number"),
+            )
+        } else {
+            info!("No slot granularity provided. Timeslots may start at any minute");
+            None
+        };
+        assert!(
+            slot_granularity_minutes.is_none_or(|minutes| minutes > 0),
+            "Slot granularity must be at least 1 minute"
+        );
+
+        let reject_unaligned_slots = if args.reject_unaligned_slots {
+            info!("Rejecting unaligned timeslots provided as argument");
+            true
+        } else if let Ok(reject_unaligned_slots) = env::var("REJECT_UNALIGNED_SLOTS") {
+            info!("Rejecting unaligned timeslots not provided as argument. Using REJECT_UNALIGNED_SLOTS specified in \".env\" file");
+            reject_unaligned_slots
+                .parse()
+                .expect("REJECT_UNALIGNED_SLOTS must be either true or false")
+        } else {
+            false
+        };
+
+        let hard_delete = if args.hard_delete {
+            info!("Hard delete provided as argument");
+            true
+        } else if let Ok(hard_delete) = env::var("HARD_DELETE") {
+            info!("Hard delete not provided as argument. Using HARD_DELETE specified in \".env\" file");
+            hard_delete
+                .parse()
+                .expect("HARD_DELETE must be either true or false")
+        } else {
+            false
+        };
+
+        let read_only = if args.read_only {
+            info!("Read-only mode provided as argument");
+            true
+        } else if let Ok(read_only) = env::var("READ_ONLY") {
+            info!("Read-only mode not provided as argument. Using READ_ONLY specified in \".env\" file");
+            read_only
+                .parse()
+                .expect("READ_ONLY must be either true or false")
+        } else {
+            false
+        };
+
+        let cleanup_retention_hours = if let Some(cleanup_retention_hours) =
+            args.cleanup_retention_hours
+        {
+            info!("Cleanup retention provided as argument");
+            cleanup_retention_hours
+        } else if let Ok(cleanup_retention_hours) = env::var("CLEANUP_RETENTION_HOURS") {
+            info!("Cleanup retention not provided as argument. Using CLEANUP_RETENTION_HOURS specified in \".env\" file");
+            cleanup_retention_hours
+                .parse()
+                .expect("CLEANUP_RETENTION_HOURS must be a number")
+        } else {
+            info!("No cleanup retention provided. Using default of {DEFAULT_CLEANUP_RETENTION_HOURS} hours");
+            DEFAULT_CLEANUP_RETENTION_HOURS
+        };
+
+        let cleanup_interval_seconds = if let Some(cleanup_interval_seconds) =
+            args.cleanup_interval_seconds
+        {
+            info!("Cleanup interval provided as argument");
+            cleanup_interval_seconds
+        } else if let Ok(cleanup_interval_seconds) = env::var("CLEANUP_INTERVAL_SECONDS") {
+            info!("Cleanup interval not provided as argument. Using CLEANUP_INTERVAL_SECONDS specified in \".env\" file");
+            cleanup_interval_seconds
+                .parse()
+                .expect("CLEANUP_INTERVAL_SECONDS must be a positive number")
+        } else {
+            info!("No cleanup interval provided. Using default of {DEFAULT_CLEANUP_INTERVAL_SECONDS} seconds");
+            DEFAULT_CLEANUP_INTERVAL_SECONDS
+        };
+        assert!(
+            cleanup_interval_seconds > 0,
+            "Cleanup interval must be greater than 0"
+        );
+
+        let past_grace_minutes = if let Some(past_grace_minutes) = args.past_grace_minutes {
+            info!("Past grace period provided as argument");
+            past_grace_minutes
+        } else if let Ok(past_grace_minutes) = env::var("PAST_GRACE_MINUTES") {
+            info!("Past grace period not provided as argument. Using PAST_GRACE_MINUTES specified in \".env\" file");
+            past_grace_minutes
+                .parse()
+                .expect("PAST_GRACE_MINUTES must be a number")
+        } else {
+            info!("No past grace period provided. Using default of {DEFAULT_PAST_GRACE_MINUTES} minutes");
+            DEFAULT_PAST_GRACE_MINUTES
+        };
+
+        let max_future_days = if let Some(max_future_days) = args.max_future_days {
+            info!("Maximum future horizon provided as argument");
+            Some(max_future_days)
+        } else if let Ok(max_future_days) = env::var("MAX_FUTURE_DAYS") {
+            info!("Maximum future horizon not provided as argument. Using MAX_FUTURE_DAYS specified in \".env\" file");
+            Some(
+                max_future_days
+                    .parse()
+                    .expect("MAX_FUTURE_DAYS must be a number"),
+            )
+        } else {
+            info!(
+                "No maximum future horizon provided. Timeslots may start at any time in the future"
+            );
+            None
+        };
+        assert!(
+            max_future_days.is_none_or(|days| days > 0),
+            "Maximum future horizon must be at least 1 day"
+        );
+
+        let allowed_categories = if !args.allowed_categories.is_empty() {
+            info!("Categories provided as argument");
+            args.allowed_categories
+        } else if let Ok(allowed_categories) = env::var("CATEGORIES") {
+            info!(
+                "Categories not provided as argument. Using CATEGORIES specified in \".env\" file"
+            );
+            allowed_categories
+                .split(',')
+                .map(|category| category.trim().to_string())
+                .filter(|category| !category.is_empty())
+                .collect()
+        } else {
+            info!("No categories provided. Any category is allowed");
+            vec![]
+        };
+
+        let rate_limit_per_minute = if let Some(rate_limit_per_minute) = args.rate_limit_per_minute
+        {
+            info!("Rate limit provided as argument");
+            rate_limit_per_minute
+        } else if let Ok(rate_limit_per_minute) = env::var("RATE_LIMIT_PER_MINUTE") {
+            info!("Rate limit not provided as argument. Using RATE_LIMIT_PER_MINUTE specified in \".env\" file");
+            rate_limit_per_minute
+                .parse()
+                .expect("RATE_LIMIT_PER_MINUTE must be a number")
+        } else {
+            info!("No rate limit provided. Using default of {DEFAULT_RATE_LIMIT_PER_MINUTE} requests per minute");
+            DEFAULT_RATE_LIMIT_PER_MINUTE
+        };
+
+        let webhook_url = if let Some(webhook_url) = args.webhook_url {
+            info!("Webhook url provided as argument");
+            Some(webhook_url)
+        } else if let Ok(webhook_url) = env::var("WEBHOOK_URL") {
+            info!("Webhook url not provided as argument. Using WEBHOOK_URL specified in \".env\" file");
+            Some(webhook_url)
+        } else {
+            info!("No webhook url provided. Bookings are not forwarded");
+            None
+        };
+
+        let webhook_max_attempts = if let Some(webhook_max_attempts) = args.webhook_max_attempts {
+            info!("Webhook attempts provided as argument");
+            webhook_max_attempts
+        } else if let Ok(webhook_max_attempts) = env::var("WEBHOOK_MAX_ATTEMPTS") {
+            info!("Webhook attempts not provided as argument. Using WEBHOOK_MAX_ATTEMPTS specified in \".env\" file");
+            webhook_max_attempts
+                .parse()
+                .expect("WEBHOOK_MAX_ATTEMPTS must be a positive number")
+        } else {
+            info!("No webhook attempts provided. Using default of {DEFAULT_WEBHOOK_MAX_ATTEMPTS}");
+            DEFAULT_WEBHOOK_MAX_ATTEMPTS
+        };
+        assert!(
+            webhook_max_attempts > 0,
+            "Webhook attempts must be greater than 0"
+        );
+
+        let webhook_retry_delay_ms = if let Some(webhook_retry_delay_ms) =
+            args.webhook_retry_delay_ms
+        {
+            info!("Webhook retry delay provided as argument");
+            webhook_retry_delay_ms
+        } else if let Ok(webhook_retry_delay_ms) = env::var("WEBHOOK_RETRY_DELAY_MS") {
+            info!("Webhook retry delay not provided as argument. Using WEBHOOK_RETRY_DELAY_MS specified in \".env\" file");
+            webhook_retry_delay_ms
+                .parse()
+                .expect("WEBHOOK_RETRY_DELAY_MS must be a number")
+        } else {
+            info!("No webhook retry delay provided. Using default of {DEFAULT_WEBHOOK_RETRY_DELAY_MS} ms");
+            DEFAULT_WEBHOOK_RETRY_DELAY_MS
+        };
+
+        let webhook_timeout_ms = if let Some(webhook_timeout_ms) = args.webhook_timeout_ms {
+            info!("Webhook timeout provided as argument");
+            webhook_timeout_ms
+        } else if let Ok(webhook_timeout_ms) = env::var("WEBHOOK_TIMEOUT_MS") {
+            info!("Webhook timeout not provided as argument. Using WEBHOOK_TIMEOUT_MS specified in \".env\" file");
+            webhook_timeout_ms
+                .parse()
+                .expect("WEBHOOK_TIMEOUT_MS must be a number")
+        } else {
+            info!("No webhook timeout provided. Using default of {DEFAULT_WEBHOOK_TIMEOUT_MS} ms");
+            DEFAULT_WEBHOOK_TIMEOUT_MS
+        };
+
+        let allowed_origins = if !args.allowed_origins.is_empty() {
+            info!("CORS origins provided as argument");
+            args.allowed_origins
+        } else if let Ok(allowed_origins) = env::var("CORS_ORIGINS") {
+            info!("CORS origins not provided as argument. Using CORS_ORIGINS specified in \".env\" file");
+            allowed_origins
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect()
+        } else {
+            info!("No CORS origins provided. Any origin is allowed");
+            vec![]
+        };
+        for origin in &allowed_origins {
+            HeaderValue::from_str(origin).expect("CORS origins must be valid header values");
+        }
+
+        let sse_keep_alive_seconds = if let Some(sse_keep_alive_seconds) =
+            args.sse_keep_alive_seconds
+        {
+            info!("Keep-alive interval provided as argument");
+            sse_keep_alive_seconds
+        } else if let Ok(sse_keep_alive_seconds) = env::var("SSE_KEEP_ALIVE_SECONDS") {
+            info!("Keep-alive interval not provided as argument. Using SSE_KEEP_ALIVE_SECONDS specified in \".env\" file");
+            sse_keep_alive_seconds
+                .parse()
+                .expect("SSE_KEEP_ALIVE_SECONDS must be a number")
+        } else {
+            info!("No keep-alive interval provided. Using default of {DEFAULT_SSE_KEEP_ALIVE_SECONDS} seconds");
+            DEFAULT_SSE_KEEP_ALIVE_SECONDS
+        };
+        assert!(
+            sse_keep_alive_seconds > 0,
+            "Keep-alive interval must be at least 1 second"
+        );
+
+        let max_body_bytes = if let Some(max_body_bytes) = args.max_body_bytes {
+            info!("Maximum body size provided as argument");
+            max_body_bytes
+        } else if let Ok(max_body_bytes) = env::var("MAX_BODY_BYTES") {
+            info!("Maximum body size not provided as argument. Using MAX_BODY_BYTES specified in \".env\" file");
+            max_body_bytes
+                .parse()
+                .expect("MAX_BODY_BYTES must be a positive number")
+        } else {
+            info!("No maximum body size provided. Using default of {DEFAULT_MAX_BODY_BYTES} bytes");
+            DEFAULT_MAX_BODY_BYTES
+        };
+        assert!(
+            max_body_bytes > 0,
+            "Maximum body size must be greater than 0"
+        );
+
+        let max_concurrent_requests = if let Some(max_concurrent_requests) =
+            args.max_concurrent_requests
+        {
+            info!("Maximum number of concurrent requests provided as argument");
+            max_concurrent_requests
+        } else if let Ok(max_concurrent_requests) = env::var("MAX_CONCURRENT_REQUESTS") {
+            info!("Maximum number of concurrent requests not provided as argument. Using MAX_CONCURRENT_REQUESTS specified in \".env\" file");
+            max_concurrent_requests
+                .parse()
+                .expect("MAX_CONCURRENT_REQUESTS must be a positive number")
+        } else {
+            info!("No maximum number of concurrent requests provided. Using default of {DEFAULT_MAX_CONCURRENT_REQUESTS}");
+            DEFAULT_MAX_CONCURRENT_REQUESTS
+        };
+        assert!(
+            max_concurrent_requests > 0,
+            "Maximum number of concurrent requests must be greater than 0"
+        );
+
+        let access_log_level = if let Some(access_log_level) = args.access_log_level {
+            info!("Access log level provided as argument");
+            access_log_level
+        } else if let Ok(access_log_level) = env::var("ACCESS_LOG_LEVEL") {
+            info!("Access log level not provided as argument. Using ACCESS_LOG_LEVEL specified in \".env\" file");
+            Level::from_str(&access_log_level)
+                .expect("ACCESS_LOG_LEVEL must be one of trace, debug, info, warn or error")
+        } else {
+            info!("No access log level provided. Using info");
+            Level::INFO
+        };
 
         Self {
             website_title,
-            password,
+            name_max_len,
+            name_pattern,
+            notes_max_len,
+            notes_pattern,
+            default_notes,
+            display_timezone,
+            empty_schedule_message,
+            business_hours,
+            slot_granularity_minutes,
+            reject_unaligned_slots,
+            password: Arc::new(Mutex::new(password)),
+            admins: Arc::new(Mutex::new(admins)),
+            auth_header_name,
+            auth_lockout_attempts,
+            auth_lockout_window_seconds,
+            auth_lockout_seconds,
+            base_path,
             frontend_path: PathBuf::from("frontend/index.html"),
             database_url,
+            database_pool_size,
+            skip_migrations,
+            allow_overlap,
+            max_timeslots,
+            booking_hold_minutes,
+            max_bookings_per_client,
+            min_booking_lead_minutes,
+            hard_delete,
+            seed_examples,
+            bind_address,
             port,
+            tls_cert_path,
+            tls_key_path,
+            cleanup_retention_hours,
+            cleanup_interval_seconds,
+            past_grace_minutes,
+            max_future_days,
+            allowed_categories,
+            rate_limit_per_minute,
+            webhook_url,
+            webhook_max_attempts,
+            webhook_retry_delay_ms,
+            webhook_timeout_ms,
+            allowed_origins,
+            sse_keep_alive_seconds,
+            max_body_bytes,
+            max_concurrent_requests,
+            access_log_level,
+            read_only: Arc::new(AtomicBool::new(read_only)),
         }
     }
 }
@@ -93,8 +1209,86 @@ impl Configuration for ConfigurationHandler {
         self.website_title.clone()
     }
 
-    fn password(&self) -> String {
-        self.password.clone()
+    fn name_max_len(&self) -> usize {
+        self.name_max_len
+    }
+
+    fn name_pattern(&self) -> Option<String> {
+        self.name_pattern.clone()
+    }
+
+    fn notes_max_len(&self) -> usize {
+        self.notes_max_len
+    }
+
+    fn notes_pattern(&self) -> Option<String> {
+        self.notes_pattern.clone()
+    }
+
+    fn default_notes(&self) -> String {
+        self.default_notes.clone()
+    }
+
+    fn display_timezone(&self) -> String {
+        self.display_timezone.clone()
+    }
+
+    fn empty_schedule_message(&self) -> Option<String> {
+        self.empty_schedule_message.clone()
+    }
+
+    fn business_hours(&self) -> Option<BusinessHours> {
+        self.business_hours.clone()
+    }
+
+    fn slot_granularity_minutes(&self) -> Option<i64> {
+        self.slot_granularity_minutes
+    }
+
+    fn reject_unaligned_slots(&self) -> bool {
+        self.reject_unaligned_slots
+    }
+
+    fn password(&self) -> AdminPassword {
+        self.password.lock().unwrap().clone()
+    }
+
+    fn admins(&self) -> Vec<Admin> {
+        self.admins.lock().unwrap().clone()
+    }
+
+    fn set_password(&self, admin_name: &str, password: AdminPassword) {
+        if admin_name == DEFAULT_ADMIN_NAME {
+            *self.password.lock().unwrap() = password;
+        } else if let Some(admin) = self
+            .admins
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|admin| admin.name == admin_name)
+        {
+            admin.password = password;
+        }
+    }
+
+    fn auth_header_name(&self) -> String {
+        self.auth_header_name.clone()
+    }
+
+    fn auth_lockout_attempts(&self) -> u32 {
+        self.auth_lockout_attempts
+    }
+
+    fn auth_lockout_window_seconds(&self) -> u64 {
+        self.auth_lockout_window_seconds
+    }
+
+    fn auth_lockout_seconds(&self) -> u64 {
+        self.auth_lockout_seconds
+    }
+
+    fn base_path(&self) -> String {
+        self.base_path.clone()
     }
 
     fn frontend_path(&self) -> PathBuf {
@@ -105,7 +1299,123 @@ impl Configuration for ConfigurationHandler {
         self.database_url.clone()
     }
 
+    fn database_pool_size(&self) -> Option<u32> {
+        self.database_pool_size
+    }
+
+    fn skip_migrations(&self) -> bool {
+        self.skip_migrations
+    }
+
+    fn allow_overlap(&self) -> bool {
+        self.allow_overlap
+    }
+
+    fn max_timeslots(&self) -> Option<usize> {
+        self.max_timeslots
+    }
+
+    fn booking_hold_minutes(&self) -> Option<u32> {
+        self.booking_hold_minutes
+    }
+
+    fn max_bookings_per_client(&self) -> Option<u32> {
+        self.max_bookings_per_client
+    }
+
+    fn min_booking_lead_minutes(&self) -> i64 {
+        self.min_booking_lead_minutes
+    }
+
+    fn hard_delete(&self) -> bool {
+        self.hard_delete
+    }
+
+    fn seed_examples(&self) -> u32 {
+        self.seed_examples
+    }
+
+    fn bind_address(&self) -> String {
+        self.bind_address.clone()
+    }
+
     fn port(&self) -> String {
         self.port.clone()
     }
+
+    fn tls_cert_path(&self) -> Option<PathBuf> {
+        self.tls_cert_path.clone()
+    }
+
+    fn tls_key_path(&self) -> Option<PathBuf> {
+        self.tls_key_path.clone()
+    }
+
+    fn cleanup_retention_hours(&self) -> i64 {
+        self.cleanup_retention_hours
+    }
+
+    fn cleanup_interval_seconds(&self) -> u64 {
+        self.cleanup_interval_seconds
+    }
+
+    fn past_grace_minutes(&self) -> i64 {
+        self.past_grace_minutes
+    }
+
+    fn max_future_days(&self) -> Option<i64> {
+        self.max_future_days
+    }
+
+    fn allowed_categories(&self) -> Vec<String> {
+        self.allowed_categories.clone()
+    }
+
+    fn rate_limit_per_minute(&self) -> u32 {
+        self.rate_limit_per_minute
+    }
+
+    fn webhook_url(&self) -> Option<String> {
+        self.webhook_url.clone()
+    }
+
+    fn webhook_max_attempts(&self) -> u32 {
+        self.webhook_max_attempts
+    }
+
+    fn webhook_retry_delay_ms(&self) -> u64 {
+        self.webhook_retry_delay_ms
+    }
+
+    fn webhook_timeout_ms(&self) -> u64 {
+        self.webhook_timeout_ms
+    }
+
+    fn allowed_origins(&self) -> Vec<String> {
+        self.allowed_origins.clone()
+    }
+
+    fn sse_keep_alive_seconds(&self) -> u64 {
+        self.sse_keep_alive_seconds
+    }
+
+    fn max_body_bytes(&self) -> usize {
+        self.max_body_bytes
+    }
+
+    fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
+    fn access_log_level(&self) -> Level {
+        self.access_log_level
+    }
+
+    fn read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::SeqCst);
+    }
 }
@@ -0,0 +1,112 @@
+use crate::types::{Booking, Timeslot};
+use chrono_tz::Tz;
+
+const CONFIRMATION_TEMPLATE: &str = include_str!("../frontend/confirmation.html");
+
+/// Renders the printable confirmation page of a booking of the timeslot. Times are shown in the
+/// display timezone.
+pub fn to_confirmation_page(
+    website_title: &str,
+    timeslot: &Timeslot,
+    booking: &Booking,
+    timezone: Tz,
+) -> String {
+    let start = timeslot.datetime.with_timezone(&timezone);
+    let end = timeslot.end().with_timezone(&timezone);
+    let time = format!(
+        "{} - {} ({timezone})",
+        start.format("%H:%M"),
+        end.format("%H:%M")
+    );
+
+    // The booker name is replaced last, so a name looking like a placeholder stays as it is
+    CONFIRMATION_TEMPLATE
+        .replace(
+            "generic_timeslot_booking_manager_name",
+            &escape_html(website_title),
+        )
+        .replace(
+            "generic_confirmation_date",
+            &start.format("%A, %-d %B %Y").to_string(),
+        )
+        .replace("generic_confirmation_time", &time)
+        .replace(
+            "generic_confirmation_code",
+            &escape_html(&booking.confirmation_code),
+        )
+        .replace("generic_confirmation_notes", &escape_html(&timeslot.notes))
+        .replace(
+            "generic_confirmation_booker_name",
+            &escape_html(&booking.booker_name),
+        )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn booked_timeslot(notes: &str, booker_name: &str) -> Timeslot {
+        Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc.with_ymd_and_hms(2030, 7, 1, 9, 30, 0).unwrap(),
+            available: false,
+            booker_name: booker_name.into(),
+            notes: notes.into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![Booking {
+                booker_name: booker_name.into(),
+                confirmation_code: "ABCD2345".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+                booked_at: None,
+                confirmed: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_to_confirmation_page() {
+        let timeslot = booked_timeslot("Workshop", "Stefan");
+        let page = to_confirmation_page(
+            "IT Project Week",
+            &timeslot,
+            &timeslot.bookings[0],
+            chrono_tz::Europe::Vienna,
+        );
+
+        assert!(page.contains("<h1>IT Project Week</h1>"));
+        assert!(page.contains("<td>Monday, 1 July 2030</td>"));
+        assert!(page.contains("<td>11:30 - 12:00 (Europe/Vienna)</td>"));
+        assert!(page.contains("<td>Stefan</td>"));
+        assert!(page.contains("<td>Workshop</td>"));
+        assert!(page.contains("<td class=\"code\">ABCD2345</td>"));
+        assert!(!page.contains("generic_"));
+    }
+
+    #[test]
+    fn test_escaped_values() {
+        let timeslot = booked_timeslot("<b>Workshop</b> & more", "generic_confirmation_code");
+        let page = to_confirmation_page("Title", &timeslot, &timeslot.bookings[0], Tz::UTC);
+
+        assert!(page.contains("<td>&lt;b&gt;Workshop&lt;/b&gt; &amp; more</td>"));
+        assert!(page.contains("<td>generic_confirmation_code</td>"));
+    }
+}
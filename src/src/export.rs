@@ -0,0 +1,85 @@
+use crate::types::Timeslot;
+use tracing::error;
+
+const CSV_HEADER: [&str; 5] = ["id", "datetime", "available", "booker_name", "notes"];
+
+/// Serializes the timeslots into CSV with one row per timeslot. Fields containing commas, quotes
+/// or line breaks are quoted.
+pub fn to_csv(timeslots: &[Timeslot]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    let result = writer.write_record(CSV_HEADER).and_then(|()| {
+        timeslots.iter().try_for_each(|timeslot| {
+            writer.write_record([
+                timeslot.id.to_string(),
+                timeslot.datetime.to_rfc3339(),
+                timeslot.available.to_string(),
+                timeslot.booker_name.clone(),
+                timeslot.notes.clone(),
+            ])
+        })
+    });
+    if let Err(err) = result {
+        error!(?err, "Failed to write CSV");
+        return Err("Failed to export timeslots".into());
+    }
+
+    let bytes = writer.into_inner().map_err(|err| {
+        error!(?err, "Failed to write CSV");
+        "Failed to export timeslots".to_string()
+    })?;
+    String::from_utf8(bytes).map_err(|err| {
+        error!(?err, "CSV is not valid UTF-8");
+        "Failed to export timeslots".into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    fn timeslot(notes: &str, booker_name: &str) -> Timeslot {
+        Timeslot {
+            id: Uuid::nil(),
+            datetime: "2030-01-31T09:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+            available: booker_name.is_empty(),
+            booker_name: booker_name.into(),
+            notes: notes.into(),
+            capacity: 1,
+            booked_count: i32::from(!booker_name.is_empty()),
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = to_csv(&[
+            timeslot("Workshop", ""),
+            timeslot("Room 1, \"Lab\"\nSecond floor", "Stefan"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            csv,
+            "id,datetime,available,booker_name,notes\n\
+            00000000-0000-0000-0000-000000000000,2030-01-31T09:00:00+00:00,true,,Workshop\n\
+            00000000-0000-0000-0000-000000000000,2030-01-31T09:00:00+00:00,false,Stefan,\
+            \"Room 1, \"\"Lab\"\"\nSecond floor\"\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_without_timeslots() {
+        assert_eq!(
+            to_csv(&[]).unwrap(),
+            "id,datetime,available,booker_name,notes\n"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    backend::{BackendError, TimeslotBackend},
+    local_timeslots::LocalTimeslots,
+    types::{Timeslot, TimeslotDraft, TimeslotEvent},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use tokio_stream::wrappers::{BroadcastStream, WatchStream};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Keeps the timeslots in memory like `LocalTimeslots` and writes them to a JSON file after every
+/// change, so they survive a restart without a database server.
+#[derive(Debug, Clone)]
+pub struct FileTimeslots {
+    timeslots: LocalTimeslots,
+    path: PathBuf,
+    // Serializes the writes, so an older state can't overwrite a newer one
+    file_lock: Arc<Mutex<()>>,
+}
+
+impl FileTimeslots {
+    pub fn new(
+        path: &str,
+        cleanup_retention: Duration,
+        allow_overlap: bool,
+        max_timeslots: Option<usize>,
+        hard_delete: bool,
+    ) -> Self {
+        let path = PathBuf::from(path);
+        Self {
+            timeslots: LocalTimeslots::with_timeslots(
+                Self::load(&path),
+                cleanup_retention,
+                allow_overlap,
+                max_timeslots,
+                hard_delete,
+            ),
+            path,
+            file_lock: Arc::default(),
+        }
+    }
+
+    fn load(path: &Path) -> HashMap<Uuid, Timeslot> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                info!(
+                    ?path,
+                    "Timeslot file does not exist yet. Starting without timeslots"
+                );
+                return HashMap::new();
+            }
+            Err(err) => {
+                warn!(
+                    ?err,
+                    ?path,
+                    "Failed to read timeslot file. Starting without timeslots"
+                );
+                return HashMap::new();
+            }
+        };
+        match serde_json::from_str(&contents) {
+            Ok(timeslots) => timeslots,
+            Err(err) => {
+                warn!(
+                    ?err,
+                    ?path,
+                    "Timeslot file is corrupt. Starting without timeslots"
+                );
+                HashMap::new()
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), BackendError> {
+        let _file_lock = self.file_lock.lock().unwrap();
+        let timeslots: HashMap<Uuid, Timeslot> = self
+            .timeslots
+            .current_timeslots()?
+            .into_iter()
+            .chain(self.timeslots.archived_timeslots()?)
+            .map(|timeslot| (timeslot.id, timeslot))
+            .collect();
+
+        // Write to a temporary file first, so a crash while writing can't corrupt the file
+        let temporary_path = self.path.with_extension("tmp");
+        let result = serde_json::to_string(&timeslots)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                fs::write(&temporary_path, contents).map_err(|err| err.to_string())
+            })
+            .and_then(|()| fs::rename(&temporary_path, &self.path).map_err(|err| err.to_string()));
+
+        if let Err(err) = result {
+            error!(?err, path = ?self.path, "Failed to save timeslots");
+            return Err("File Error. Timeslots can't be saved".into());
+        }
+        Ok(())
+    }
+}
+
+impl TimeslotBackend for FileTimeslots {
+    fn timeslot_stream(&self) -> WatchStream<Vec<Timeslot>> {
+        self.timeslots.timeslot_stream()
+    }
+
+    fn event_stream(&self) -> BroadcastStream<TimeslotEvent> {
+        self.timeslots.event_stream()
+    }
+
+    fn current_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.timeslots.current_timeslots()
+    }
+
+    fn timeslots_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Timeslot>, BackendError> {
+        self.timeslots.timeslots_between(from, to)
+    }
+
+    fn count_timeslots(&self, available_only: bool) -> Result<i64, BackendError> {
+        self.timeslots.count_timeslots(available_only)
+    }
+
+    fn book_timeslot(
+        &self,
+        id: Uuid,
+        booker_name: String,
+        client_email: Option<String>,
+        client_phone: Option<String>,
+        booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError> {
+        let booked_timeslot = self.timeslots.book_timeslot(
+            id,
+            booker_name,
+            client_email,
+            client_phone,
+            booker_notes,
+        )?;
+        self.save()?;
+        Ok(booked_timeslot)
+    }
+
+    fn book_first_available(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        booker_name: String,
+    ) -> Result<Timeslot, BackendError> {
+        let booked_timeslot = self.timeslots.book_first_available(from, to, booker_name)?;
+        self.save()?;
+        Ok(booked_timeslot)
+    }
+
+    fn cancel_booking(&self, id: Uuid) -> Result<Vec<String>, BackendError> {
+        let promoted_codes = self.timeslots.cancel_booking(id)?;
+        self.save()?;
+        Ok(promoted_codes)
+    }
+
+    fn cancel_own_booking(&self, confirmation_code: &str) -> Result<Vec<String>, BackendError> {
+        let promoted_codes = self.timeslots.cancel_own_booking(confirmation_code)?;
+        self.save()?;
+        Ok(promoted_codes)
+    }
+
+    fn swap_bookings(&self, first_id: Uuid, second_id: Uuid) -> Result<(), BackendError> {
+        self.timeslots.swap_bookings(first_id, second_id)?;
+        self.save()
+    }
+
+    fn confirm_booking(&self, id: Uuid) -> Result<(), BackendError> {
+        self.timeslots.confirm_booking(id)?;
+        self.save()
+    }
+
+    fn release_expired_holds(&self, cutoff: DateTime<Utc>) -> Result<usize, BackendError> {
+        let released = self.timeslots.release_expired_holds(cutoff)?;
+        if released > 0 {
+            self.save()?;
+        }
+        Ok(released)
+    }
+
+    fn join_waitlist(&self, id: Uuid, client_name: String) -> Result<usize, BackendError> {
+        let position = self.timeslots.join_waitlist(id, client_name)?;
+        self.save()?;
+        Ok(position)
+    }
+
+    fn add_timeslot(
+        &self,
+        datetime: DateTime<Utc>,
+        notes: String,
+        capacity: i32,
+        duration_minutes: i32,
+        internal_notes: Option<String>,
+        category: Option<String>,
+    ) -> Result<(), BackendError> {
+        self.timeslots.add_timeslot(
+            datetime,
+            notes,
+            capacity,
+            duration_minutes,
+            internal_notes,
+            category,
+        )?;
+        self.save()
+    }
+
+    fn check_timeslots_bulk(&self, drafts: &[TimeslotDraft]) -> Result<(), BackendError> {
+        self.timeslots.check_timeslots_bulk(drafts)
+    }
+
+    fn edit_timeslot(
+        &self,
+        id: Uuid,
+        datetime: DateTime<Utc>,
+        notes: String,
+        internal_notes: Option<String>,
+        version: i32,
+    ) -> Result<(), BackendError> {
+        self.timeslots
+            .edit_timeslot(id, datetime, notes, internal_notes, version)?;
+        self.save()
+    }
+
+    fn update_notes(&self, id: Uuid, notes: String) -> Result<(), BackendError> {
+        self.timeslots.update_notes(id, notes)?;
+        self.save()
+    }
+
+    fn reschedule(&self, id: Uuid, datetime: DateTime<Utc>) -> Result<(), BackendError> {
+        self.timeslots.reschedule(id, datetime)?;
+        self.save()
+    }
+
+    fn remove_timeslot(&self, id: Uuid) -> Result<(), BackendError> {
+        self.timeslots.remove_timeslot(id)?;
+        self.save()
+    }
+
+    fn archived_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.timeslots.archived_timeslots()
+    }
+
+    fn remove_all_timeslot(&self) -> Result<(), BackendError> {
+        self.timeslots.remove_all_timeslot()?;
+        self.save()
+    }
+
+    fn restore_last(&self) -> Result<(), BackendError> {
+        self.timeslots.restore_last()?;
+        self.save()
+    }
+
+    fn count_bookings(&self, client_name: &str) -> Result<usize, BackendError> {
+        self.timeslots.count_bookings(client_name)
+    }
+
+    fn remove_outdated_timeslots(&self) -> Result<usize, BackendError> {
+        let removed = self.timeslots.remove_outdated_timeslots()?;
+        if removed > 0 {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    fn search_bookings(&self, search: &str) -> Result<Vec<Timeslot>, BackendError> {
+        self.timeslots.search_bookings(search)
+    }
+
+    fn find_timeslot(&self, id: Uuid) -> Result<Option<Timeslot>, BackendError> {
+        self.timeslots.find_timeslot(id)
+    }
+
+    fn find_by_confirmation(&self, code: &str) -> Result<Option<Timeslot>, BackendError> {
+        self.timeslots.find_by_confirmation(code)
+    }
+
+    fn health_check(&self) -> Result<(), BackendError> {
+        self.timeslots.health_check()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testutils::read_from_timeslot_stream;
+    use tempfile::TempDir;
+
+    fn timeslot_file(directory: &TempDir) -> String {
+        directory
+            .path()
+            .join("timeslots.json")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_persistence_across_reconstruction() {
+        let directory = TempDir::new().unwrap();
+        let path = timeslot_file(&directory);
+
+        let file_timeslots = FileTimeslots::new(&path, Duration::days(1), true, None, false);
+        let datetime = Utc::now() + Duration::hours(1);
+        file_timeslots
+            .add_timeslot(datetime, "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        file_timeslots
+            .add_timeslot(datetime, "Second Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        let id = file_timeslots.current_timeslots().unwrap()[0].id;
+        let confirmation_code = file_timeslots
+            .book_timeslot(id, "Stefan".into(), None, None, None)
+            .unwrap()
+            .bookings[0]
+            .confirmation_code
+            .clone();
+        drop(file_timeslots);
+
+        let file_timeslots = FileTimeslots::new(&path, Duration::days(1), true, None, false);
+        let mut timeslot_stream = file_timeslots.timeslot_stream();
+        let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(timeslots.len(), 2);
+        let booked = file_timeslots
+            .find_by_confirmation(&confirmation_code)
+            .unwrap()
+            .unwrap();
+        assert_eq!(booked.id, id);
+        assert_eq!(booked.booker_name, "Stefan");
+
+        file_timeslots.remove_all_timeslot().unwrap();
+        let file_timeslots = FileTimeslots::new(&path, Duration::days(1), true, None, false);
+        assert!(file_timeslots.current_timeslots().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_file() {
+        let directory = TempDir::new().unwrap();
+        let path = timeslot_file(&directory);
+        fs::write(&path, "{ not json").unwrap();
+
+        let file_timeslots = FileTimeslots::new(&path, Duration::days(1), true, None, false);
+        assert!(file_timeslots.current_timeslots().unwrap().is_empty());
+
+        // The corrupt file is replaced on the next change
+        file_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "First Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let file_timeslots = FileTimeslots::new(&path, Duration::days(1), true, None, false);
+        assert_eq!(file_timeslots.current_timeslots().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let directory = TempDir::new().unwrap();
+        let file_timeslots = FileTimeslots::new(
+            &timeslot_file(&directory),
+            Duration::days(1),
+            true,
+            None,
+            false,
+        );
+        assert!(file_timeslots.current_timeslots().unwrap().is_empty());
+    }
+}
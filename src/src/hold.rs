@@ -0,0 +1,78 @@
+use crate::backend::{run_blocking, TimeslotBackend};
+use chrono::{Duration, Utc};
+use tracing::{error, info};
+
+/// How often unconfirmed bookings are checked for an expired hold
+pub const HOLD_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Periodically releases the bookings which weren't confirmed within `hold`. Runs until the task
+/// is dropped, e.g. next to the server.
+pub async fn release_expired_holds<T: TimeslotBackend>(
+    backend: T,
+    hold: Duration,
+    check_interval: std::time::Duration,
+) {
+    let mut interval = tokio::time::interval(check_interval);
+    loop {
+        interval.tick().await;
+        let cutoff = Utc::now() - hold;
+        match run_blocking(&backend, move |backend| {
+            backend.release_expired_holds(cutoff)
+        })
+        .await
+        {
+            Ok(0) => {}
+            Ok(released) => info!(released, "Released unconfirmed bookings"),
+            Err(err) => error!(?err, "Failed to release unconfirmed bookings"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::local_timeslots::LocalTimeslots;
+
+    #[tokio::test]
+    async fn test_release_expired_holds() {
+        let backend = LocalTimeslots::new(Duration::days(1), true, None, false);
+        let datetime = Utc::now() + Duration::hours(1);
+        backend
+            .add_timeslot(datetime, "Unconfirmed".into(), 1, 30, None, None)
+            .unwrap();
+        backend
+            .add_timeslot(datetime, "Confirmed".into(), 1, 30, None, None)
+            .unwrap();
+        for timeslot in backend.current_timeslots().unwrap() {
+            backend
+                .book_timeslot(timeslot.id, "Stefan".into(), None, None, None)
+                .unwrap();
+            if timeslot.notes == "Confirmed" {
+                backend.confirm_booking(timeslot.id).unwrap();
+            }
+        }
+
+        let task = tokio::spawn(release_expired_holds(
+            backend.clone(),
+            Duration::milliseconds(200),
+            std::time::Duration::from_millis(50),
+        ));
+
+        // Still held
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(backend
+            .current_timeslots()
+            .unwrap()
+            .iter()
+            .all(|timeslot| timeslot.booked_count == 1));
+
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        for timeslot in backend.current_timeslots().unwrap() {
+            let released = timeslot.notes == "Unconfirmed";
+            assert_eq!(timeslot.booked_count == 0, released);
+            assert_eq!(timeslot.available, released);
+        }
+
+        task.abort();
+    }
+}
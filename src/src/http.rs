@@ -1,443 +1,7313 @@
-use crate::backend::TimeslotBackend;
-use crate::configuration::Configuration;
+use crate::api_error::ApiError;
+use crate::auth_lockout::{AuthAttempt, AuthLockout};
+use crate::authentication::{generate_key, Admin, AdminPassword, Secret, DEFAULT_ADMIN_NAME};
+use crate::backend::{run_blocking, BackendError, TimeslotBackend};
+use crate::calendar::to_icalendar;
+use crate::configuration::{BusinessHours, Configuration};
+use crate::confirmation::to_confirmation_page;
+use crate::export::to_csv;
+use crate::idempotency::{IdempotencyCache, IDEMPOTENCY_TTL, MAX_IDEMPOTENCY_KEYS};
+use crate::import::from_csv;
+use crate::messages::Language;
+use crate::monitoring::{
+    prometheus_handle, AUTH_FAILURES_TOTAL, BOOKINGS_TOTAL, REQUEST_DURATION_SECONDS,
+    TIMESLOTS_ADDED_TOTAL,
+};
+use crate::rate_limit::RateLimiter;
+use crate::recurrence::{self, Interval};
+use crate::shutdown::ShutdownSignal;
+use crate::types::{Timeslot, TimeslotDraft, TimeslotResponse};
+use crate::webhook::Webhook;
 use axum::body::Body;
-use axum::extract::Request;
+use axum::error_handling::HandleErrorLayer;
+use axum::extract::{
+    ConnectInfo, DefaultBodyLimit, Extension, FromRequest, MatchedPath, Path, Query, Request,
+};
 use axum::middleware::{self, Next};
-use axum::response::sse::{Event, Sse};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{Html, Response};
-use axum::routing::delete;
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::routing::{delete, patch, put};
+use axum::{
+    async_trait,
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    response::IntoResponse,
+    BoxError, Form, Json,
+};
 use axum::{
     routing::{get, post},
     Router,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Offset, Utc};
+use chrono_tz::Tz;
 use futures::stream::Stream;
+use metrics_exporter_prometheus::PrometheusHandle;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::convert::Infallible;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
 use tokio::fs;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::StreamExt;
-use tower_http::cors::{Any, CorsLayer};
-use tracing::{debug, error};
+use tower::limit::GlobalConcurrencyLimitLayer;
+use tower::load_shed::LoadShedLayer;
+use tower::ServiceBuilder;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tracing::{debug, error, event, field, info, info_span, warn, Instrument, Level, Span};
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidateArgs, ValidationError, ValidationErrors, ValidationErrorsKind};
 
 const VALID_NAMES: &str = r"^[\p{L}0-9 .!?-@_]+$";
+// E.164: a leading "+", the country code and at most 15 digits in total
+const VALID_PHONE: &str = r"^\+[1-9][0-9]{1,14}$";
 const VALID_NOTES: &str = r"^[\p{L}0-9 .!?@_#%*\-()+=:~\n£€¥$¢]+$";
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+const FALLBACK_FRONTEND: &str = include_str!("../frontend/fallback.html");
 
 #[derive(Clone)]
 pub struct AppState<T: TimeslotBackend, S: Configuration> {
     pub backend: T,
     pub configuration: S,
+    pub rate_limiter: RateLimiter,
+    pub auth_lockout: AuthLockout,
+    pub webhook: Option<Webhook>,
+    pub shutdown: ShutdownSignal,
+    pub metrics: PrometheusHandle,
+    pub text_rules: TextRules,
+    /// Results of `/book` by their `Idempotency-Key` header
+    pub bookings_by_idempotency_key: IdempotencyCache<Result<BookingResponse, ApiError>>,
+    pub frontend_cache: Arc<Mutex<Option<RenderedFrontend>>>,
+    /// Id of the last event sent on any timeslot stream, increasing during a server run
+    pub last_event_id: Arc<AtomicU64>,
+}
+
+/// The frontend file with the configured values filled in
+#[derive(Debug, Clone)]
+pub struct RenderedFrontend {
+    path: PathBuf,
+    /// Modification time of the file when it was read
+    modified: SystemTime,
+    html: String,
+    etag: String,
+}
+
+impl RenderedFrontend {
+    fn new(path: PathBuf, modified: SystemTime, html: String) -> Self {
+        let mut hasher = DefaultHasher::new();
+        html.hash(&mut hasher);
+        Self {
+            path,
+            modified,
+            etag: format!("\"{:016x}\"", hasher.finish()),
+            html,
+        }
+    }
+}
+
+/// Allowed length and characters of client names and timeslot notes, see `Configuration`
+#[derive(Debug, Clone)]
+pub struct TextRules {
+    name_max_len: usize,
+    name_pattern: Regex,
+    notes_max_len: usize,
+    notes_pattern: Regex,
+}
+
+impl TextRules {
+    pub fn new<S: Configuration>(configuration: &S) -> Result<Self, String> {
+        let compile = |pattern: Option<String>, default: &str| {
+            let pattern = pattern.unwrap_or_else(|| default.into());
+            Regex::new(&pattern)
+                .map_err(|err| format!("Invalid regular expression \"{pattern}\": {err}"))
+        };
+        Ok(Self {
+            name_max_len: configuration.name_max_len(),
+            name_pattern: compile(configuration.name_pattern(), VALID_NAMES)?,
+            notes_max_len: configuration.notes_max_len(),
+            notes_pattern: compile(configuration.notes_pattern(), VALID_NOTES)?,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
 struct BookingRequest {
     id: Uuid,
-    #[validate(
-        length(min = 1, max = 20),
-        regex(path = Regex::new(VALID_NAMES).unwrap(), message = "Invalid characters in name")
-    )]
+    #[validate(custom(function = "validate_name", use_context))]
+    client_name: String,
+    #[serde(default)]
+    #[validate(email(message = "Invalid email address"))]
+    client_email: Option<String>,
+    #[serde(default)]
+    #[validate(regex(
+        path = Regex::new(VALID_PHONE).unwrap(),
+        message = "Phone number must be in international format, e.g. +436641234567"
+    ))]
+    client_phone: Option<String>,
+    /// Only visible to admins, e.g. special requests
+    #[serde(default)]
+    #[validate(custom(function = "validate_notes", use_context))]
+    booker_notes: Option<String>,
+}
+
+/// Extracts the body as JSON or, if it is sent as `application/x-www-form-urlencoded` (e.g. by a
+/// plain HTML form), as form data
+struct JsonOrForm<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonOrForm<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_form = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| {
+                content_type.starts_with("application/x-www-form-urlencoded")
+            });
+        if is_form {
+            let Form(value) = Form::from_request(request, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            return Ok(Self(value));
+        }
+        let Json(value) = Json::from_request(request, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(Self(value))
+    }
+}
+
+struct ValidationContext {
+    /// Timeslots must not start before this point in time
+    earliest: DateTime<Utc>,
+    /// New timeslots must not start after this point in time
+    latest: Option<DateTime<Utc>>,
+    text_rules: TextRules,
+    /// New timeslots must start within these, in the display timezone
+    business_hours: Option<BusinessHours>,
+    display_timezone: Tz,
+    /// Any category is allowed if empty
+    allowed_categories: Vec<String>,
+}
+
+/// Tolerates a clock skew between client and server of up to the configured grace period
+fn validation_context<T: TimeslotBackend, S: Configuration>(
+    state: &AppState<T, S>,
+) -> ValidationContext {
+    ValidationContext {
+        earliest: Utc::now() - chrono::Duration::minutes(state.configuration.past_grace_minutes()),
+        latest: state
+            .configuration
+            .max_future_days()
+            .map(|days| Utc::now() + chrono::Duration::days(days)),
+        text_rules: state.text_rules.clone(),
+        business_hours: state.configuration.business_hours(),
+        display_timezone: display_timezone(&state.configuration),
+        allowed_categories: state.configuration.allowed_categories(),
+    }
+}
+
+fn display_timezone<S: Configuration>(configuration: &S) -> Tz {
+    configuration.display_timezone().parse().unwrap_or(Tz::UTC)
+}
+
+/// Rounds the start to the nearest multiple of `granularity_minutes` on the wall clock of the
+/// timezone, e.g. 10:07 to 10:00 and 10:08 to 10:15 for 15 minutes. Starts exactly between two
+/// boundaries are rounded up, seconds are dropped.
+fn align_to_granularity(
+    datetime: DateTime<Utc>,
+    granularity_minutes: i64,
+    timezone: Tz,
+) -> DateTime<Utc> {
+    let offset = i64::from(
+        datetime
+            .with_timezone(&timezone)
+            .offset()
+            .fix()
+            .local_minus_utc(),
+    );
+    let granularity = granularity_minutes * 60;
+    let local_seconds = datetime.timestamp() + offset;
+    let aligned = (local_seconds + granularity / 2).div_euclid(granularity) * granularity;
+    DateTime::from_timestamp(aligned - offset, 0).unwrap_or(datetime)
+}
+
+/// Aligns the start of a new timeslot to the configured slot granularity, unless unaligned
+/// starts are rejected instead.
+fn align_start<S: Configuration>(
+    configuration: &S,
+    datetime: DateTime<Utc>,
+) -> Result<DateTime<Utc>, ApiError> {
+    let Some(granularity_minutes) = configuration.slot_granularity_minutes() else {
+        return Ok(datetime);
+    };
+    let aligned = align_to_granularity(
+        datetime,
+        granularity_minutes,
+        display_timezone(configuration),
+    );
+    if aligned != datetime {
+        if configuration.reject_unaligned_slots() {
+            error!(%datetime, granularity_minutes, "Timeslot is not aligned");
+            return Err(ApiError::bad_request(
+                "unaligned_datetime",
+                format!("Timeslot must start at a multiple of {granularity_minutes} minutes"),
+            ));
+        }
+        debug!(%datetime, %aligned, "Aligning timeslot to the slot granularity");
+    }
+    Ok(aligned)
+}
+
+fn validate_text(
+    text: &str,
+    max_len: usize,
+    pattern: &Regex,
+    message: &'static str,
+) -> Result<(), ValidationError> {
+    let len = text.chars().count();
+    if len < 1 || len > max_len {
+        let mut err = ValidationError::new("length");
+        err.add_param("min".into(), &1);
+        err.add_param("max".into(), &max_len);
+        return Err(err);
+    }
+    if !pattern.is_match(text) {
+        return Err(ValidationError::new("regex").with_message(message.into()));
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str, context: &ValidationContext) -> Result<(), ValidationError> {
+    let rules = &context.text_rules;
+    validate_text(
+        name,
+        rules.name_max_len,
+        &rules.name_pattern,
+        "Invalid characters in name",
+    )
+}
+
+fn validate_notes(notes: &str, context: &ValidationContext) -> Result<(), ValidationError> {
+    let rules = &context.text_rules;
+    validate_text(
+        notes,
+        rules.notes_max_len,
+        &rules.notes_pattern,
+        "Invalid characters in notes",
+    )
+}
+
+fn validate_category(category: &str, context: &ValidationContext) -> Result<(), ValidationError> {
+    validate_notes(category, context)?;
+    if !context.allowed_categories.is_empty()
+        && !context
+            .allowed_categories
+            .iter()
+            .any(|allowed| allowed == category)
+    {
+        return Err(ValidationError::new("category_not_allowed").with_message(
+            format!(
+                "Category must be one of: {}",
+                context.allowed_categories.join(", ")
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_not_in_past(
+    datetime: &DateTime<Utc>,
+    context: &ValidationContext,
+) -> Result<(), ValidationError> {
+    if *datetime < context.earliest {
+        return Err(ValidationError::new("in_past")
+            .with_message("Timeslot must not start in the past".into()));
+    }
+    Ok(())
+}
+
+fn validate_new_timeslot_start(
+    datetime: &DateTime<Utc>,
+    context: &ValidationContext,
+) -> Result<(), ValidationError> {
+    validate_not_in_past(datetime, context)?;
+    if context.latest.is_some_and(|latest| *datetime > latest) {
+        return Err(ValidationError::new("too_far_in_future")
+            .with_message("Timeslot must not start that far in the future".into()));
+    }
+    if let Some(business_hours) = &context.business_hours {
+        let start = datetime
+            .with_timezone(&context.display_timezone)
+            .naive_local();
+        if !business_hours.contains(start) {
+            return Err(ValidationError::new("outside_business_hours")
+                .with_message("Timeslot must start within the business hours".into()));
+        }
+    }
+    Ok(())
+}
+
+/// Books the earliest available timeslot starting from `from` to `to` (both inclusive)
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
+struct BookAnyRequest {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    #[validate(custom(function = "validate_name", use_context))]
+    client_name: String,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
+struct WaitlistRequest {
+    id: Uuid,
+    #[validate(custom(function = "validate_name", use_context))]
     client_name: String,
 }
 
 #[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
 struct AddTimeslotRequest {
+    #[validate(custom(function = "validate_new_timeslot_start", use_context))]
+    datetime: DateTime<Utc>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_notes", use_context))]
+    notes: String,
+    #[serde(default = "default_capacity")]
+    #[validate(range(min = 1))]
+    capacity: i32,
+    #[serde(default = "default_duration_minutes")]
+    #[validate(range(min = 1))]
+    duration_minutes: i32,
+    /// Only visible to admins
+    #[serde(default)]
+    #[validate(custom(function = "validate_notes", use_context))]
+    internal_notes: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_category", use_context))]
+    category: Option<String>,
+}
+
+/// The slots are validated separately, as nested validation doesn't support a context
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+struct AddTimeslotsBulkRequest {
+    #[validate(length(min = 1))]
+    slots: Vec<AddTimeslotRequest>,
+}
+
+impl AddTimeslotsBulkRequest {
+    fn validate_with_slots(&self, context: &ValidationContext) -> Result<(), ValidationErrors> {
+        let mut errors = self.validate().err().unwrap_or_default();
+        let slot_errors: BTreeMap<usize, Box<ValidationErrors>> = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                slot.validate_with_args(context)
+                    .err()
+                    .map(|err| (index, Box::new(err)))
+            })
+            .collect();
+        if !slot_errors.is_empty() {
+            errors
+                .0
+                .insert("slots".into(), ValidationErrorsKind::List(slot_errors));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
+struct AddRecurringTimeslotsRequest {
+    datetime: DateTime<Utc>,
+    interval: Interval,
+    #[validate(range(min = 1, max = 366))]
+    count: u32,
+    #[validate(custom(function = "validate_notes", use_context))]
+    notes: String,
+    #[serde(default = "default_capacity")]
+    #[validate(range(min = 1))]
+    capacity: i32,
+    #[serde(default = "default_duration_minutes")]
+    #[validate(range(min = 1))]
+    duration_minutes: i32,
+    #[serde(default)]
+    #[validate(custom(function = "validate_notes", use_context))]
+    internal_notes: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_category", use_context))]
+    category: Option<String>,
+    /// IANA timezone whose wall clock is used for stepping, e.g. "Europe/Vienna"
+    #[serde(default)]
+    timezone: Option<String>,
+}
+
+fn default_capacity() -> i32 {
+    1
+}
+
+fn default_duration_minutes() -> i32 {
+    30
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
+struct EditTimeslotRequest {
+    id: Uuid,
+    #[validate(custom(function = "validate_new_timeslot_start", use_context))]
     datetime: DateTime<Utc>,
-    #[validate(
-        length(min = 1, max = 60),
-        regex(path = Regex::new(VALID_NOTES).unwrap(), message = "Invalid characters in notes")
-    )]
+    #[validate(custom(function = "validate_notes", use_context))]
+    notes: String,
+    #[serde(default)]
+    #[validate(custom(function = "validate_notes", use_context))]
+    internal_notes: Option<String>,
+    /// Version of the timeslot the edit is based on
+    version: i32,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
+struct UpdateNotesRequest {
+    id: Uuid,
+    #[validate(custom(function = "validate_notes", use_context))]
     notes: String,
 }
 
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(context = ValidationContext)]
+struct RescheduleRequest {
+    id: Uuid,
+    #[validate(custom(function = "validate_not_in_past", use_context))]
+    new_datetime: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SwapBookingsRequest {
+    first_id: Uuid,
+    second_id: Uuid,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DeleteTimeslotRequest {
     id: Uuid,
 }
 
-pub fn create_app<T: TimeslotBackend, S: Configuration>(backend: T, configuration: S) -> Router {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CancelBookingRequest {
+    id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConfirmBookingRequest {
+    id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CancelOwnBookingRequest {
+    code: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortOrder {
+    #[default]
+    DatetimeAsc,
+    DatetimeDesc,
+    /// Alphabetically by booker name, unbooked timeslots first
+    Booker,
+}
+
+/// Body of the snapshot endpoint
+#[derive(Debug, Serialize)]
+struct SnapshotResponse {
+    timeslots: Vec<TimeslotResponse>,
+    /// Configured message, only present if there are no timeslots at all, regardless of the
+    /// filters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SnapshotQuery {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    #[serde(default)]
+    sort: SortOrder,
+    /// Only timeslots of this category
+    category: Option<String>,
+}
+
+impl SnapshotQuery {
+    /// Applies the category filter, sort order and pagination to timeslots sorted by datetime. The date range is
+    /// already applied by the backend query.
+    fn apply(&self, mut timeslots: Vec<Timeslot>) -> Vec<Timeslot> {
+        if let Some(category) = &self.category {
+            timeslots.retain(|timeslot| timeslot.category.as_ref() == Some(category));
+        }
+        match self.sort {
+            SortOrder::DatetimeAsc => {}
+            SortOrder::DatetimeDesc => timeslots.reverse(),
+            // Stable, so timeslots of the same booker stay sorted by datetime
+            SortOrder::Booker => {
+                timeslots.sort_by_key(|timeslot| timeslot.booker_name.to_lowercase())
+            }
+        }
+        timeslots
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TimeslotStreamQuery {
+    /// Leaves out timeslots which can't be booked anymore
+    #[serde(default)]
+    available_only: bool,
+    /// Only timeslots of this category
+    category: Option<String>,
+}
+
+impl TimeslotStreamQuery {
+    fn matches(&self, timeslot: &Timeslot) -> bool {
+        (!self.available_only || timeslot.available)
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|category| timeslot.category.as_ref() == Some(category))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CountQuery {
+    /// Only counts timeslots which can still be booked
+    #[serde(default)]
+    available_only: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DryRunQuery {
+    /// Only checks the timeslots and responds with the timeslots which would be added
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BookingsQuery {
+    /// Only bookings on this day (UTC)
+    date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConfirmationQuery {
+    /// Confirmation code of the booking, proving the client made it
+    code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SearchQuery {
+    /// Part of the client name, case is ignored
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CountResponse {
+    count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct StatsResponse {
+    total_slots: usize,
+    /// Timeslots without free places
+    booked_slots: usize,
+    available_slots: usize,
+    occupancy_percent: f64,
+}
+
+impl StatsResponse {
+    fn new(timeslots: &[Timeslot]) -> Self {
+        let total_slots = timeslots.len();
+        let booked_slots = timeslots
+            .iter()
+            .filter(|timeslot| !timeslot.available)
+            .count();
+        let occupancy_percent = if total_slots == 0 {
+            0.0
+        } else {
+            booked_slots as f64 * 100.0 / total_slots as f64
+        };
+        Self {
+            total_slots,
+            booked_slots,
+            available_slots: total_slots - booked_slots,
+            occupancy_percent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookingResponse {
+    message: String,
+    confirmation_code: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BookAnyResponse {
+    message: String,
+    confirmation_code: String,
+    /// The booked timeslot, as the client didn't pick it
+    timeslot: TimeslotResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WaitlistResponse {
+    message: String,
+    /// Position on the waitlist, starting at 1
+    position: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RotateKeyResponse {
+    message: String,
+    key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReadOnlyRequest {
+    read_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AddTimeslotsBulkResponse {
+    message: String,
+    count: usize,
+}
+
+/// Identifies the running build, provided by the build script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct VersionResponse {
+    version: String,
+    git_hash: String,
+    build_timestamp: Option<DateTime<Utc>>,
+}
+
+impl VersionResponse {
+    fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").into(),
+            git_hash: env!("GIT_HASH").into(),
+            build_timestamp: env!("BUILD_TIMESTAMP")
+                .parse()
+                .ok()
+                .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
+        }
+    }
+}
+
+pub fn create_app<T: TimeslotBackend, S: Configuration>(
+    backend: T,
+    configuration: S,
+    shutdown: ShutdownSignal,
+) -> Router {
+    let allowed_origins = configuration.allowed_origins();
+    let allow_origin = if allowed_origins.is_empty() {
+        AllowOrigin::from(Any)
+    } else {
+        AllowOrigin::list(allowed_origins.iter().filter_map(|origin| {
+            HeaderValue::from_str(origin)
+                .inspect_err(|err| error!(?err, origin, "Ignoring invalid CORS origin"))
+                .ok()
+        }))
+    };
+    let rate_limiter = RateLimiter::new(configuration.rate_limit_per_minute());
+    let auth_lockout = AuthLockout::new(
+        configuration.auth_lockout_attempts(),
+        std::time::Duration::from_secs(configuration.auth_lockout_window_seconds()),
+        std::time::Duration::from_secs(configuration.auth_lockout_seconds()),
+    );
+    let access_log_level = configuration.access_log_level();
+    let max_concurrent_requests = configuration.max_concurrent_requests();
+    let webhook = configuration.webhook_url().map(|url| {
+        Webhook::new(
+            url,
+            configuration.webhook_max_attempts(),
+            std::time::Duration::from_millis(configuration.webhook_retry_delay_ms()),
+            std::time::Duration::from_millis(configuration.webhook_timeout_ms()),
+        )
+    });
+    let text_rules = TextRules::new(&configuration).unwrap_or_else(|err| panic!("{err}"));
     let state = AppState {
         backend,
         configuration,
+        rate_limiter,
+        auth_lockout,
+        webhook,
+        shutdown,
+        metrics: prometheus_handle(),
+        text_rules,
+        bookings_by_idempotency_key: IdempotencyCache::new(IDEMPOTENCY_TTL, MAX_IDEMPOTENCY_KEYS),
+        frontend_cache: Arc::default(),
+        last_event_id: Arc::default(),
     };
 
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(allow_origin)
         .allow_methods(Any)
         .allow_headers(Any);
 
+    // Bounds the body of the mutating routes, so a huge payload can't exhaust the memory. The
+    // default limit of axum is replaced to allow configuring a larger one.
+    let body_limit = (
+        DefaultBodyLimit::disable(),
+        RequestBodyLimitLayer::new(state.configuration.max_body_bytes()),
+    );
+
+    let public_mutations = Router::new()
+        .route("/book", post(book_timeslot))
+        .route("/book_any", post(book_any))
+        .route("/waitlist", post(join_waitlist))
+        .route("/cancel_own", post(cancel_own_booking))
+        .route_layer(middleware::from_fn_with_state(state.clone(), read_only))
+        .layer(body_limit);
     let public = Router::new()
         .route("/", get(get_frontend))
         .route("/timeslots", get(get_timeslots))
-        .route("/book", post(book_timeslot));
+        .route("/timeslots_snapshot", get(get_timeslots_snapshot))
+        .route("/timeslots_by_day", get(get_timeslots_by_day))
+        .route("/count", get(get_count))
+        .route("/calendar.ics", get(get_calendar))
+        .route("/booking/:code", get(get_booking))
+        .route("/confirmation/:id", get(get_confirmation))
+        .merge(public_mutations)
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit));
 
-    let admin = Router::new()
-        .route("/admin_page", get(get_admin_page))
+    // Probes for container orchestration and the build version, neither rate limited nor
+    // protected
+    let probes = Router::new()
+        .route("/health", get(get_health))
+        .route("/ready", get(get_ready))
+        .route("/version", get(get_version))
+        .route("/metrics", get(get_metrics));
+
+    let admin_mutations = Router::new()
+        .route("/cancel", post(cancel_booking))
+        .route("/confirm", post(confirm_booking))
         .route("/add", post(add_timeslot))
+        .route("/add_bulk", post(add_timeslots_bulk))
+        .route("/import.csv", post(import_timeslots))
+        .route("/add_recurring", post(add_recurring_timeslots))
+        .route("/edit", put(edit_timeslot))
+        .route("/notes", patch(update_notes))
+        .route("/reschedule", post(reschedule))
+        .route("/swap", post(swap_bookings))
         .route("/remove", delete(remove_timeslot))
         .route("/remove_all", post(remove_all_timeslot))
+        .route("/restore_last", post(restore_last))
+        // Only applies to the routes above, so the read-only mode can still be switched off
+        .route_layer(middleware::from_fn_with_state(state.clone(), read_only))
+        .route("/admin/rotate_key", post(rotate_key))
+        .route("/admin/read_only", post(set_read_only))
+        .layer(body_limit);
+    let admin = Router::new()
+        .route("/admin_page", get(get_admin_page))
+        .route("/admin_timeslots", get(get_admin_timeslots))
+        .route("/bookings", get(get_bookings))
+        .route("/search", get(search_bookings))
+        .route("/stats", get(get_stats))
+        .route("/export.csv", get(get_export))
+        .route("/archived", get(get_archived_timeslots))
+        .route("/events", get(get_events))
+        .merge(admin_mutations)
         .route_layer(middleware::from_fn_with_state(state.clone(), admin_auth));
 
+    // The probes stay at the root, where e.g. the container orchestration expects them
+    let base_path = state.configuration.base_path();
+    let routes = public.merge(admin);
+    let routes = if base_path.is_empty() {
+        routes
+    } else {
+        Router::new().nest(&base_path, routes)
+    };
+
     Router::new()
-        .merge(public)
-        .merge(admin)
+        .merge(routes)
+        .merge(probes)
         .with_state(state)
+        .layer(middleware::from_fn(track_latency))
+        .layer(middleware::from_fn_with_state(access_log_level, access_log))
         .layer(cors)
+        // Rejects requests beyond the limit right away instead of queueing them, so a traffic
+        // spike can't exhaust the memory. The limit is shared by all routes. The live streams only
+        // count until their first response.
+        .layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(overloaded))
+                .layer(LoadShedLayer::new())
+                .layer(GlobalConcurrencyLimitLayer::new(max_concurrent_requests)),
+        )
+        // Compresses responses for clients sending `Accept-Encoding`. The default predicate leaves
+        // out the SSE stream, whose events would otherwise be held back in the compression buffer
+        .layer(CompressionLayer::new())
+        // Layers run from the bottom up: the id is set first, so the span and the response
+        // header can use it. An id sent by the client is kept.
+        .layer(middleware::from_fn(request_span))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
 }
 
-async fn admin_auth<T: TimeslotBackend, S: Configuration>(
-    State(state): State<AppState<T, S>>,
-    request: Request<Body>,
-    next: Next,
-) -> Result<Response, (StatusCode, String)> {
-    let password = state.configuration.password();
-
-    if let Some(auth_header) = request.headers().get("x-admin-password") {
-        if auth_header.to_str().unwrap_or("") != password {
-            error!("Authorization failed");
-            return Err((StatusCode::UNAUTHORIZED, "Unauthorized".to_string()));
-        }
-    } else {
-        error!("Authorization failed: Missing credentials");
-        return Err((StatusCode::UNAUTHORIZED, "Missing credentials".to_string()));
-    }
-    Ok(next.run(request).await)
+async fn overloaded(err: BoxError) -> ApiError {
+    error!(%err, "Request rejected: Too many requests in progress");
+    ApiError::overloaded()
 }
 
-async fn get_timeslots<T: TimeslotBackend, S: Configuration>(
-    State(state): State<AppState<T, S>>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    debug!("Starting SSE timeslot stream");
+async fn track_latency(request: Request<Body>, next: Next) -> Response {
+    // The route pattern instead of the actual path keeps the number of label values bounded
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".into());
+    let method = request.method().to_string();
 
-    Sse::new(
-        state
-            .backend
-            .timeslot_stream()
-            .map(|timeslots| Ok(Event::default().json_data(timeslots).unwrap())),
+    let start = Instant::now();
+    let response = next.run(request).await;
+    metrics::histogram!(
+        REQUEST_DURATION_SECONDS,
+        "method" => method,
+        "path" => path,
+        "status" => response.status().as_u16().to_string(),
     )
+    .record(start.elapsed().as_secs_f64());
+    response
 }
 
-async fn book_timeslot<T: TimeslotBackend, S: Configuration>(
-    State(state): State<AppState<T, S>>,
-    Json(booking): Json<BookingRequest>,
-) -> impl IntoResponse {
-    debug!("Book timeslot");
-    if let Err(err) = booking.validate() {
-        error!(?err, "Invalid input");
-        return (StatusCode::BAD_REQUEST, format!("Invalid input: {err:?}"));
-    }
+/// Logs every request with its status and how long it took to respond.
+async fn access_log(State(level): State<Level>, request: Request<Body>, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
 
-    match state.backend.book_timeslot(booking.id, booking.client_name) {
-        Ok(()) => (StatusCode::OK, "Timeslot booked successfully".to_string()),
-        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
+    let start = Instant::now();
+    let response = next.run(request).await;
+    let status = response.status().as_u16();
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    // The level of an event must be known at compile time
+    macro_rules! log_request {
+        ($level:expr) => {
+            event!($level, %method, path, status, latency_ms, "Request handled")
+        };
     }
+    match level {
+        Level::ERROR => log_request!(Level::ERROR),
+        Level::WARN => log_request!(Level::WARN),
+        Level::INFO => log_request!(Level::INFO),
+        Level::DEBUG => log_request!(Level::DEBUG),
+        Level::TRACE => log_request!(Level::TRACE),
+    }
+    response
 }
 
-async fn add_timeslot<T: TimeslotBackend, S: Configuration>(
+/// Wraps each request in a span, so all logs of a request can be correlated via its id.
+async fn request_span(request: Request<Body>, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|request_id| request_id.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let span = info_span!(
+        "request",
+        request_id,
+        method = %request.method(),
+        uri = %request.uri(),
+        admin = field::Empty,
+    );
+    next.run(request).instrument(span).await
+}
+
+/// Name of the admin who sent the request, added to the request extensions by `admin_auth`
+#[derive(Debug, Clone)]
+struct AuthenticatedAdmin(String);
+
+async fn admin_auth<T: TimeslotBackend, S: Configuration>(
     State(state): State<AppState<T, S>>,
-    Json(timeslot): Json<AddTimeslotRequest>,
-) -> impl IntoResponse {
-    debug!("Add timeslot");
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    // Without connection info (e.g. when served without it) the client can't be identified
+    let client_ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(client_address)| client_address.ip());
+    let record_failure = |attempt: Option<AuthAttempt>| {
+        if attempt.is_some_and(AuthAttempt::fail) {
+            warn!(
+                ?client_ip,
+                "Too many failed authentications. Locking out the client"
+            );
+        }
+    };
 
-    if let Err(err) = timeslot.validate() {
-        error!(?err, "Invalid input");
-        return (StatusCode::BAD_REQUEST, format!("Invalid input: {err:?}"));
-    }
+    let mut admins = state.configuration.admins();
+    admins.insert(
+        0,
+        Admin {
+            name: DEFAULT_ADMIN_NAME.to_string(),
+            password: state.configuration.password(),
+        },
+    );
+    let auth_header_name = state.configuration.auth_header_name();
 
-    match state
-        .backend
-        .add_timeslot(timeslot.datetime, timeslot.notes)
+    let header_password = request
+        .headers()
+        .get(auth_header_name.as_str())
+        .map(|auth_header| Secret::from(auth_header.to_str().unwrap_or("")));
+    let bearer_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok())
+        .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        .map(|token| Secret::from(token.trim()));
+
+    // The password header takes precedence, a Bearer token is only used without it. If both are
+    // sent, they must be identical.
+    let provided = match (header_password, bearer_token) {
+        (Some(header_password), Some(bearer_token))
+            if header_password.expose() != bearer_token.expose() =>
+        {
+            Err("Conflicting credentials")
+        }
+        (Some(header_password), _) => Ok(header_password),
+        (None, Some(bearer_token)) => Ok(bearer_token),
+        (None, None) => {
+            error!("Authorization failed: Missing credentials");
+            metrics::counter!(AUTH_FAILURES_TOTAL).increment(1);
+            return Err(ApiError::unauthorized("Missing credentials"));
+        }
+    };
+
+    // Reserved before the verification, so concurrent guesses can't pass the lockout
+    let attempt = match client_ip
+        .map(|client_ip| state.auth_lockout.begin_attempt(client_ip))
+        .transpose()
     {
-        Ok(()) => (StatusCode::OK, "Timeslot added successfully".to_string()),
-        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
+        Ok(attempt) => attempt,
+        Err(remaining) => {
+            error!(?client_ip, ?remaining, "Authorization failed: Locked out");
+            metrics::counter!(AUTH_FAILURES_TOTAL).increment(1);
+            // Rounded up, so a retry after the header's seconds is never still locked out
+            return Err(ApiError::locked_out(
+                remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0),
+            ));
+        }
+    };
+
+    let provided = match provided {
+        Ok(provided) => provided,
+        Err(message) => {
+            error!("Authorization failed: {message}");
+            metrics::counter!(AUTH_FAILURES_TOTAL).increment(1);
+            record_failure(attempt);
+            return Err(ApiError::unauthorized(message));
+        }
+    };
+
+    // Hash verification is CPU intensive and must not block the runtime
+    let admin_name = tokio::task::spawn_blocking(move || {
+        admins
+            .into_iter()
+            .find(|admin| admin.password.verify(&provided))
+            .map(|admin| admin.name)
+    })
+    .await
+    .unwrap_or(None);
+    let Some(admin_name) = admin_name else {
+        error!("Authorization failed");
+        metrics::counter!(AUTH_FAILURES_TOTAL).increment(1);
+        record_failure(attempt);
+        return Err(ApiError::unauthorized("Unauthorized"));
+    };
+    if let Some(attempt) = attempt {
+        attempt.succeed();
+    }
+
+    Span::current().record("admin", admin_name.as_str());
+    if request.method() != Method::GET {
+        info!(admin = admin_name, path = %request.uri().path(), "Admin action");
     }
+    request
+        .extensions_mut()
+        .insert(AuthenticatedAdmin(admin_name));
+    Ok(next.run(request).await)
 }
 
-async fn remove_timeslot<T: TimeslotBackend, S: Configuration>(
+async fn rate_limit<T: TimeslotBackend, S: Configuration>(
     State(state): State<AppState<T, S>>,
-    Json(timeslot): Json<DeleteTimeslotRequest>,
-) -> impl IntoResponse {
-    debug!("Remove timeslot");
-    match state.backend.remove_timeslot(timeslot.id) {
-        Ok(()) => (StatusCode::OK, "Timeslot removed successfully".to_string()),
-        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    // Without connection info (e.g. when served without it) the client can't be identified
+    if let Some(ConnectInfo(client_address)) = request.extensions().get::<ConnectInfo<SocketAddr>>()
+    {
+        if !state.rate_limiter.check(client_address.ip()) {
+            error!(%client_address, "Rate limit exceeded");
+            return Err(ApiError::too_many_requests());
+        }
     }
+    Ok(next.run(request).await)
 }
 
-async fn remove_all_timeslot<T: TimeslotBackend, S: Configuration>(
+/// Rejects the request while the read-only mode is active.
+async fn read_only<T: TimeslotBackend, S: Configuration>(
     State(state): State<AppState<T, S>>,
-) -> impl IntoResponse {
-    debug!("Remove all timeslots");
-    match state.backend.remove_all_timeslot() {
-        Ok(()) => (
-            StatusCode::OK,
-            "All timeslots removed successfully".to_string(),
-        ),
-        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if state.configuration.read_only() {
+        error!(path = %request.uri().path(), "Rejected change in read-only mode");
+        return Err(ApiError::read_only());
     }
+    Ok(next.run(request).await)
 }
 
-async fn get_frontend<T: TimeslotBackend, S: Configuration>(
+async fn get_timeslots<T: TimeslotBackend, S: Configuration>(
     State(state): State<AppState<T, S>>,
-) -> Result<Html<String>, (StatusCode, String)> {
-    debug!("Get frontend");
-    let title = state.configuration.website_title();
-    let path = state.configuration.frontend_path();
+    Query(query): Query<TimeslotStreamQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // The stream starts with the current timeslots, so a reconnecting client catches up on
+    // everything it missed right away
+    if let Some(last_event_id) = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|last_event_id| last_event_id.to_str().ok())
+    {
+        debug!(last_event_id, "Resuming SSE timeslot stream");
+    } else {
+        debug!("Starting SSE timeslot stream");
+    }
 
-    match fs::read_to_string(path).await {
-        Ok(contents) => {
-            let contents = contents.replace("generic_timeslot_booking_manager_name", &title);
-            Ok(Html(contents))
+    let last_event_id = state.last_event_id.clone();
+    let stream = state.backend.timeslot_stream().map(move |timeslots| {
+        let timeslots: Vec<TimeslotResponse> = timeslots
+            .into_iter()
+            .filter(|timeslot| query.matches(timeslot))
+            .map(|timeslot| timeslot.into_public().into())
+            .collect();
+        let event_id = last_event_id.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(Event::default()
+            .id(event_id.to_string())
+            .json_data(timeslots)
+            .unwrap())
+    });
+    // Load balancers drop connections which stay idle for too long
+    let keep_alive = KeepAlive::new().interval(std::time::Duration::from_secs(
+        state.configuration.sse_keep_alive_seconds(),
+    ));
+    // End the stream on shutdown, otherwise the graceful shutdown would wait for it forever
+    Sse::new(futures::StreamExt::take_until(stream, async move {
+        state.shutdown.triggered().await
+    }))
+    .keep_alive(keep_alive)
+}
+
+/// Live feed of the changes of single timeslots, e.g. new bookings, without the unchanged
+/// timeslots `/timeslots` sends on every change
+async fn get_events<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    debug!("Starting SSE event stream");
+    let stream = state
+        .backend
+        .event_stream()
+        .filter_map(|event| match event {
+            Ok(event) => Some(Ok(Event::default()
+                .event(event.kind.as_str())
+                .json_data(event)
+                .unwrap())),
+            Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                warn!(missed, "SSE event stream fell behind. Events were skipped");
+                None
+            }
+        });
+    let keep_alive = KeepAlive::new().interval(std::time::Duration::from_secs(
+        state.configuration.sse_keep_alive_seconds(),
+    ));
+    Sse::new(futures::StreamExt::take_until(stream, async move {
+        state.shutdown.triggered().await
+    }))
+    .keep_alive(keep_alive)
+}
+
+async fn get_timeslots_snapshot<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<SnapshotQuery>,
+) -> Result<Json<SnapshotResponse>, ApiError> {
+    debug!("Get timeslots snapshot");
+    let (from, to) = (query.from, query.to);
+    // Whether the whole schedule is empty, not only the requested part of it
+    let result = run_blocking(&state.backend, move |backend| {
+        if from.is_none() && to.is_none() {
+            let timeslots = backend.current_timeslots()?;
+            let schedule_empty = timeslots.is_empty();
+            return Ok((timeslots, schedule_empty));
         }
-        Err(e) => {
-            let error_message = format!("Failed to read frontend file: {e}");
-            Err((StatusCode::INTERNAL_SERVER_ERROR, error_message))
+        // Open bounds are limited to dates every backend can store
+        let timeslots = backend.timeslots_between(
+            from.unwrap_or(DateTime::UNIX_EPOCH),
+            to.unwrap_or_else(|| {
+                NaiveDate::from_ymd_opt(9999, 12, 31)
+                    .and_then(|date| date.and_hms_opt(23, 59, 59))
+                    .unwrap()
+                    .and_utc()
+            }),
+        )?;
+        let schedule_empty = timeslots.is_empty() && backend.count_timeslots(false)? == 0;
+        Ok((timeslots, schedule_empty))
+    })
+    .await;
+    match result {
+        Ok((timeslots, schedule_empty)) => {
+            let timeslots: Vec<TimeslotResponse> = query
+                .apply(timeslots)
+                .into_iter()
+                .map(|timeslot| timeslot.into_public().into())
+                .collect();
+            let message = if schedule_empty {
+                state.configuration.empty_schedule_message()
+            } else {
+                None
+            };
+            Ok(Json(SnapshotResponse { timeslots, message }))
         }
+        Err(err) => Err(ApiError::backend(err)),
     }
 }
 
-async fn get_admin_page() -> impl IntoResponse {
-    StatusCode::OK
+/// The current timeslots keyed by their day (`YYYY-MM-DD`) in the display timezone, e.g. for a
+/// calendar
+async fn get_timeslots_by_day<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<Json<BTreeMap<String, Vec<TimeslotResponse>>>, ApiError> {
+    debug!("Get timeslots by day");
+    let mut timeslots = run_blocking(&state.backend, |backend| backend.current_timeslots())
+        .await
+        .map_err(ApiError::backend)?;
+    timeslots.sort_by_key(|timeslot| timeslot.datetime);
+
+    let timezone = display_timezone(&state.configuration);
+    let mut days: BTreeMap<String, Vec<TimeslotResponse>> = BTreeMap::new();
+    for timeslot in timeslots {
+        let day = timeslot
+            .datetime
+            .with_timezone(&timezone)
+            .format("%Y-%m-%d")
+            .to_string();
+        days.entry(day)
+            .or_default()
+            .push(timeslot.into_public().into());
+    }
+    Ok(Json(days))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::testutils::{MockConfiguration, MockTimeslotBackend};
-    use crate::types::Timeslot;
-    use axum::body::Bytes;
-    use axum::http::StatusCode;
-    use reqwest::{Client, Error};
-    use std::io::Write;
-    use std::net::SocketAddr;
-    use std::{sync::atomic::Ordering, time::Duration};
-    use tempfile::NamedTempFile;
-    use tokio::net::TcpListener;
-    use tokio::task::JoinHandle;
-    use tokio::time::timeout;
+/// Counts the timeslots without sending them, e.g. for a badge
+async fn get_count<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<CountQuery>,
+) -> Result<Json<CountResponse>, ApiError> {
+    debug!("Count timeslots");
+    run_blocking(&state.backend, move |backend| {
+        backend.count_timeslots(query.available_only)
+    })
+    .await
+    .map(|count| Json(CountResponse { count }))
+    .map_err(ApiError::backend)
+}
 
-    #[derive(Debug, Clone, Serialize, Deserialize)]
-    struct EmptyRequest {}
+async fn get_calendar<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Get calendar");
+    match run_blocking(&state.backend, |backend| backend.current_timeslots()).await {
+        Ok(timeslots) => Ok((
+            [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+            to_icalendar(&timeslots),
+        )),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
 
-    fn assert_backend_calls(
-        mock_backend: MockTimeslotBackend,
-        path: &str,
-        expected_backend_calls: u64,
-    ) {
-        match path {
-            "book" => assert_eq!(
-                mock_backend.0.calls_to_book_timeslot.load(Ordering::SeqCst),
-                expected_backend_calls
-            ),
-            "timeslots" => assert_eq!(
-                mock_backend.0.calls_to_timeslots.load(Ordering::SeqCst),
-                expected_backend_calls
-            ),
-            "add" => assert_eq!(
-                mock_backend.0.calls_to_add_timeslot.load(Ordering::SeqCst),
-                expected_backend_calls
-            ),
-            "remove" => assert_eq!(
-                mock_backend
-                    .0
-                    .calls_to_remove_timeslot
-                    .load(Ordering::SeqCst),
-                expected_backend_calls
-            ),
-            "remove_all" => assert_eq!(
-                mock_backend
-                    .0
-                    .calls_to_remove_all_timeslot
-                    .load(Ordering::SeqCst),
-                expected_backend_calls
+async fn get_export<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Get export");
+    let timeslots = run_blocking(&state.backend, |backend| backend.current_timeslots())
+        .await
+        .map_err(ApiError::backend)?;
+    let csv = to_csv(&timeslots).map_err(|err| ApiError::backend(err.into()))?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"bookings.csv\"",
             ),
-            "admin_page" => {} // No related backend call
-            _ => unimplemented!(),
+        ],
+        csv,
+    ))
+}
+
+async fn get_admin_timeslots<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<Json<Vec<TimeslotResponse>>, ApiError> {
+    debug!("Get admin timeslots");
+    match run_blocking(&state.backend, |backend| backend.current_timeslots()).await {
+        Ok(timeslots) => Ok(Json(timeslots.into_iter().map(Into::into).collect())),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn get_archived_timeslots<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<Json<Vec<Timeslot>>, ApiError> {
+    debug!("Get archived timeslots");
+    match run_blocking(&state.backend, |backend| backend.archived_timeslots()).await {
+        Ok(timeslots) => Ok(Json(timeslots)),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn get_bookings<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<BookingsQuery>,
+) -> Result<Json<Vec<Timeslot>>, ApiError> {
+    debug!("Get bookings");
+    let timeslots = run_blocking(&state.backend, |backend| backend.current_timeslots())
+        .await
+        .map_err(ApiError::backend)?;
+
+    // Timeslots with a capacity above 1 stay available while partially booked
+    let mut bookings: Vec<Timeslot> = timeslots
+        .into_iter()
+        .filter(|timeslot| !timeslot.available || timeslot.booked_count > 0)
+        .filter(|timeslot| {
+            query
+                .date
+                .is_none_or(|date| timeslot.datetime.date_naive() == date)
+        })
+        .collect();
+    bookings.sort_by_key(|timeslot| timeslot.datetime);
+    Ok(Json(bookings))
+}
+
+async fn search_bookings<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<Timeslot>>, ApiError> {
+    debug!("Search bookings");
+    if query.name.trim().is_empty() {
+        error!("Search failed: Empty name");
+        return Err(ApiError::bad_request(
+            "empty_search",
+            "Name to search for must not be empty",
+        ));
+    }
+    let timeslots = run_blocking(&state.backend, move |backend| {
+        backend.search_bookings(&query.name)
+    })
+    .await
+    .map_err(ApiError::backend)?;
+    Ok(Json(timeslots))
+}
+
+async fn get_stats<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<BookingsQuery>,
+) -> Result<Json<StatsResponse>, ApiError> {
+    debug!("Get stats");
+    let timeslots: Vec<Timeslot> =
+        run_blocking(&state.backend, |backend| backend.current_timeslots())
+            .await
+            .map_err(ApiError::backend)?
+            .into_iter()
+            .filter(|timeslot| {
+                query
+                    .date
+                    .is_none_or(|date| timeslot.datetime.date_naive() == date)
+            })
+            .collect();
+    Ok(Json(StatsResponse::new(&timeslots)))
+}
+
+/// The booked timeslot with only the booking of the code, see `Timeslot::into_booking_of`.
+async fn get_booking<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Path(code): Path<String>,
+) -> Result<Json<Timeslot>, ApiError> {
+    debug!("Get booking by confirmation code");
+    let timeslot = run_blocking(&state.backend, {
+        let code = code.clone();
+        move |backend| backend.find_by_confirmation(&code)
+    })
+    .await
+    .map_err(ApiError::backend)?;
+    match timeslot.and_then(|timeslot| timeslot.into_booking_of(&code)) {
+        Some(timeslot) => Ok(Json(timeslot)),
+        None => Err(ApiError::not_found("Booking not found")),
+    }
+}
+
+/// Printable confirmation of a booking. Unknown timeslots and wrong codes are both answered with
+/// 404, so the response doesn't reveal which timeslots are booked.
+async fn get_confirmation<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ConfirmationQuery>,
+) -> Result<Html<String>, ApiError> {
+    debug!("Get booking confirmation");
+    let code = query.code.clone();
+    let timeslot = run_blocking(&state.backend, move |backend| {
+        backend.find_by_confirmation(&code)
+    })
+    .await
+    .map_err(ApiError::backend)?
+    .filter(|timeslot| timeslot.id == id);
+    let Some((timeslot, booking)) = timeslot.as_ref().and_then(|timeslot| {
+        timeslot
+            .booking(&query.code)
+            .map(|booking| (timeslot, booking))
+    }) else {
+        error!(%id, "Booking confirmation not found");
+        return Err(ApiError::not_found("Booking not found"));
+    };
+    Ok(Html(to_confirmation_page(
+        &state.configuration.website_title(),
+        timeslot,
+        booking,
+        display_timezone(&state.configuration),
+    )))
+}
+
+/// A retried request with the same `Idempotency-Key` header returns the result of the first one
+/// instead of booking again. Server errors aren't remembered, so they can be retried.
+async fn book_timeslot<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    headers: HeaderMap,
+    JsonOrForm(booking): JsonOrForm<BookingRequest>,
+) -> Result<Json<BookingResponse>, ApiError> {
+    debug!("Book timeslot");
+    let language = request_language(&headers);
+    let Some(idempotency_key) = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|key| key.to_str().ok())
+        .map(str::to_string)
+    else {
+        return book(&state, booking, language).await.map(Json);
+    };
+
+    // Held until the booking is done, so a concurrent retry waits for its result
+    let mut cached = state
+        .bookings_by_idempotency_key
+        .reserve(idempotency_key.clone())
+        .await;
+    if let Some(result) = cached.clone() {
+        info!(
+            idempotency_key,
+            "Repeated booking request. Returning the previous result"
+        );
+        return result.map(Json);
+    }
+    let result = book(&state, booking, language).await;
+    // Server errors may be temporary, so a retry must be processed again
+    if result
+        .as_ref()
+        .err()
+        .is_none_or(|err| !err.status().is_server_error())
+    {
+        *cached = Some(result.clone());
+    }
+    result.map(Json)
+}
+
+/// Success and error messages are sent in the language of the client. Errors of the backend are
+/// only available in English.
+async fn book<T: TimeslotBackend, S: Configuration>(
+    state: &AppState<T, S>,
+    booking: BookingRequest,
+    language: Language,
+) -> Result<BookingResponse, ApiError> {
+    let booking = BookingRequest {
+        client_email: booking.client_email.filter(|email| !email.is_empty()),
+        client_phone: booking.client_phone.filter(|phone| !phone.is_empty()),
+        booker_notes: booking.booker_notes.filter(|notes| !notes.is_empty()),
+        ..booking
+    };
+    if let Err(err) = booking.validate_with_args(&validation_context(state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err).with_error(language.invalid_input()));
+    }
+
+    // Checked here instead of in the backends, so all of them behave the same
+    let lead_minutes = state.configuration.min_booking_lead_minutes();
+    if lead_minutes > 0 {
+        let id = booking.id;
+        let timeslot = run_blocking(&state.backend, move |backend| backend.find_timeslot(id))
+            .await
+            .map_err(ApiError::backend)?;
+        // Unknown timeslots are rejected by the backend
+        if let Some(timeslot) = timeslot {
+            if timeslot.datetime - Utc::now() < chrono::Duration::minutes(lead_minutes) {
+                error!(%timeslot.datetime, lead_minutes, "Timeslot starts too soon to be booked");
+                return Err(ApiError::new(
+                    StatusCode::CONFLICT,
+                    "booking_closed",
+                    language.booking_closed(lead_minutes),
+                ));
+            }
         }
     }
 
-    async fn init() -> (
-        JoinHandle<Result<(), std::io::Error>>,
-        SocketAddr,
-        MockTimeslotBackend,
-        MockConfiguration,
+    check_booking_limit(state, &booking.client_name, language).await?;
+
+    let booked_timeslot = run_blocking(&state.backend, move |backend| {
+        backend.book_timeslot(
+            booking.id,
+            booking.client_name,
+            booking.client_email,
+            booking.client_phone,
+            booking.booker_notes,
+        )
+    })
+    .await
+    .map_err(ApiError::backend)?;
+    let confirmation_code = new_confirmation_code(&booked_timeslot);
+    booking_created(state, booked_timeslot);
+    Ok(BookingResponse {
+        message: language.timeslot_booked().to_string(),
+        confirmation_code,
+    })
+}
+
+/// Books the earliest available timeslot of a window, for clients which don't mind the exact
+/// timeslot. Timeslots starting too soon to be booked (see `book`) are skipped.
+async fn book_any<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    headers: HeaderMap,
+    Json(request): Json<BookAnyRequest>,
+) -> Result<Json<BookAnyResponse>, ApiError> {
+    debug!("Book first available timeslot");
+    let language = request_language(&headers);
+    if let Err(err) = request.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err).with_error(language.invalid_input()));
+    }
+    if request.to < request.from {
+        error!(%request.from, %request.to, "Invalid time window");
+        return Err(ApiError::bad_request(
+            "invalid_window",
+            "The time window must not end before it starts",
+        ));
+    }
+    check_booking_limit(&state, &request.client_name, language).await?;
+
+    let from = request.from.max(
+        Utc::now() + chrono::Duration::minutes(state.configuration.min_booking_lead_minutes()),
+    );
+    let booked_timeslot = run_blocking(&state.backend, move |backend| {
+        backend.book_first_available(from, request.to, request.client_name)
+    })
+    .await
+    .map_err(ApiError::backend)?;
+    let confirmation_code = new_confirmation_code(&booked_timeslot);
+    booking_created(&state, booked_timeslot.clone());
+    Ok(Json(BookAnyResponse {
+        message: language.timeslot_booked().to_string(),
+        confirmation_code,
+        timeslot: booked_timeslot.into_public().into(),
+    }))
+}
+
+/// Language of the messages sent to the client, see `Language::from_accept_language`.
+fn request_language(headers: &HeaderMap) -> Language {
+    Language::from_accept_language(
+        headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|accept_language| accept_language.to_str().ok()),
+    )
+}
+
+/// Rejects the booking if the client already has the maximum number of bookings.
+async fn check_booking_limit<T: TimeslotBackend, S: Configuration>(
+    state: &AppState<T, S>,
+    client_name: &str,
+    language: Language,
+) -> Result<(), ApiError> {
+    let Some(max_bookings) = state.configuration.max_bookings_per_client() else {
+        return Ok(());
+    };
+    let client_name = client_name.to_string();
+    let bookings = run_blocking(&state.backend, move |backend| {
+        backend.count_bookings(&client_name)
+    })
+    .await
+    .map_err(ApiError::backend)?;
+    if bookings >= max_bookings as usize {
+        error!(bookings, max_bookings, "Booking limit per client reached");
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "booking_limit_reached",
+            language.booking_limit_reached(max_bookings),
+        ));
+    }
+    Ok(())
+}
+
+/// Confirmation code of the booking just added to the timeslot, which is its last one.
+fn new_confirmation_code(booked_timeslot: &Timeslot) -> String {
+    booked_timeslot
+        .bookings
+        .last()
+        .map(|booking| booking.confirmation_code.clone())
+        .unwrap_or_default()
+}
+
+/// Counts a new booking and notifies the webhook about the booked timeslot.
+fn booking_created<T: TimeslotBackend, S: Configuration>(
+    state: &AppState<T, S>,
+    booked_timeslot: Timeslot,
+) {
+    metrics::counter!(BOOKINGS_TOTAL).increment(1);
+    if let Some(webhook) = &state.webhook {
+        webhook.notify(booked_timeslot);
+    }
+}
+
+async fn join_waitlist<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(request): Json<WaitlistRequest>,
+) -> Result<Json<WaitlistResponse>, ApiError> {
+    debug!("Join waitlist");
+    if let Err(err) = request.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+
+    match run_blocking(&state.backend, move |backend| {
+        backend.join_waitlist(request.id, request.client_name)
+    })
+    .await
+    {
+        Ok(position) => Ok(Json(WaitlistResponse {
+            message: "Waitlist joined successfully".to_string(),
+            position,
+        })),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+/// Runs the cancellation, which returns the confirmation codes of the promoted bookings. Each
+/// waitlisted client taking over counts as new booking.
+async fn cancel_and_promote<T, S, F>(state: &AppState<T, S>, cancel: F) -> Result<String, ApiError>
+where
+    T: TimeslotBackend,
+    S: Configuration,
+    F: FnOnce(&T) -> Result<Vec<String>, BackendError> + Send + 'static,
+{
+    let promoted_codes = run_blocking(&state.backend, cancel)
+        .await
+        .map_err(ApiError::backend)?;
+    for confirmation_code in promoted_codes {
+        // The waitlisted client took over the booking, so the webhook needs the updated timeslot
+        match run_blocking(&state.backend, move |backend| {
+            backend.find_by_confirmation(&confirmation_code)
+        })
+        .await
+        {
+            Ok(Some(timeslot)) => booking_created(state, timeslot),
+            Ok(None) => error!("Promoted booking not found. Webhook is not sent"),
+            Err(err) => error!(?err, "Failed to read promoted booking. Webhook is not sent"),
+        }
+    }
+    Ok("Booking cancelled successfully".to_string())
+}
+
+async fn cancel_booking<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(booking): Json<CancelBookingRequest>,
+) -> Result<String, ApiError> {
+    debug!("Cancel booking");
+    cancel_and_promote(&state, move |backend| backend.cancel_booking(booking.id)).await
+}
+
+/// Confirms a booking, e.g. after its payment arrived, so it is kept beyond the booking hold
+async fn confirm_booking<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(booking): Json<ConfirmBookingRequest>,
+) -> Result<String, ApiError> {
+    debug!("Confirm booking");
+    match run_blocking(&state.backend, move |backend| {
+        backend.confirm_booking(booking.id)
+    })
+    .await
+    {
+        Ok(()) => Ok("Booking confirmed successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn cancel_own_booking<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(booking): Json<CancelOwnBookingRequest>,
+) -> Result<String, ApiError> {
+    debug!("Cancel own booking");
+    let timeslot = match run_blocking(&state.backend, {
+        let code = booking.code.clone();
+        move |backend| backend.find_by_confirmation(&code)
+    })
+    .await
+    {
+        Ok(Some(timeslot)) => timeslot,
+        Ok(None) => return Err(ApiError::not_found("Booking not found")),
+        Err(err) => return Err(ApiError::backend(err)),
+    };
+    if timeslot.booked_count == 0 {
+        error!("Cancellation failed: Timeslot is not booked");
+        return Err(ApiError::conflict("Timeslot is not booked"));
+    }
+
+    // Only the place of this booking is released, the other bookers of the timeslot keep theirs
+    cancel_and_promote(&state, move |backend| {
+        backend.cancel_own_booking(&booking.code)
+    })
+    .await
+}
+
+async fn add_timeslot<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(mut timeslot): Json<AddTimeslotRequest>,
+) -> Result<String, ApiError> {
+    debug!("Add timeslot");
+
+    if timeslot.notes.is_empty() {
+        timeslot.notes = state.configuration.default_notes();
+    }
+    timeslot.datetime = align_start(&state.configuration, timeslot.datetime)?;
+    if let Err(err) = timeslot.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+
+    match run_blocking(&state.backend, move |backend| {
+        backend.add_timeslot(
+            timeslot.datetime,
+            timeslot.notes,
+            timeslot.capacity,
+            timeslot.duration_minutes,
+            timeslot.internal_notes.filter(|notes| !notes.is_empty()),
+            timeslot.category.filter(|category| !category.is_empty()),
+        )
+    })
+    .await
+    {
+        Ok(()) => {
+            metrics::counter!(TIMESLOTS_ADDED_TOTAL).increment(1);
+            Ok("Timeslot added successfully".to_string())
+        }
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+/// Responds with the timeslots which adding the drafts would create, without adding them
+async fn preview_timeslots<T: TimeslotBackend>(
+    backend: &T,
+    drafts: Vec<TimeslotDraft>,
+) -> Result<Response, ApiError> {
+    let drafts = run_blocking(backend, move |backend| {
+        backend.check_timeslots_bulk(&drafts).map(|()| drafts)
+    })
+    .await
+    .map_err(ApiError::backend)?;
+    let timeslots: Vec<Timeslot> = drafts
+        .into_iter()
+        .map(TimeslotDraft::into_timeslot)
+        .collect();
+    Ok(Json(timeslots).into_response())
+}
+
+async fn add_timeslots_bulk<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<DryRunQuery>,
+    Json(mut request): Json<AddTimeslotsBulkRequest>,
+) -> Result<Response, ApiError> {
+    debug!("Add timeslots in bulk");
+
+    for slot in &mut request.slots {
+        slot.datetime = align_start(&state.configuration, slot.datetime)?;
+    }
+    if let Err(err) = request.validate_with_slots(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+
+    let drafts = request
+        .slots
+        .into_iter()
+        .map(|slot| TimeslotDraft {
+            datetime: slot.datetime,
+            notes: slot.notes,
+            capacity: slot.capacity,
+            duration_minutes: slot.duration_minutes,
+            internal_notes: slot.internal_notes,
+            category: slot.category,
+        })
+        .collect();
+    if query.dry_run {
+        return preview_timeslots(&state.backend, drafts).await;
+    }
+    match run_blocking(&state.backend, move |backend| {
+        backend.add_timeslots_bulk(drafts)
+    })
+    .await
+    {
+        Ok(count) => {
+            metrics::counter!(TIMESLOTS_ADDED_TOTAL).increment(count as u64);
+            Ok(Json(AddTimeslotsBulkResponse {
+                message: "Timeslots added successfully".to_string(),
+                count,
+            })
+            .into_response())
+        }
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn import_timeslots<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    body: String,
+) -> Result<Json<AddTimeslotsBulkResponse>, ApiError> {
+    debug!("Import timeslots");
+
+    let rows = from_csv(&body).map_err(|err| {
+        error!(?err, "Invalid CSV");
+        ApiError::bad_request("invalid_csv", err)
+    })?;
+    if rows.is_empty() {
+        return Err(ApiError::bad_request(
+            "invalid_csv",
+            "No timeslots to import",
+        ));
+    }
+
+    // Nothing is added unless every row is valid
+    let context = validation_context(&state);
+    let mut drafts = Vec::with_capacity(rows.len());
+    for (index, row) in rows.into_iter().enumerate() {
+        let slot = AddTimeslotRequest {
+            datetime: align_start(&state.configuration, row.datetime)
+                .map_err(|err| err.in_row(index + 1))?,
+            notes: row.notes,
+            capacity: row.capacity.unwrap_or_else(default_capacity),
+            duration_minutes: default_duration_minutes(),
+            internal_notes: None,
+            category: None,
+        };
+        if let Err(err) = slot.validate_with_args(&context) {
+            error!(?err, row = index + 1, "Invalid input");
+            return Err(ApiError::invalid_row(index + 1, &err));
+        }
+        drafts.push(TimeslotDraft {
+            datetime: slot.datetime,
+            notes: slot.notes,
+            capacity: slot.capacity,
+            duration_minutes: slot.duration_minutes,
+            internal_notes: slot.internal_notes,
+            category: slot.category,
+        });
+    }
+
+    match run_blocking(&state.backend, move |backend| {
+        backend.add_timeslots_bulk(drafts)
+    })
+    .await
+    {
+        Ok(count) => {
+            metrics::counter!(TIMESLOTS_ADDED_TOTAL).increment(count as u64);
+            Ok(Json(AddTimeslotsBulkResponse {
+                message: "Timeslots imported successfully".to_string(),
+                count,
+            }))
+        }
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn add_recurring_timeslots<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Query(query): Query<DryRunQuery>,
+    Json(request): Json<AddRecurringTimeslotsRequest>,
+) -> Result<Response, ApiError> {
+    debug!("Add recurring timeslots");
+
+    if let Err(err) = request.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+    let timezone = match request.timezone.as_deref().unwrap_or("UTC").parse() {
+        Ok(timezone) => timezone,
+        Err(err) => {
+            error!(?err, "Invalid timezone");
+            return Err(ApiError::bad_request(
+                "invalid_timezone",
+                format!("Invalid timezone: {err}"),
+            ));
+        }
+    };
+
+    // Every occurrence is checked like the start of a single new timeslot
+    let context = validation_context(&state);
+    let mut datetimes = Vec::new();
+    for datetime in recurrence::expand(
+        request.datetime,
+        request.interval,
+        request.count,
+        timezone,
+        Utc::now(),
     ) {
-        let mock_backend = MockTimeslotBackend::new();
-        let mock_configuration = MockConfiguration::new();
+        let datetime = align_start(&state.configuration, datetime)?;
+        if let Err(err) = validate_new_timeslot_start(&datetime, &context) {
+            error!(?err, %datetime, "Invalid occurrence");
+            let mut errors = ValidationErrors::new();
+            errors.add("datetime", err);
+            return Err(ApiError::validation(&errors));
+        }
+        datetimes.push(datetime);
+    }
+    if query.dry_run {
+        let drafts = datetimes
+            .into_iter()
+            .map(|datetime| TimeslotDraft {
+                datetime,
+                notes: request.notes.clone(),
+                capacity: request.capacity,
+                duration_minutes: request.duration_minutes,
+                internal_notes: request.internal_notes.clone(),
+                category: request.category.clone(),
+            })
+            .collect();
+        return preview_timeslots(&state.backend, drafts).await;
+    }
+    let count = datetimes.len();
+    for datetime in datetimes {
+        let notes = request.notes.clone();
+        let internal_notes = request.internal_notes.clone();
+        let category = request.category.clone();
+        run_blocking(&state.backend, move |backend| {
+            backend.add_timeslot(
+                datetime,
+                notes,
+                request.capacity,
+                request.duration_minutes,
+                internal_notes,
+                category,
+            )
+        })
+        .await
+        .map_err(ApiError::backend)?;
+        metrics::counter!(TIMESLOTS_ADDED_TOTAL).increment(1);
+    }
 
-        let app = create_app(mock_backend.clone(), mock_configuration.clone());
-        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
-        let addr = listener.local_addr().unwrap();
-        let join = tokio::spawn(async move { axum::serve(listener, app).await });
+    Ok(Json(AddTimeslotsBulkResponse {
+        message: "Timeslots added successfully".to_string(),
+        count,
+    })
+    .into_response())
+}
 
-        (join, addr, mock_backend, mock_configuration)
+async fn edit_timeslot<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(timeslot): Json<EditTimeslotRequest>,
+) -> Result<String, ApiError> {
+    debug!("Edit timeslot");
+
+    if let Err(err) = timeslot.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+
+    match run_blocking(&state.backend, move |backend| {
+        backend.edit_timeslot(
+            timeslot.id,
+            timeslot.datetime,
+            timeslot.notes,
+            timeslot.internal_notes.filter(|notes| !notes.is_empty()),
+            timeslot.version,
+        )
+    })
+    .await
+    {
+        Ok(()) => Ok("Timeslot edited successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+/// Changes only the notes, so the start can't be overwritten with a stale value by accident
+async fn update_notes<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(update): Json<UpdateNotesRequest>,
+) -> Result<String, ApiError> {
+    debug!("Update notes");
+
+    if let Err(err) = update.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+
+    match run_blocking(&state.backend, move |backend| {
+        backend.update_notes(update.id, update.notes)
+    })
+    .await
+    {
+        Ok(()) => Ok("Notes updated successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+/// Moves a booked timeslot, e.g. if the client asks for another appointment, keeping its booking
+async fn reschedule<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(reschedule): Json<RescheduleRequest>,
+) -> Result<String, ApiError> {
+    debug!("Reschedule timeslot");
+
+    if let Err(err) = reschedule.validate_with_args(&validation_context(&state)) {
+        error!(?err, "Invalid input");
+        return Err(ApiError::validation(&err));
+    }
+
+    match run_blocking(&state.backend, move |backend| {
+        backend.reschedule(reschedule.id, reschedule.new_datetime)
+    })
+    .await
+    {
+        Ok(()) => Ok("Timeslot rescheduled successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+/// Exchanges the bookings of two timeslots, e.g. if two clients trade their appointments
+async fn swap_bookings<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(swap): Json<SwapBookingsRequest>,
+) -> Result<String, ApiError> {
+    debug!("Swap bookings");
+    match run_blocking(&state.backend, move |backend| {
+        backend.swap_bookings(swap.first_id, swap.second_id)
+    })
+    .await
+    {
+        Ok(()) => Ok("Bookings swapped successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn remove_timeslot<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Json(timeslot): Json<DeleteTimeslotRequest>,
+) -> Result<String, ApiError> {
+    debug!("Remove timeslot");
+    match run_blocking(&state.backend, move |backend| {
+        backend.remove_timeslot(timeslot.id)
+    })
+    .await
+    {
+        Ok(()) => Ok("Timeslot removed successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn remove_all_timeslot<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<String, ApiError> {
+    debug!("Remove all timeslots");
+    match run_blocking(&state.backend, |backend| backend.remove_all_timeslot()).await {
+        Ok(()) => Ok("All timeslots removed successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+async fn restore_last<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<String, ApiError> {
+    debug!("Restore last removed timeslots");
+    match run_blocking(&state.backend, |backend| backend.restore_last()).await {
+        Ok(()) => Ok("Timeslots restored successfully".to_string()),
+        Err(err) => Err(ApiError::backend(err)),
+    }
+}
+
+/// Replaces the password of the calling admin by a random key. The key is only returned here and
+/// kept in memory, so the configured password applies again after a restart.
+async fn rotate_key<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Extension(AuthenticatedAdmin(admin_name)): Extension<AuthenticatedAdmin>,
+) -> Json<RotateKeyResponse> {
+    debug!("Rotate admin key");
+    let key = generate_key();
+    state
+        .configuration
+        .set_password(&admin_name, AdminPassword::Plaintext(key.clone().into()));
+    info!(admin = admin_name, "Admin key rotated");
+    Json(RotateKeyResponse {
+        message: "Admin key rotated successfully".to_string(),
+        key,
+    })
+}
+
+async fn set_read_only<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    Extension(AuthenticatedAdmin(admin_name)): Extension<AuthenticatedAdmin>,
+    Json(request): Json<ReadOnlyRequest>,
+) -> String {
+    debug!("Set read-only mode");
+    state.configuration.set_read_only(request.read_only);
+    if request.read_only {
+        info!(admin = admin_name, "Read-only mode switched on");
+        "Read-only mode switched on".to_string()
+    } else {
+        info!(admin = admin_name, "Read-only mode switched off");
+        "Read-only mode switched off".to_string()
+    }
+}
+
+/// Serves the configured frontend file. If it can't be read, a minimal embedded page is served
+/// instead, so the error (e.g. the path on the server) isn't exposed to clients. Responds with
+/// `304 Not Modified` if the client already has the current page.
+async fn get_frontend<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+    headers: HeaderMap,
+) -> Response {
+    debug!("Get frontend");
+    let frontend = rendered_frontend(&state).await;
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|if_none_match| if_none_match.to_str().ok())
+        .is_some_and(|if_none_match| {
+            if_none_match
+                .split(',')
+                .any(|etag| etag.trim() == frontend.etag || etag.trim() == "*")
+        });
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, frontend.etag)]).into_response();
+    }
+    ([(header::ETAG, frontend.etag)], Html(frontend.html)).into_response()
+}
+
+/// The frontend with the configured values filled in. The file is only read again once its
+/// modification time changed.
+async fn rendered_frontend<T: TimeslotBackend, S: Configuration>(
+    state: &AppState<T, S>,
+) -> RenderedFrontend {
+    let path = state.configuration.frontend_path();
+    let modified = match fs::metadata(&path)
+        .await
+        .and_then(|metadata| metadata.modified())
+    {
+        Ok(modified) => modified,
+        Err(err) => {
+            error!(
+                ?err,
+                ?path,
+                "Failed to read frontend file. Serving the fallback page"
+            );
+            return RenderedFrontend::new(
+                path,
+                SystemTime::UNIX_EPOCH,
+                render_frontend(state, FALLBACK_FRONTEND),
+            );
+        }
+    };
+
+    if let Some(cached) = state
+        .frontend_cache
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|cached| cached.path == path && cached.modified == modified)
+    {
+        return cached.clone();
+    }
+
+    match fs::read_to_string(&path).await {
+        Ok(contents) => {
+            let frontend = RenderedFrontend::new(path, modified, render_frontend(state, &contents));
+            *state.frontend_cache.lock().unwrap() = Some(frontend.clone());
+            frontend
+        }
+        Err(err) => {
+            error!(
+                ?err,
+                ?path,
+                "Failed to read frontend file. Serving the fallback page"
+            );
+            RenderedFrontend::new(path, modified, render_frontend(state, FALLBACK_FRONTEND))
+        }
+    }
+}
+
+fn render_frontend<T: TimeslotBackend, S: Configuration>(
+    state: &AppState<T, S>,
+    contents: &str,
+) -> String {
+    contents
+        .replace(
+            "generic_timeslot_booking_manager_name",
+            &state.configuration.website_title(),
+        )
+        .replace(
+            "generic_auth_header_name",
+            &state.configuration.auth_header_name(),
+        )
+        .replace("generic_base_path", &state.configuration.base_path())
+        .replace(
+            "generic_timeslot_timezone",
+            &state.configuration.display_timezone(),
+        )
+        .replace(
+            "generic_name_max_len",
+            &state.text_rules.name_max_len.to_string(),
+        )
+        .replace(
+            "generic_notes_max_len",
+            &state.text_rules.notes_max_len.to_string(),
+        )
+}
+
+/// Responds with the name of the authenticated admin
+async fn get_admin_page(
+    Extension(AuthenticatedAdmin(admin_name)): Extension<AuthenticatedAdmin>,
+) -> String {
+    admin_name
+}
+
+async fn get_health() -> impl IntoResponse {
+    StatusCode::OK
+}
+
+async fn get_ready<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> Result<String, ApiError> {
+    debug!("Readiness check");
+    match run_blocking(&state.backend, |backend| backend.health_check()).await {
+        Ok(()) => Ok("Ready".to_string()),
+        // The probe is public, so the details of the failure are only logged
+        Err(err) => {
+            error!(%err, "Readiness check failed");
+            Err(ApiError::unavailable(
+                "Database Error. Health check failed".to_string(),
+            ))
+        }
+    }
+}
+
+async fn get_version() -> Json<VersionResponse> {
+    Json(VersionResponse::current())
+}
+
+async fn get_metrics<T: TimeslotBackend, S: Configuration>(
+    State(state): State<AppState<T, S>>,
+) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api_error::ApiErrorBody;
+    use crate::local_timeslots::LocalTimeslots;
+    use crate::testutils::{
+        mock_booking, read_from_timeslot_stream, MockConfiguration, MockTimeslotBackend,
+        MOCK_CONFIRMATION_CODE,
+    };
+    use crate::types::{TimeslotEvent, TimeslotEventKind};
+    use axum::body::Bytes;
+    use axum::http::StatusCode;
+    use reqwest::{Client, Error};
+    use std::io::Write;
+    use std::net::SocketAddr;
+    use std::{str::FromStr, sync::atomic::Ordering, time::Duration};
+    use tempfile::NamedTempFile;
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+    use tokio::time::timeout;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct EmptyRequest {}
+
+    /// Counterpart of `SnapshotResponse`, deserializing into full timeslots
+    #[derive(Debug, Deserialize)]
+    struct Snapshot {
+        timeslots: Vec<Timeslot>,
+        message: Option<String>,
+    }
+
+    fn assert_backend_calls(
+        mock_backend: MockTimeslotBackend,
+        path: &str,
+        expected_backend_calls: u64,
+    ) {
+        match path {
+            "book" => assert_eq!(
+                mock_backend.0.calls_to_book_timeslot.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "book_any" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_book_first_available
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "waitlist" => assert_eq!(
+                mock_backend.0.calls_to_join_waitlist.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "cancel" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_cancel_booking
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "cancel_own" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_cancel_own_booking
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "swap" => assert_eq!(
+                mock_backend.0.calls_to_swap_bookings.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "confirm" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_confirm_booking
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "admin_timeslots" | "bookings" | "stats" | "export.csv" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_current_timeslots
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "search" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_search_bookings
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "timeslots" => assert_eq!(
+                mock_backend.0.calls_to_timeslots.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "add" => assert_eq!(
+                mock_backend.0.calls_to_add_timeslot.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "add_bulk" | "import.csv" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_add_timeslots_bulk
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "edit" => assert_eq!(
+                mock_backend.0.calls_to_edit_timeslot.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "notes" => assert_eq!(
+                mock_backend.0.calls_to_update_notes.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "reschedule" => assert_eq!(
+                mock_backend.0.calls_to_reschedule.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "remove" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_remove_timeslot
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "archived" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_archived_timeslots
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "remove_all" => assert_eq!(
+                mock_backend
+                    .0
+                    .calls_to_remove_all_timeslot
+                    .load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "restore_last" => assert_eq!(
+                mock_backend.0.calls_to_restore_last.load(Ordering::SeqCst),
+                expected_backend_calls
+            ),
+            "admin_page" | "admin/rotate_key" | "admin/read_only" => {} // No related backend call
+            "events" => {} // Subscribing to the event stream isn't counted
+            _ => unimplemented!(),
+        }
+    }
+
+    async fn init() -> (
+        JoinHandle<Result<(), std::io::Error>>,
+        SocketAddr,
+        MockTimeslotBackend,
+        MockConfiguration,
+    ) {
+        init_with_configuration(MockConfiguration::new()).await
+    }
+
+    async fn init_with_configuration(
+        mock_configuration: MockConfiguration,
+    ) -> (
+        JoinHandle<Result<(), std::io::Error>>,
+        SocketAddr,
+        MockTimeslotBackend,
+        MockConfiguration,
+    ) {
+        let mock_backend = MockTimeslotBackend::new();
+
+        let app = create_app(
+            mock_backend.clone(),
+            mock_configuration.clone(),
+            ShutdownSignal::new(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let join = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        (join, addr, mock_backend, mock_configuration)
+    }
+
+    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: None, client_phone: None, booker_notes: None }, true)]
+    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: None, client_phone: None, booker_notes: None }, false)]
+    #[test_case::test_case ("confirm", ConfirmBookingRequest { id: Uuid::new_v4() }, true)]
+    #[test_case::test_case ("confirm", ConfirmBookingRequest { id: Uuid::new_v4() }, false)]
+    #[test_case::test_case ("cancel", CancelBookingRequest { id: Uuid::new_v4() }, true)]
+    #[test_case::test_case ("cancel", CancelBookingRequest { id: Uuid::new_v4() }, false)]
+    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, true)]
+    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now() - chrono::Duration::minutes(2), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, true)]
+    #[test_case::test_case ("add_bulk", AddTimeslotsBulkRequest { slots: vec![AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }] }, true)]
+    #[test_case::test_case ("add_bulk", AddTimeslotsBulkRequest { slots: vec![AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }] }, false)]
+    #[test_case::test_case ("edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::from("Example Notes"), version: 0, internal_notes: None }, true)]
+    #[test_case::test_case ("edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::from("Example Notes"), version: 0, internal_notes: None }, false)]
+    #[test_case::test_case ("notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::from("Example Notes") }, true)]
+    #[test_case::test_case ("notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::from("Example Notes") }, false)]
+    #[test_case::test_case ("reschedule", RescheduleRequest { id: Uuid::new_v4(), new_datetime: Utc::now() + chrono::Duration::hours(1) }, true)]
+    #[test_case::test_case ("reschedule", RescheduleRequest { id: Uuid::new_v4(), new_datetime: Utc::now() + chrono::Duration::hours(1) }, false)]
+    #[test_case::test_case ("swap", SwapBookingsRequest { first_id: Uuid::new_v4(), second_id: Uuid::new_v4() }, true)]
+    #[test_case::test_case ("swap", SwapBookingsRequest { first_id: Uuid::new_v4(), second_id: Uuid::new_v4() }, false)]
+    #[test_case::test_case ("remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, true)]
+    #[test_case::test_case ("remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, false)]
+    #[test_case::test_case ("remove_all", EmptyRequest {  }, true)]
+    #[test_case::test_case ("restore_last", EmptyRequest {  }, true)]
+    #[test_case::test_case ("restore_last", EmptyRequest {  }, false)]
+    #[tokio::test]
+    async fn test_access_backend<T>(path: &str, request: T, backend_success: bool)
+    where
+        T: Serialize,
+    {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        mock_backend
+            .0
+            .success
+            .store(backend_success, Ordering::SeqCst);
+
+        let client = Client::new();
+
+        let request_builder = match path {
+            "remove" => client.delete(format!("http://{addr}/{path}")),
+            "edit" => client.put(format!("http://{addr}/{path}")),
+            "notes" => client.patch(format!("http://{addr}/{path}")),
+            _ => client.post(format!("http://{addr}/{path}")),
+        }
+        .header("x-admin-password", password);
+        let response = request_builder.json(&request).send().await.unwrap();
+
+        if backend_success {
+            assert_eq!(response.status(), StatusCode::OK.as_u16());
+        } else {
+            assert_eq!(
+                response.status(),
+                StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+            );
+        }
+
+        assert_backend_calls(mock_backend, path, 1);
+        server.abort();
+    }
+
+    #[test_case::test_case(false, "Stefan", StatusCode::OK, 1 ; "json")]
+    #[test_case::test_case(true, "Stefan", StatusCode::OK, 1 ; "form")]
+    #[test_case::test_case(false, "", StatusCode::BAD_REQUEST, 0 ; "invalid json")]
+    #[test_case::test_case(true, "", StatusCode::BAD_REQUEST, 0 ; "invalid form")]
+    #[tokio::test]
+    async fn test_book_timeslot_json_or_form(
+        form: bool,
+        client_name: &str,
+        status_code: StatusCode,
+        expected_backend_calls: u64,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+        let booking = BookingRequest {
+            id: Uuid::new_v4(),
+            client_name: client_name.into(),
+            client_email: Some("stefan@example.com".into()),
+            client_phone: None,
+            booker_notes: None,
+        };
+
+        let request_builder = Client::new().post(format!("http://{addr}/book"));
+        let response = if form {
+            request_builder.form(&booking)
+        } else {
+            request_builder.json(&booking)
+        }
+        .send()
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_backend_calls(mock_backend.clone(), "book", expected_backend_calls);
+        if expected_backend_calls > 0 {
+            assert_eq!(
+                *mock_backend.0.last_client_email.lock().unwrap(),
+                Some("stefan@example.com".into())
+            );
+        }
+        server.abort();
+    }
+
+    #[test_case::test_case(Some("first"), Some("first"), 1 ; "same key")]
+    #[test_case::test_case(Some("first"), Some("second"), 2 ; "different keys")]
+    #[test_case::test_case(None, None, 2 ; "without key")]
+    #[tokio::test]
+    async fn test_book_idempotency_key(
+        first_key: Option<&str>,
+        second_key: Option<&str>,
+        expected_backend_calls: u64,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+        let booking = BookingRequest {
+            id: Uuid::new_v4(),
+            client_name: String::from("Stefan"),
+            client_email: None,
+            client_phone: None,
+            booker_notes: None,
+        };
+
+        let client = Client::new();
+        let mut responses = Vec::new();
+        for key in [first_key, second_key] {
+            let mut request_builder = client.post(format!("http://{addr}/book"));
+            if let Some(key) = key {
+                request_builder = request_builder.header("Idempotency-Key", key);
+            }
+            let response = request_builder.json(&booking).send().await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK.as_u16());
+            responses.push(response.json::<BookingResponse>().await.unwrap());
+        }
+
+        if first_key.is_some() && first_key == second_key {
+            assert_eq!(
+                responses[0].confirmation_code,
+                responses[1].confirmation_code
+            );
+        }
+        assert_backend_calls(mock_backend, "book", expected_backend_calls);
+        server.abort();
+    }
+
+    #[test_case::test_case(BackendError::NotFound("Timeslot does not exist".into()), StatusCode::NOT_FOUND, StatusCode::NOT_FOUND, 1 ; "client error is cached")]
+    #[test_case::test_case(BackendError::AlreadyBooked("Timeslot is already fully booked".into()), StatusCode::CONFLICT, StatusCode::CONFLICT, 1 ; "conflict is cached")]
+    #[test_case::test_case(BackendError::Other("Database Error".into()), StatusCode::INTERNAL_SERVER_ERROR, StatusCode::OK, 2 ; "server error is retried")]
+    #[tokio::test]
+    async fn test_book_idempotency_key_failure(
+        error: BackendError,
+        first_status: StatusCode,
+        second_status: StatusCode,
+        expected_backend_calls: u64,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+        *mock_backend.0.error.lock().unwrap() = error;
+        let booking = BookingRequest {
+            id: Uuid::new_v4(),
+            client_name: String::from("Stefan"),
+            client_email: None,
+            client_phone: None,
+            booker_notes: None,
+        };
+
+        // The backend only fails the first request
+        let client = Client::new();
+        for (backend_success, status_code) in [(false, first_status), (true, second_status)] {
+            mock_backend
+                .0
+                .success
+                .store(backend_success, Ordering::SeqCst);
+            let response = client
+                .post(format!("http://{addr}/book"))
+                .header("Idempotency-Key", "first")
+                .json(&booking)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        assert_backend_calls(mock_backend, "book", expected_backend_calls);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_book_idempotency_key_concurrent() {
+        let (server, addr, mock_backend, _) = init().await;
+        *mock_backend.0.delay.lock().unwrap() = std::time::Duration::from_millis(200);
+        let booking = BookingRequest {
+            id: Uuid::new_v4(),
+            client_name: String::from("Stefan"),
+            client_email: None,
+            client_phone: None,
+            booker_notes: None,
+        };
+
+        let client = Client::new();
+        let requests = (0..2).map(|_| {
+            client
+                .post(format!("http://{addr}/book"))
+                .header("Idempotency-Key", "first")
+                .json(&booking)
+                .send()
+        });
+        for response in futures::future::join_all(requests).await {
+            assert_eq!(response.unwrap().status(), StatusCode::OK.as_u16());
+        }
+
+        assert_backend_calls(mock_backend, "book", 1);
+        server.abort();
+    }
+
+    #[test_case::test_case ("book", BackendError::NotFound("Timeslot does not exist".into()), StatusCode::NOT_FOUND, "not_found")]
+    #[test_case::test_case ("book", BackendError::AlreadyBooked("Timeslot is already fully booked".into()), StatusCode::CONFLICT, "already_booked")]
+    #[test_case::test_case ("book", BackendError::Other("Database Error".into()), StatusCode::INTERNAL_SERVER_ERROR, "backend_error")]
+    #[test_case::test_case ("remove", BackendError::NotFound("Timeslot does not exist".into()), StatusCode::NOT_FOUND, "not_found")]
+    #[test_case::test_case ("remove", BackendError::Other("Database Error".into()), StatusCode::INTERNAL_SERVER_ERROR, "backend_error")]
+    #[tokio::test]
+    async fn test_backend_error_status(
+        path: &str,
+        error: BackendError,
+        expected_status: StatusCode,
+        expected_code: &str,
+    ) {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        mock_backend.0.success.store(false, Ordering::SeqCst);
+        *mock_backend.0.error.lock().unwrap() = error.clone();
+
+        let client = Client::new();
+        let id = Uuid::new_v4();
+        let response = match path {
+            "remove" => client
+                .delete(format!("http://{addr}/remove"))
+                .header("x-admin-password", "123")
+                .json(&DeleteTimeslotRequest { id }),
+            _ => client
+                .post(format!("http://{addr}/book"))
+                .json(&BookingRequest {
+                    id,
+                    client_name: String::from("Stefan"),
+                    client_email: None,
+                    client_phone: None,
+                    booker_notes: None,
+                }),
+        }
+        .send()
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), expected_status.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["code"], expected_code);
+        assert_eq!(body["error"], error.to_string());
+
+        assert_backend_calls(mock_backend, path, 1);
+        server.abort();
+    }
+
+    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("\n"), client_email: None, client_phone: None, booker_notes: None })]
+    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from(""), client_email: None, client_phone: None, booker_notes: None })]
+    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: Some(String::from("stefan.example.com")), client_phone: None, booker_notes: None })]
+    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: Some(String::from("stefan@")), client_phone: None, booker_notes: None })]
+    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("'"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None })]
+    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 0, duration_minutes: 30, internal_notes: None, category: None })]
+    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 0, internal_notes: None, category: None })]
+    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now() - chrono::Duration::minutes(10), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None })]
+    #[test_case::test_case ("add_bulk", AddTimeslotsBulkRequest { slots: vec![AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, AddTimeslotRequest { datetime: Utc::now(), notes: String::from("'"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }] })]
+    #[test_case::test_case ("add_bulk", AddTimeslotsBulkRequest { slots: vec![] })]
+    #[test_case::test_case ("add_bulk", AddTimeslotsBulkRequest { slots: vec![AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, AddTimeslotRequest { datetime: Utc::now() - chrono::Duration::minutes(10), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }] })]
+    #[test_case::test_case ("edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::from("'"), version: 0, internal_notes: None })]
+    #[test_case::test_case ("edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::new(), version: 0, internal_notes: None })]
+    #[test_case::test_case ("notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::from("'") })]
+    #[test_case::test_case ("notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::new() })]
+    #[tokio::test]
+    async fn test_invalid_input<T>(path: &str, request: T)
+    where
+        T: Serialize,
+    {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        mock_backend.0.success.store(false, Ordering::SeqCst);
+
+        let client = Client::new();
+
+        let request_builder = match path {
+            "edit" => client.put(format!("http://{addr}/{path}")),
+            "notes" => client.patch(format!("http://{addr}/{path}")),
+            _ => client.post(format!("http://{addr}/{path}")),
+        }
+        .header("x-admin-password", password);
+        let response = request_builder.json(&request).send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+
+        assert_backend_calls(mock_backend, path, 0);
+        server.abort();
+    }
+
+    enum Authorization {
+        None,
+        Invalid,
+        Valid,
+        BearerInvalid,
+        BearerValid,
+        BothValid,
+        Mismatched,
+    }
+
+    #[test_case::test_case ("post", "book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: None, client_phone: None, booker_notes: None }, Authorization::None, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: None, client_phone: None, booker_notes: None }, Authorization::Invalid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan"), client_email: None, client_phone: None, booker_notes: None }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "confirm", ConfirmBookingRequest { id: Uuid::new_v4() }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "confirm", ConfirmBookingRequest { id: Uuid::new_v4() }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "confirm", ConfirmBookingRequest { id: Uuid::new_v4() }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::BearerInvalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::BearerValid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::BothValid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "cancel", CancelBookingRequest { id: Uuid::new_v4() }, Authorization::Mismatched, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "add_bulk", AddTimeslotsBulkRequest { slots: vec![AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }] }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "add_bulk", AddTimeslotsBulkRequest { slots: vec![AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes"), capacity: 1, duration_minutes: 30, internal_notes: None, category: None }] }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "reschedule", RescheduleRequest { id: Uuid::new_v4(), new_datetime: Utc::now() + chrono::Duration::hours(1) }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "reschedule", RescheduleRequest { id: Uuid::new_v4(), new_datetime: Utc::now() + chrono::Duration::hours(1) }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "reschedule", RescheduleRequest { id: Uuid::new_v4(), new_datetime: Utc::now() + chrono::Duration::hours(1) }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("put", "edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::from("Example Notes"), version: 0, internal_notes: None }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("put", "edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::from("Example Notes"), version: 0, internal_notes: None }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("put", "edit", EditTimeslotRequest { id: Uuid::new_v4(), datetime: Utc::now(), notes: String::from("Example Notes"), version: 0, internal_notes: None }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("patch", "notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::from("Example Notes") }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("patch", "notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::from("Example Notes") }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("patch", "notes", UpdateNotesRequest { id: Uuid::new_v4(), notes: String::from("Example Notes") }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "swap", SwapBookingsRequest { first_id: Uuid::new_v4(), second_id: Uuid::new_v4() }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "swap", SwapBookingsRequest { first_id: Uuid::new_v4(), second_id: Uuid::new_v4() }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("delete", "remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("delete", "remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "remove_all", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "remove_all", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "restore_last", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "restore_last", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("post", "admin/rotate_key", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "admin/rotate_key", EmptyRequest {  }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "admin/read_only", ReadOnlyRequest { read_only: true }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "admin/read_only", ReadOnlyRequest { read_only: true }, Authorization::Valid, 0, StatusCode::OK)]
+    #[test_case::test_case ("get", "admin_timeslots", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "admin_timeslots", EmptyRequest {  }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "admin_timeslots", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("get", "bookings", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "bookings", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("get", "search", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "archived", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "events", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "events", EmptyRequest {  }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "archived", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("get", "export.csv", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("post", "import.csv", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "export.csv", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("get", "stats", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "stats", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
+    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::Valid, 0,StatusCode::OK)]
+    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::BearerInvalid, 0, StatusCode::UNAUTHORIZED)]
+    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::BearerValid, 0, StatusCode::OK)]
+    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::Mismatched, 0, StatusCode::UNAUTHORIZED)]
+    #[tokio::test]
+    async fn test_authorization<T>(
+        method: &str,
+        path: &str,
+        request: T,
+        authorization: Authorization,
+        expected_backend_calls: u64,
+        status_code: StatusCode,
+    ) where
+        T: Serialize,
+    {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        let wrong_password = String::from("xyz");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let client = Client::new();
+        let mut request_builder = match method.to_lowercase().as_str() {
+            "get" => client.get(format!("http://{addr}/{path}")),
+            "post" => client.post(format!("http://{addr}/{path}")),
+            "put" => client.put(format!("http://{addr}/{path}")),
+            "patch" => client.patch(format!("http://{addr}/{path}")),
+            "delete" => client.delete(format!("http://{addr}/{path}")),
+            _ => panic!("Unsupported HTTP method: {method}"),
+        };
+        request_builder = match authorization {
+            Authorization::None => request_builder,
+            Authorization::Invalid => request_builder.header("x-admin-password", wrong_password),
+            Authorization::Valid => request_builder.header("x-admin-password", password),
+            Authorization::BearerInvalid => request_builder.bearer_auth(wrong_password),
+            Authorization::BearerValid => request_builder.bearer_auth(password),
+            Authorization::BothValid => request_builder
+                .header("x-admin-password", password.clone())
+                .bearer_auth(password),
+            Authorization::Mismatched => request_builder
+                .header("x-admin-password", password)
+                .bearer_auth(wrong_password),
+        };
+        let response = request_builder.json(&request).send().await.unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_backend_calls(mock_backend, path, expected_backend_calls);
+        server.abort();
+    }
+
+    #[test_case::test_case("get", "", StatusCode::OK ; "frontend")]
+    #[test_case::test_case("get", "timeslots", StatusCode::OK ; "timeslot stream")]
+    #[test_case::test_case("get", "timeslots_snapshot", StatusCode::OK ; "snapshot")]
+    #[test_case::test_case("get", "timeslots_by_day", StatusCode::OK ; "timeslots by day")]
+    #[test_case::test_case("get", "admin_timeslots", StatusCode::OK ; "admin timeslots")]
+    #[test_case::test_case("post", "book", StatusCode::SERVICE_UNAVAILABLE ; "book")]
+    #[test_case::test_case("post", "book_any", StatusCode::SERVICE_UNAVAILABLE ; "book any")]
+    #[test_case::test_case("post", "waitlist", StatusCode::SERVICE_UNAVAILABLE ; "waitlist")]
+    #[test_case::test_case("post", "add", StatusCode::SERVICE_UNAVAILABLE ; "add")]
+    #[test_case::test_case("post", "cancel", StatusCode::SERVICE_UNAVAILABLE ; "cancel")]
+    #[test_case::test_case("delete", "remove", StatusCode::SERVICE_UNAVAILABLE ; "remove")]
+    #[test_case::test_case("post", "remove_all", StatusCode::SERVICE_UNAVAILABLE ; "remove all")]
+    #[tokio::test]
+    async fn test_read_only(method: &str, path: &str, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        mock_configuration.0.read_only.store(true, Ordering::SeqCst);
+        let (server, addr, mock_backend, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let request_builder = match method {
+            "get" => client.get(format!("http://{addr}/{path}")),
+            "post" => client.post(format!("http://{addr}/{path}")),
+            "delete" => client.delete(format!("http://{addr}/{path}")),
+            _ => panic!("Unsupported HTTP method: {method}"),
+        };
+        // The mode is checked before the body is parsed, so any body is fine
+        let response = request_builder
+            .header("x-admin-password", "123")
+            .json(&EmptyRequest {})
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::SERVICE_UNAVAILABLE {
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["code"], "read_only");
+            assert_backend_calls(mock_backend, path, 0);
+        }
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_toggle_read_only() {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+
+        let client = Client::new();
+        let set_read_only = |read_only: bool| {
+            client
+                .post(format!("http://{addr}/admin/read_only"))
+                .header("x-admin-password", "123")
+                .json(&ReadOnlyRequest { read_only })
+                .send()
+        };
+        let book = || {
+            client
+                .post(format!("http://{addr}/book"))
+                .json(&BookingRequest {
+                    id: Uuid::new_v4(),
+                    client_name: "Stefan".into(),
+                    client_email: None,
+                    client_phone: None,
+                    booker_notes: None,
+                })
+                .send()
+        };
+
+        assert_eq!(
+            set_read_only(true).await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        assert!(mock_configuration.read_only());
+        assert_eq!(
+            book().await.unwrap().status(),
+            StatusCode::SERVICE_UNAVAILABLE.as_u16()
+        );
+
+        // The mode can be switched off again, although it blocks the other admin changes
+        assert_eq!(
+            set_read_only(false).await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        assert!(!mock_configuration.read_only());
+        assert_eq!(book().await.unwrap().status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            mock_backend.0.calls_to_book_timeslot.load(Ordering::SeqCst),
+            1
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key() {
+        let (server, addr, _, mock_configuration) = init().await;
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/admin/rotate_key"))
+            .header("x-admin-password", "123")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: RotateKeyResponse = response.json().await.unwrap();
+        assert_ne!(data.key, "123");
+
+        for (password, status_code) in [
+            ("123", StatusCode::UNAUTHORIZED),
+            (data.key.as_str(), StatusCode::OK),
+        ] {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", password)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_named_admins() {
+        let (server, addr, _, mock_configuration) = init().await;
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.admins.lock().unwrap() = vec![
+            Admin::from_str("alice:alice-key").unwrap(),
+            Admin::from_str("bob:bob-key").unwrap(),
+        ];
+
+        let client = Client::new();
+        for (password, admin_name) in [("123", "admin"), ("alice-key", "alice"), ("bob-key", "bob")]
+        {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", password)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK.as_u16());
+            assert_eq!(response.text().await.unwrap(), admin_name);
+        }
+
+        // Rotating the key only affects the calling admin
+        let response = client
+            .post(format!("http://{addr}/admin/rotate_key"))
+            .header("x-admin-password", "alice-key")
+            .send()
+            .await
+            .unwrap();
+        let data: RotateKeyResponse = response.json().await.unwrap();
+        for (password, status_code) in [
+            ("alice-key", StatusCode::UNAUTHORIZED),
+            (data.key.as_str(), StatusCode::OK),
+            ("bob-key", StatusCode::OK),
+            ("123", StatusCode::OK),
+        ] {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", password)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_authorization_with_password_hash() {
+        use argon2::password_hash::{PasswordHasher, SaltString};
+
+        let (server, addr, _, mock_configuration) = init().await;
+        let salt = SaltString::from_b64("c29tZXNhbHRzb21lc2FsdA").unwrap();
+        let password_hash = argon2::Argon2::default()
+            .hash_password(b"123", &salt)
+            .unwrap()
+            .to_string();
+        *mock_configuration.0.password_hash.lock().unwrap() = Some(password_hash.clone());
+
+        let client = Client::new();
+        for (password, status_code) in [
+            ("123", StatusCode::OK),
+            ("xyz", StatusCode::UNAUTHORIZED),
+            (password_hash.as_str(), StatusCode::UNAUTHORIZED),
+        ] {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", password)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_auth_lockout() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.auth_lockout_attempts.lock().unwrap() = 3;
+        *mock_configuration.0.auth_lockout_seconds.lock().unwrap() = 60;
+        let (server, addr, _, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        // A success clears the failed attempts before it
+        for (password, status_code) in [
+            ("xyz", StatusCode::UNAUTHORIZED),
+            ("xyz", StatusCode::UNAUTHORIZED),
+            ("123", StatusCode::OK),
+            ("xyz", StatusCode::UNAUTHORIZED),
+            ("xyz", StatusCode::UNAUTHORIZED),
+            ("xyz", StatusCode::UNAUTHORIZED),
+        ] {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", password)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        // Even the correct password is rejected while locked out
+        let response = client
+            .get(format!("http://{addr}/admin_page"))
+            .header("x-admin-password", "123")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS.as_u16());
+        let retry_after: u64 = response.headers()["retry-after"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after));
+        let error: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(error["code"], "locked_out");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_auth_lockout_concurrent_guesses() {
+        use argon2::password_hash::{PasswordHasher, SaltString};
+
+        let mock_configuration = MockConfiguration::new();
+        let salt = SaltString::from_b64("c29tZXNhbHRzb21lc2FsdA").unwrap();
+        // Hashed, so the guesses are verified concurrently
+        *mock_configuration.0.password_hash.lock().unwrap() = Some(
+            argon2::Argon2::default()
+                .hash_password(b"123", &salt)
+                .unwrap()
+                .to_string(),
+        );
+        *mock_configuration.0.auth_lockout_attempts.lock().unwrap() = 3;
+        *mock_configuration.0.auth_lockout_seconds.lock().unwrap() = 60;
+        let (server, addr, _, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let guesses = (0..10).map(|guess| {
+            client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", format!("guess{guess}"))
+                .send()
+        });
+        let responses = futures::future::join_all(guesses).await;
+
+        let verified = responses
+            .iter()
+            .filter(|response| {
+                response.as_ref().unwrap().status() == StatusCode::UNAUTHORIZED.as_u16()
+            })
+            .count();
+        assert!(verified <= 3);
+        assert!(responses.iter().all(|response| {
+            let status = response.as_ref().unwrap().status();
+            status == StatusCode::UNAUTHORIZED.as_u16()
+                || status == StatusCode::TOO_MANY_REQUESTS.as_u16()
+        }));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_auth_lockout_expiry() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.auth_lockout_attempts.lock().unwrap() = 2;
+        *mock_configuration.0.auth_lockout_seconds.lock().unwrap() = 1;
+        let (server, addr, _, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        for (password, status_code) in [
+            ("xyz", StatusCode::UNAUTHORIZED),
+            ("xyz", StatusCode::UNAUTHORIZED),
+            ("123", StatusCode::TOO_MANY_REQUESTS),
+        ] {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", password)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let response = client
+            .get(format!("http://{addr}/admin_page"))
+            .header("x-admin-password", "123")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.rate_limit_per_minute.lock().unwrap() = 3;
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, mock_backend, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let booking = BookingRequest {
+            id: Uuid::new_v4(),
+            client_name: "Stefan".into(),
+            client_email: None,
+            client_phone: None,
+            booker_notes: None,
+        };
+        for _ in 0..3 {
+            let response = client
+                .post(format!("http://{addr}/book"))
+                .json(&booking)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK.as_u16());
+        }
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&booking)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS.as_u16());
+        assert_backend_calls(mock_backend.clone(), "book", 3);
+
+        // Admin routes are not rate limited
+        for _ in 0..5 {
+            let response = client
+                .get(format!("http://{addr}/admin_page"))
+                .header("x-admin-password", "123")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK.as_u16());
+        }
+
+        server.abort();
+    }
+
+    #[test_case::test_case("health", true, StatusCode::OK, 0)]
+    #[test_case::test_case("health", false, StatusCode::OK, 0)]
+    #[test_case::test_case("ready", true, StatusCode::OK, 1)]
+    #[test_case::test_case("ready", false, StatusCode::SERVICE_UNAVAILABLE, 1)]
+    #[tokio::test]
+    async fn test_probes(
+        path: &str,
+        backend_success: bool,
+        status_code: StatusCode,
+        expected_backend_calls: u64,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+        mock_backend
+            .0
+            .success
+            .store(backend_success, Ordering::SeqCst);
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/{path}"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_eq!(
+            mock_backend.0.calls_to_health_check.load(Ordering::SeqCst),
+            expected_backend_calls
+        );
+        if status_code == StatusCode::SERVICE_UNAVAILABLE {
+            let body: ApiErrorBody = response.json().await.unwrap();
+            assert_eq!(body.error, "Database Error. Health check failed");
+        }
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_frontend() {
+        let (server, addr, _, mock_configuration) = init().await;
+
+        let mut tmp_file = NamedTempFile::new().unwrap();
+        let expected_html = r#"<!DOCTYPE html>
+<html>
+<head><title>Test</title></head>
+<body><h1>Test</h1></body>
+</html>"#;
+        write!(tmp_file, "{expected_html}").unwrap();
+        *mock_configuration.0.frontend_path.lock().unwrap() = tmp_file.path().to_path_buf();
+
+        let client = Client::new();
+        let response = client.get(format!("http://{addr}/")).send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response
+                .headers()
+                .get("content-type")
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "text/html; charset=utf-8"
+        );
+
+        let actual_html = response.text().await.unwrap();
+        assert_eq!(actual_html, expected_html);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_base_path() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.base_path.lock().unwrap() = "/booking".into();
+        let mut tmp_file = NamedTempFile::new().unwrap();
+        write!(
+            tmp_file,
+            "const apiAddress = `${{origin}}generic_base_path`;"
+        )
+        .unwrap();
+        *mock_configuration.0.frontend_path.lock().unwrap() = tmp_file.path().to_path_buf();
+        let (server, addr, _, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let get = |path: &str| client.get(format!("http://{addr}{path}")).send();
+
+        let response = get("/booking/timeslots").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response.headers()["content-type"].to_str().unwrap(),
+            "text/event-stream"
+        );
+        for path in ["/booking/timeslots_snapshot", "/booking/count"] {
+            assert_eq!(get(path).await.unwrap().status(), StatusCode::OK.as_u16());
+        }
+        assert_eq!(
+            get("/booking/admin_timeslots").await.unwrap().status(),
+            StatusCode::UNAUTHORIZED.as_u16()
+        );
+
+        // The frontend calls the routes under the base path
+        let response = get("/booking").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response.text().await.unwrap(),
+            "const apiAddress = `${origin}/booking`;"
+        );
+
+        // Only the probes are left at the root
+        assert_eq!(
+            get("/timeslots_snapshot").await.unwrap().status(),
+            StatusCode::NOT_FOUND.as_u16()
+        );
+        assert_eq!(
+            get("/health").await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        server.abort();
+    }
+
+    /// Collects the log lines written by a subscriber
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test_case::test_case(Level::INFO, true ; "logged")]
+    #[test_case::test_case(Level::DEBUG, false ; "below max level")]
+    #[tokio::test]
+    async fn test_access_log(access_log_level: Level, logged: bool) {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_max_level(Level::INFO)
+            .with_ansi(false)
+            .finish();
+        // The test runtime is single threaded, so the server logs to this subscriber as well
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.access_log_level.lock().unwrap() = access_log_level;
+        let (server, addr, _) = init_with_local_backend(mock_configuration).await;
+        let response = Client::new()
+            .get(format!("http://{addr}/booking/ABCD2345"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND.as_u16());
+        server.abort();
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let access_log_line = logs.lines().find(|line| line.contains("Request handled"));
+        assert_eq!(access_log_line.is_some(), logged);
+        if let Some(line) = access_log_line {
+            assert!(line.contains("method=GET"));
+            assert!(line.contains("path=\"/booking/ABCD2345\""));
+            assert!(line.contains("status=404"));
+            assert!(line.contains("latency_ms="));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_frontend_etag() {
+        let (server, addr, _, mock_configuration) = init().await;
+        let mut tmp_file = NamedTempFile::new().unwrap();
+        write!(tmp_file, "<h1>First Version</h1>").unwrap();
+        *mock_configuration.0.frontend_path.lock().unwrap() = tmp_file.path().to_path_buf();
+
+        let client = Client::new();
+        let response = client.get(format!("http://{addr}/")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let etag = response.headers()["etag"].to_str().unwrap().to_string();
+        assert_eq!(response.text().await.unwrap(), "<h1>First Version</h1>");
+
+        let response = client
+            .get(format!("http://{addr}/"))
+            .header("If-None-Match", &etag)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED.as_u16());
+        assert_eq!(response.headers()["etag"].to_str().unwrap(), etag);
+        assert!(response.text().await.unwrap().is_empty());
+
+        // A changed file is read again and gets a new ETag
+        std::fs::write(tmp_file.path(), "<h1>Second Version</h1>").unwrap();
+        let modified = std::fs::metadata(tmp_file.path())
+            .unwrap()
+            .modified()
+            .unwrap();
+        tmp_file
+            .as_file()
+            .set_modified(modified + Duration::from_secs(1))
+            .unwrap();
+        let response = client
+            .get(format!("http://{addr}/"))
+            .header("If-None-Match", &etag)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_ne!(response.headers()["etag"].to_str().unwrap(), etag);
+        assert_eq!(response.text().await.unwrap(), "<h1>Second Version</h1>");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_frontend_fallback() {
+        let (server, addr, _, mock_configuration) = init().await;
+        *mock_configuration.0.frontend_path.lock().unwrap() =
+            std::path::PathBuf::from("/nonexistent/frontend/index.html");
+
+        let response = Client::new()
+            .get(format!("http://{addr}/"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let html = response.text().await.unwrap();
+        assert!(!html.contains("generic_timeslot_booking_manager_name"));
+        assert!(html.contains("currently unavailable"));
+        assert!(!html.contains("nonexistent"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_frontend_timezone() {
+        let (server, addr, _, mock_configuration) = init().await;
+        *mock_configuration.0.display_timezone.lock().unwrap() = "Europe/Vienna".into();
+
+        let mut tmp_file = NamedTempFile::new().unwrap();
+        write!(
+            tmp_file,
+            "<script>const displayTimezone = 'generic_timeslot_timezone';</script>"
+        )
+        .unwrap();
+        *mock_configuration.0.frontend_path.lock().unwrap() = tmp_file.path().to_path_buf();
+
+        let client = Client::new();
+        let response = client.get(format!("http://{addr}/")).send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response.text().await.unwrap(),
+            "<script>const displayTimezone = 'Europe/Vienna';</script>"
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_frontend_max_len() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.notes_max_len.lock().unwrap() = 200;
+        let (server, addr, _, mock_configuration) =
+            init_with_configuration(mock_configuration).await;
+
+        let mut tmp_file = NamedTempFile::new().unwrap();
+        write!(
+            tmp_file,
+            r#"<input maxlength="generic_name_max_len"><textarea maxlength="generic_notes_max_len">"#
+        )
+        .unwrap();
+        *mock_configuration.0.frontend_path.lock().unwrap() = tmp_file.path().to_path_buf();
+
+        let client = Client::new();
+        let response = client.get(format!("http://{addr}/")).send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response.text().await.unwrap(),
+            r#"<input maxlength="20"><textarea maxlength="200">"#
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_book_timeslot_returns_confirmation_code() {
+        let (server, addr, _, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: BookingResponse = response.json().await.unwrap();
+        assert_eq!(data.confirmation_code, MOCK_CONFIRMATION_CODE);
+
+        server.abort();
+    }
+
+    #[test_case::test_case("Stefan", 1, StatusCode::OK)]
+    #[test_case::test_case("", 0, StatusCode::BAD_REQUEST)]
+    #[test_case::test_case("Stefan'", 0, StatusCode::BAD_REQUEST)]
+    #[tokio::test]
+    async fn test_join_waitlist(
+        client_name: &str,
+        expected_backend_calls: u64,
+        status_code: StatusCode,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/waitlist"))
+            .json(&WaitlistRequest {
+                id: Uuid::new_v4(),
+                client_name: client_name.into(),
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_backend_calls(mock_backend, "waitlist", expected_backend_calls);
+        if status_code == StatusCode::OK {
+            let data: WaitlistResponse = response.json().await.unwrap();
+            assert_eq!(data.position, 1);
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_edit_timeslot_stale_version() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        let datetime = Utc::now() + chrono::Duration::hours(1);
+        backend
+            .add_timeslot(datetime, "Workshop".into(), 1, 30, None, None)
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+
+        // Both admins load version 0, the second edit is based on an outdated state
+        let client = Client::new();
+        for (notes, status_code) in [
+            ("First Edit", StatusCode::OK),
+            ("Second Edit", StatusCode::CONFLICT),
+        ] {
+            let response = client
+                .put(format!("http://{addr}/edit"))
+                .header("x-admin-password", "123")
+                .json(&EditTimeslotRequest {
+                    id,
+                    datetime,
+                    notes: notes.into(),
+                    version: 0,
+                    internal_notes: None,
+                })
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status_code.as_u16());
+        }
+
+        let timeslot = backend.current_timeslots().unwrap()[0].clone();
+        assert_eq!(timeslot.notes, "First Edit");
+        assert_eq!(timeslot.version, 1);
+
+        server.abort();
+    }
+
+    #[test_case::test_case (2, StatusCode::OK ; "future")]
+    #[test_case::test_case (-1, StatusCode::BAD_REQUEST ; "past")]
+    #[tokio::test]
+    async fn test_reschedule(offset_hours: i64, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        let datetime = Utc::now() + chrono::Duration::hours(1);
+        backend
+            .add_timeslot(datetime, "Workshop".into(), 1, 30, None, None)
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        backend
+            .book_timeslot(id, "Stefan".into(), None, None, None)
+            .unwrap();
+
+        let new_datetime = Utc::now() + chrono::Duration::hours(offset_hours);
+        let response = Client::new()
+            .post(format!("http://{addr}/reschedule"))
+            .header("x-admin-password", "123")
+            .json(&RescheduleRequest { id, new_datetime })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        let timeslot = backend.current_timeslots().unwrap()[0].clone();
+        let expected = if status_code == StatusCode::OK {
+            new_datetime
+        } else {
+            datetime
+        };
+        assert_eq!(timeslot.datetime, expected);
+        assert_eq!(timeslot.booker_name, "Stefan");
+        assert!(!timeslot.available);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_unbooked_or_passed_timeslot_conflict() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        for hours in [-2, 1] {
+            backend
+                .add_timeslot(
+                    Utc::now() + chrono::Duration::hours(hours),
+                    "Workshop".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let timeslots = backend.current_timeslots().unwrap();
+        let (passed_id, id) = (timeslots[0].id, timeslots[1].id);
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/cancel"))
+            .header("x-admin-password", "123")
+            .json(&CancelBookingRequest { id })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        let body: ApiErrorBody = response.json().await.unwrap();
+        assert_eq!(body.code, "conflict");
+
+        let response = client
+            .post(format!("http://{addr}/reschedule"))
+            .header("x-admin-password", "123")
+            .json(&RescheduleRequest {
+                id,
+                new_datetime: Utc::now() + chrono::Duration::hours(3),
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: passed_id,
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        assert_eq!(backend.current_timeslots().unwrap(), timeslots);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_waitlist_promotion() {
+        let (webhook_server, webhook_url, mut webhook_receiver) = init_webhook_receiver().await;
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.webhook_url.lock().unwrap() = Some(webhook_url);
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+
+        let client = Client::new();
+        let join_waitlist = || {
+            client
+                .post(format!("http://{addr}/waitlist"))
+                .json(&WaitlistRequest {
+                    id,
+                    client_name: "Peter".into(),
+                })
+                .send()
+        };
+        // An available timeslot is booked instead
+        let response = join_waitlist().await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id,
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        let booking: BookingResponse = response.json().await.unwrap();
+        timeout(Duration::from_secs(1), webhook_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let response = join_waitlist().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: WaitlistResponse = response.json().await.unwrap();
+        assert_eq!(data.position, 1);
+
+        let response = client
+            .post(format!("http://{addr}/cancel_own"))
+            .json(&CancelOwnBookingRequest {
+                code: booking.confirmation_code,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        // The promotion is a new booking
+        let payload = timeout(Duration::from_secs(1), webhook_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(payload.id, id);
+        assert_eq!(payload.booker_name, "Peter");
+        assert!(payload.waitlist.is_empty());
+        assert_eq!(backend.current_timeslots().unwrap()[0], payload);
+
+        server.abort();
+        webhook_server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_waitlist_promotion_of_group() {
+        let (webhook_server, webhook_url, mut webhook_receiver) = init_webhook_receiver().await;
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.webhook_url.lock().unwrap() = Some(webhook_url);
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        for client_name in ["Stefan", "Maria"] {
+            backend
+                .book_timeslot(id, client_name.into(), None, None, None)
+                .unwrap();
+        }
+        for client_name in ["Peter", "Anna", "Paul"] {
+            backend.join_waitlist(id, client_name.into()).unwrap();
+        }
+
+        let response = Client::new()
+            .post(format!("http://{addr}/cancel"))
+            .header("x-admin-password", "123")
+            .json(&CancelBookingRequest { id })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        // Both released places are taken over, each of them is a new booking
+        for _ in 0..2 {
+            let payload = timeout(Duration::from_secs(1), webhook_receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(payload.booker_name, "Peter, Anna");
+        }
+        assert!(timeout(Duration::from_millis(100), webhook_receiver.recv())
+            .await
+            .is_err());
+        let timeslot = backend.current_timeslots().unwrap()[0].clone();
+        assert_eq!(timeslot.booked_count, 2);
+        assert_eq!(timeslot.waitlist, vec![String::from("Paul")]);
+
+        server.abort();
+        webhook_server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_add_timeslots_bulk_returns_count() {
+        let (server, addr, _, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let slot = AddTimeslotRequest {
+            datetime: Utc::now(),
+            notes: "Example Notes".into(),
+            capacity: 1,
+            duration_minutes: 30,
+            internal_notes: None,
+            category: None,
+        };
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add_bulk"))
+            .header("x-admin-password", password)
+            .json(&AddTimeslotsBulkRequest {
+                slots: vec![slot.clone(), slot.clone(), slot],
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: AddTimeslotsBulkResponse = response.json().await.unwrap();
+        assert_eq!(data.count, 3);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_add_timeslots_bulk_dry_run() {
+        let mock_configuration = MockConfiguration::new();
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        let backend = LocalTimeslots::new(chrono::Duration::days(1), false, None, false);
+        let mut timeslot_stream = backend.timeslot_stream();
+        read_from_timeslot_stream(&mut timeslot_stream).await;
+        let app = create_app(backend.clone(), mock_configuration, ShutdownSignal::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        let start = Utc::now() + chrono::Duration::hours(1);
+        let request = AddTimeslotsBulkRequest {
+            slots: (0..3)
+                .map(|hours| AddTimeslotRequest {
+                    datetime: start + chrono::Duration::hours(hours),
+                    notes: format!("Timeslot {hours}"),
+                    capacity: 2,
+                    duration_minutes: 30,
+                    internal_notes: None,
+                    category: None,
+                })
+                .collect(),
+        };
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add_bulk?dry_run=true"))
+            .header("x-admin-password", password.clone())
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let preview: Vec<Timeslot> = response.json().await.unwrap();
+        assert_eq!(preview.len(), 3);
+
+        // Nothing was added or sent
+        assert!(backend.current_timeslots().unwrap().is_empty());
+        assert!(timeout(Duration::from_millis(100), timeslot_stream.next())
+            .await
+            .is_err());
+
+        let response = client
+            .post(format!("http://{addr}/add_bulk"))
+            .header("x-admin-password", password.clone())
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let without_ids = |timeslots: Vec<Timeslot>| {
+            timeslots
+                .into_iter()
+                .map(|timeslot| Timeslot {
+                    id: Uuid::nil(),
+                    ..timeslot
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            without_ids(backend.current_timeslots().unwrap()),
+            without_ids(preview)
+        );
+
+        // The checks of adding the timeslots apply to the preview as well
+        let response = client
+            .post(format!("http://{addr}/add_bulk?dry_run=true"))
+            .header("x-admin-password", password)
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        assert_eq!(backend.current_timeslots().unwrap().len(), 3);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_add_recurring_timeslots_dry_run() {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add_recurring?dry_run=true"))
+            .header("x-admin-password", password)
+            .json(&recurring_request(
+                "2030-01-01T09:00:00Z".parse().unwrap(),
+                4,
+                None,
+            ))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let preview: Vec<Timeslot> = response.json().await.unwrap();
+        assert_eq!(
+            preview
+                .iter()
+                .map(|timeslot| timeslot.datetime.to_rfc3339())
+                .collect::<Vec<_>>(),
+            [
+                "2030-01-01T09:00:00+00:00",
+                "2030-01-08T09:00:00+00:00",
+                "2030-01-15T09:00:00+00:00",
+                "2030-01-22T09:00:00+00:00"
+            ]
+        );
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_check_timeslots_bulk
+                .load(Ordering::SeqCst),
+            1
+        );
+        assert_backend_calls(mock_backend, "add", 0);
+
+        server.abort();
+    }
+
+    fn recurring_request(
+        datetime: DateTime<Utc>,
+        count: u32,
+        timezone: Option<&str>,
+    ) -> AddRecurringTimeslotsRequest {
+        AddRecurringTimeslotsRequest {
+            datetime,
+            interval: Interval::Weekly,
+            count,
+            notes: "Example Notes".into(),
+            capacity: 1,
+            duration_minutes: 30,
+            timezone: timezone.map(Into::into),
+            internal_notes: None,
+            category: None,
+        }
+    }
+
+    #[test_case::test_case(recurring_request(Utc::now() + chrono::Duration::hours(1), 8, None), StatusCode::OK, 8)]
+    #[test_case::test_case(recurring_request(Utc::now() + chrono::Duration::hours(1), 8, Some("Europe/Vienna")), StatusCode::OK, 8)]
+    #[test_case::test_case(recurring_request(Utc::now() - chrono::Duration::days(15), 8, None), StatusCode::OK, 5)]
+    #[test_case::test_case(recurring_request(Utc::now() + chrono::Duration::hours(1), 0, None), StatusCode::BAD_REQUEST, 0)]
+    #[test_case::test_case(recurring_request(Utc::now() + chrono::Duration::hours(1), 8, Some("Mars/Olympus")), StatusCode::BAD_REQUEST, 0)]
+    #[tokio::test]
+    async fn test_add_recurring_timeslots(
+        request: AddRecurringTimeslotsRequest,
+        status_code: StatusCode,
+        expected_backend_calls: u64,
+    ) {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add_recurring"))
+            .header("x-admin-password", password)
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::OK {
+            let data: AddTimeslotsBulkResponse = response.json().await.unwrap();
+            assert_eq!(data.count as u64, expected_backend_calls);
+        }
+        assert_backend_calls(mock_backend, "add", expected_backend_calls);
+
+        server.abort();
+    }
+
+    #[test_case::test_case(&[], "http://example.com", Some("*"); "any origin")]
+    #[test_case::test_case(&["http://example.com"], "http://example.com", Some("http://example.com"); "allowed origin")]
+    #[test_case::test_case(&["http://example.com"], "http://evil.com", None; "disallowed origin")]
+    #[tokio::test]
+    async fn test_cors_preflight(
+        allowed_origins: &[&str],
+        origin: &str,
+        expected_allow_origin: Option<&str>,
+    ) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.allowed_origins.lock().unwrap() =
+            allowed_origins.iter().map(ToString::to_string).collect();
+        let (server, addr, _, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let response = client
+            .request(reqwest::Method::OPTIONS, format!("http://{addr}/book"))
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response
+                .headers()
+                .get("access-control-allow-origin")
+                .map(|value| value.to_str().unwrap()),
+            expected_allow_origin
+        );
+
+        server.abort();
+    }
+
+    #[test_case::test_case(None; "generated request id")]
+    #[test_case::test_case(Some("my-request-id"); "propagated request id")]
+    #[tokio::test]
+    async fn test_request_id(request_id: Option<&str>) {
+        let (server, addr, _, _) = init().await;
+
+        let client = Client::new();
+        let mut request = client.get(format!("http://{addr}/health"));
+        if let Some(request_id) = request_id {
+            request = request.header("x-request-id", request_id);
+        }
+        let response = request.send().await.unwrap();
+
+        let response_request_id = response
+            .headers()
+            .get("x-request-id")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(!response_request_id.is_empty());
+        if let Some(request_id) = request_id {
+            assert_eq!(response_request_id, request_id);
+        }
+
+        server.abort();
+    }
+
+    async fn scrape_metrics(client: &Client, addr: SocketAddr) -> String {
+        client
+            .get(format!("http://{addr}/metrics"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap()
+    }
+
+    fn metric_value(metrics: &str, name: &str) -> u64 {
+        metrics
+            .lines()
+            .find_map(|line| line.strip_prefix(&format!("{name} ")))
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_version() {
+        let (server, addr, _, _) = init().await;
+
+        let response = Client::new()
+            .get(format!("http://{addr}/version"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let version: VersionResponse = response.json().await.unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        assert!(!version.git_hash.is_empty());
+        assert!(version
+            .build_timestamp
+            .is_some_and(|build_timestamp| build_timestamp <= Utc::now()));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        let (server, addr, _, _) = init().await;
+        let client = Client::new();
+
+        let bookings_before = metric_value(&scrape_metrics(&client, addr).await, BOOKINGS_TOTAL);
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        // Other tests book concurrently, so only an increase can be asserted
+        let metrics = scrape_metrics(&client, addr).await;
+        assert!(metric_value(&metrics, BOOKINGS_TOTAL) > bookings_before);
+        assert!(metrics.contains(&format!("{REQUEST_DURATION_SECONDS}_bucket")));
+        assert!(metrics.contains("path=\"/book\""));
+
+        server.abort();
+    }
+
+    #[test_case::test_case("x-booking-key", StatusCode::OK; "configured header")]
+    #[test_case::test_case("x-admin-password", StatusCode::UNAUTHORIZED; "default header")]
+    #[tokio::test]
+    async fn test_custom_auth_header_name(header_name: &str, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.auth_header_name.lock().unwrap() = "x-booking-key".into();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, _, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/admin_page"))
+            .header(header_name, "123")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::UNAUTHORIZED {
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["error"], "Missing credentials");
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_validation_error_json() {
+        let (server, addr, _, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: String::new(),
+                client_email: Some("stefan@".into()),
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"], "Invalid input");
+        assert_eq!(body["code"], "invalid_input");
+        assert_eq!(body["fields"]["client_name"][0]["code"], "length");
+        assert_eq!(body["fields"]["client_email"][0]["code"], "email");
+        assert_eq!(
+            body["fields"]["client_email"][0]["message"],
+            "Invalid email address"
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_add_timeslot_in_past_json() {
+        let (server, addr, _, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", password)
+            .json(&AddTimeslotRequest {
+                datetime: Utc::now() - chrono::Duration::hours(1),
+                notes: "Example Notes".into(),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["fields"]["datetime"][0]["code"], "in_past");
+
+        server.abort();
+    }
+
+    #[test_case::test_case("", "Workshop" ; "empty notes")]
+    #[test_case::test_case("Lab", "Lab" ; "provided notes")]
+    #[tokio::test]
+    async fn test_add_timeslot_default_notes(notes: &str, expected: &str) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.default_notes.lock().unwrap() = "Workshop".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", "123")
+            .json(&AddTimeslotRequest {
+                datetime: Utc::now() + chrono::Duration::hours(1),
+                notes: notes.into(),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(backend.current_timeslots().unwrap()[0].notes, expected);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_add_timeslot_invalid_default_notes() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.default_notes.lock().unwrap() = "x".repeat(61);
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", "123")
+            .json(&serde_json::json!({ "datetime": Utc::now() + chrono::Duration::hours(1) }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        assert!(backend.current_timeslots().unwrap().is_empty());
+
+        server.abort();
+    }
+
+    #[test_case::test_case("2030-01-31T10:07:00Z", 15, Tz::UTC, "2030-01-31T10:00:00Z" ; "round down")]
+    #[test_case::test_case("2030-01-31T10:08:00Z", 15, Tz::UTC, "2030-01-31T10:15:00Z" ; "round up")]
+    #[test_case::test_case("2030-01-31T10:07:30Z", 15, Tz::UTC, "2030-01-31T10:15:00Z" ; "tie rounds up")]
+    #[test_case::test_case("2030-01-31T10:15:00Z", 15, Tz::UTC, "2030-01-31T10:15:00Z" ; "aligned")]
+    #[test_case::test_case("2030-01-31T23:55:00Z", 15, Tz::UTC, "2030-02-01T00:00:00Z" ; "next day")]
+    #[test_case::test_case("2030-01-31T04:50:00Z", 60, chrono_tz::Asia::Kolkata, "2030-01-31T04:30:00Z" ; "local wall clock")]
+    fn test_align_to_granularity(
+        datetime: &str,
+        granularity_minutes: i64,
+        timezone: Tz,
+        expected: &str,
+    ) {
+        assert_eq!(
+            align_to_granularity(datetime.parse().unwrap(), granularity_minutes, timezone),
+            expected.parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test_case::test_case(false, StatusCode::OK ; "round")]
+    #[test_case::test_case(true, StatusCode::BAD_REQUEST ; "reject")]
+    #[tokio::test]
+    async fn test_add_timeslot_granularity(reject_unaligned: bool, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration
+            .0
+            .slot_granularity_minutes
+            .lock()
+            .unwrap() = Some(15);
+        mock_configuration
+            .0
+            .reject_unaligned_slots
+            .store(reject_unaligned, Ordering::SeqCst);
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let day = (Utc::now() + chrono::Duration::days(2)).date_naive();
+        let client = Client::new();
+        for (minute, expected_status) in [(15, StatusCode::OK), (7, status_code)] {
+            let response = client
+                .post(format!("http://{addr}/add"))
+                .header("x-admin-password", "123")
+                .json(&AddTimeslotRequest {
+                    datetime: day.and_hms_opt(10, minute, 0).unwrap().and_utc(),
+                    notes: "Workshop".into(),
+                    capacity: 1,
+                    duration_minutes: 15,
+                    internal_notes: None,
+                    category: None,
+                })
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected_status.as_u16());
+        }
+
+        let starts: Vec<DateTime<Utc>> = backend
+            .current_timeslots()
+            .unwrap()
+            .iter()
+            .map(|timeslot| timeslot.datetime)
+            .collect();
+        let mut expected = vec![day.and_hms_opt(10, 15, 0).unwrap().and_utc()];
+        if !reject_unaligned {
+            expected.insert(0, day.and_hms_opt(10, 0, 0).unwrap().and_utc());
+        }
+        assert_eq!(starts, expected);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_add_timeslots_bulk_and_import_granularity() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration
+            .0
+            .slot_granularity_minutes
+            .lock()
+            .unwrap() = Some(15);
+        mock_configuration
+            .0
+            .reject_unaligned_slots
+            .store(true, Ordering::SeqCst);
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let day = (Utc::now() + chrono::Duration::days(2)).date_naive();
+        let slot = |minute| AddTimeslotRequest {
+            datetime: day.and_hms_opt(10, minute, 0).unwrap().and_utc(),
+            notes: "Workshop".into(),
+            capacity: 1,
+            duration_minutes: 15,
+            internal_notes: None,
+            category: None,
+        };
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add_bulk"))
+            .header("x-admin-password", "123")
+            .json(&AddTimeslotsBulkRequest {
+                slots: vec![slot(0), slot(22)],
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        let body: ApiErrorBody = response.json().await.unwrap();
+        assert_eq!(body.code, "unaligned_datetime");
+
+        let response = client
+            .post(format!("http://{addr}/import.csv"))
+            .header("x-admin-password", "123")
+            .header("content-type", "text/csv")
+            .body(format!(
+                "datetime,notes\n{day}T10:00:00Z,Workshop\n{day}T10:22:00Z,Workshop\n"
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        let body: ApiErrorBody = response.json().await.unwrap();
+        assert_eq!(body.code, "unaligned_datetime");
+        assert_eq!(
+            body.error,
+            "Row 2: Timeslot must start at a multiple of 15 minutes"
+        );
+        assert!(backend.current_timeslots().unwrap().is_empty());
+
+        server.abort();
+    }
+
+    #[test_case::test_case("business hours" ; "outside business hours")]
+    #[test_case::test_case("max future days" ; "outside horizon")]
+    #[test_case::test_case("granularity" ; "unaligned")]
+    #[tokio::test]
+    async fn test_add_recurring_timeslots_start_checks(check: &str) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let day = (Utc::now() + chrono::Duration::days(2)).date_naive();
+        let datetime = match check {
+            "business hours" => {
+                *mock_configuration.0.business_hours.lock().unwrap() = Some(BusinessHours {
+                    opening: "08:00".parse().unwrap(),
+                    closing: "20:00".parse().unwrap(),
+                    ..BusinessHours::always()
+                });
+                day.and_hms_opt(2, 0, 0).unwrap().and_utc()
+            }
+            "max future days" => {
+                // Only the later occurrences are beyond the horizon
+                *mock_configuration.0.max_future_days.lock().unwrap() = Some(30);
+                day.and_hms_opt(10, 0, 0).unwrap().and_utc()
+            }
+            _ => {
+                *mock_configuration
+                    .0
+                    .slot_granularity_minutes
+                    .lock()
+                    .unwrap() = Some(15);
+                mock_configuration
+                    .0
+                    .reject_unaligned_slots
+                    .store(true, Ordering::SeqCst);
+                day.and_hms_opt(10, 7, 0).unwrap().and_utc()
+            }
+        };
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        for dry_run in [true, false] {
+            let response = Client::new()
+                .post(format!("http://{addr}/add_recurring?dry_run={dry_run}"))
+                .header("x-admin-password", "123")
+                .json(&recurring_request(datetime, 8, None))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        }
+        assert!(backend.current_timeslots().unwrap().is_empty());
+
+        server.abort();
+    }
+
+    #[test_case::test_case(&["haircut", "coloring"], Some("haircut"), StatusCode::OK ; "allowed category")]
+    #[test_case::test_case(&["haircut", "coloring"], Some("massage"), StatusCode::BAD_REQUEST ; "disallowed category")]
+    #[test_case::test_case(&["haircut", "coloring"], None, StatusCode::OK ; "without category")]
+    #[test_case::test_case(&[], Some("massage"), StatusCode::OK ; "any category")]
+    #[tokio::test]
+    async fn test_add_timeslot_category(
+        allowed_categories: &[&str],
+        category: Option<&str>,
+        status_code: StatusCode,
+    ) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.allowed_categories.lock().unwrap() = allowed_categories
+            .iter()
+            .map(|category| category.to_string())
+            .collect();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", "123")
+            .json(&AddTimeslotRequest {
+                datetime: Utc::now() + chrono::Duration::hours(1),
+                notes: "Workshop".into(),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: category.map(String::from),
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::OK {
+            assert_eq!(
+                backend.current_timeslots().unwrap()[0].category.as_deref(),
+                category
+            );
+        } else {
+            let body: ApiErrorBody = response.json().await.unwrap();
+            assert_eq!(
+                body.fields.unwrap()["category"][0].code,
+                "category_not_allowed"
+            );
+            assert!(backend.current_timeslots().unwrap().is_empty());
+        }
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_category() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        let start = Utc::now() + chrono::Duration::hours(1);
+        for (hours, category) in [(0, Some("haircut")), (1, Some("coloring")), (2, None)] {
+            backend
+                .add_timeslot(
+                    start + chrono::Duration::hours(hours),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    category.map(String::from),
+                )
+                .unwrap();
+        }
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot?category=haircut"))
+            .send()
+            .await
+            .unwrap();
+        let timeslots = response.json::<Snapshot>().await.unwrap().timeslots;
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].datetime, start);
+
+        let response = client
+            .get(format!("http://{addr}/timeslots?category=coloring"))
+            .send()
+            .await
+            .unwrap();
+        let timeslots = read_from_sse(&mut response.bytes_stream()).await;
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].category.as_deref(), Some("coloring"));
+
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap();
+        let timeslots = response.json::<Snapshot>().await.unwrap().timeslots;
+        assert_eq!(timeslots.len(), 3);
+
+        server.abort();
+    }
+
+    #[test_case::test_case(false, Some("No slots yet"), Some("No slots yet") ; "empty with message")]
+    #[test_case::test_case(true, Some("No slots yet"), None ; "non-empty with message")]
+    #[test_case::test_case(false, None, None ; "empty without message")]
+    #[test_case::test_case(true, None, None ; "non-empty without message")]
+    #[tokio::test]
+    async fn test_empty_schedule_message(
+        add_timeslot: bool,
+        configured_message: Option<&str>,
+        expected_message: Option<&str>,
+    ) {
+        let configuration = MockConfiguration::new();
+        *configuration.0.empty_schedule_message.lock().unwrap() =
+            configured_message.map(String::from);
+        let (server, addr, backend) = init_with_local_backend(configuration).await;
+        if add_timeslot {
+            backend
+                .add_timeslot(
+                    Utc::now() + chrono::Duration::hours(1),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), StatusCode::OK.as_u16());
+        let snapshot = response.json::<Snapshot>().await.unwrap();
+        assert_eq!(snapshot.timeslots.len(), usize::from(add_timeslot));
+        assert_eq!(snapshot.message.as_deref(), expected_message);
+
+        server.abort();
+    }
+
+    #[test_case::test_case("?offset=5", true, None ; "offset past the end")]
+    #[test_case::test_case("?category=coloring", true, None ; "unmatched category")]
+    #[test_case::test_case("?from=2999-01-01T00:00:00Z", true, None ; "unmatched from")]
+    #[test_case::test_case("?to=2000-01-01T00:00:00Z", true, None ; "unmatched to")]
+    #[test_case::test_case("?to=2000-01-01T00:00:00Z", false, Some("No slots yet") ; "empty schedule with filter")]
+    #[tokio::test]
+    async fn test_empty_schedule_message_with_filter(
+        query: &str,
+        add_timeslot: bool,
+        expected_message: Option<&str>,
+    ) {
+        let configuration = MockConfiguration::new();
+        *configuration.0.empty_schedule_message.lock().unwrap() = Some("No slots yet".into());
+        let (server, addr, backend) = init_with_local_backend(configuration).await;
+        if add_timeslot {
+            backend
+                .add_timeslot(
+                    Utc::now() + chrono::Duration::hours(1),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    Some("haircut".into()),
+                )
+                .unwrap();
+        }
+
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots_snapshot{query}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), StatusCode::OK.as_u16());
+        let snapshot = response.json::<Snapshot>().await.unwrap();
+        assert!(snapshot.timeslots.is_empty());
+        assert_eq!(snapshot.message.as_deref(), expected_message);
+
+        server.abort();
+    }
+
+    #[test_case::test_case(Some(30), 29, StatusCode::OK ; "inside horizon")]
+    #[test_case::test_case(Some(30), 31, StatusCode::BAD_REQUEST ; "outside horizon")]
+    #[test_case::test_case(None, 3650, StatusCode::OK ; "unlimited")]
+    #[tokio::test]
+    async fn test_add_timeslot_max_future_days(
+        max_future_days: Option<i64>,
+        days_ahead: i64,
+        status_code: StatusCode,
+    ) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.max_future_days.lock().unwrap() = max_future_days;
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", "123")
+            .json(&AddTimeslotRequest {
+                datetime: Utc::now() + chrono::Duration::days(days_ahead),
+                notes: "Workshop".into(),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_eq!(
+            backend.current_timeslots().unwrap().len(),
+            usize::from(status_code == StatusCode::OK)
+        );
+        server.abort();
+    }
+
+    #[test_case::test_case(2, StatusCode::OK ; "valid")]
+    #[test_case::test_case(-1, StatusCode::BAD_REQUEST ; "past")]
+    #[test_case::test_case(24 * 31, StatusCode::BAD_REQUEST ; "outside horizon")]
+    #[tokio::test]
+    async fn test_edit_timeslot_start(offset_hours: i64, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.max_future_days.lock().unwrap() = Some(30);
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        let datetime = Utc::now() + chrono::Duration::hours(1);
+        backend
+            .add_timeslot(datetime, "Workshop".into(), 1, 30, None, None)
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+
+        let new_datetime = Utc::now() + chrono::Duration::hours(offset_hours);
+        let response = Client::new()
+            .put(format!("http://{addr}/edit"))
+            .header("x-admin-password", "123")
+            .json(&EditTimeslotRequest {
+                id,
+                datetime: new_datetime,
+                notes: "Workshop".into(),
+                version: 0,
+                internal_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        let expected = if status_code == StatusCode::OK {
+            new_datetime
+        } else {
+            datetime
+        };
+        assert_eq!(backend.current_timeslots().unwrap()[0].datetime, expected);
+        server.abort();
+    }
+
+    #[test_case::test_case(Some(("08:00", "20:00")), 10, StatusCode::OK ; "within business hours")]
+    #[test_case::test_case(Some(("08:00", "20:00")), 2, StatusCode::BAD_REQUEST ; "outside business hours")]
+    #[test_case::test_case(Some(("08:00", "20:00")), 20, StatusCode::BAD_REQUEST ; "at closing time")]
+    #[test_case::test_case(None, 2, StatusCode::OK ; "without business hours")]
+    #[tokio::test]
+    async fn test_add_timeslot_business_hours(
+        opening_hours: Option<(&str, &str)>,
+        local_hour: u32,
+        status_code: StatusCode,
+    ) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.display_timezone.lock().unwrap() = "Europe/Vienna".into();
+        *mock_configuration.0.business_hours.lock().unwrap() =
+            opening_hours.map(|(opening, closing)| BusinessHours {
+                opening: opening.parse().unwrap(),
+                closing: closing.parse().unwrap(),
+                ..BusinessHours::always()
+            });
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let datetime = (Utc::now() + chrono::Duration::days(2))
+            .date_naive()
+            .and_hms_opt(local_hour, 0, 0)
+            .unwrap()
+            .and_local_timezone(chrono_tz::Europe::Vienna)
+            .unwrap()
+            .with_timezone(&Utc);
+        let response = Client::new()
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", "123")
+            .json(&AddTimeslotRequest {
+                datetime,
+                notes: "Workshop".into(),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_eq!(
+            backend.current_timeslots().unwrap().len(),
+            usize::from(status_code == StatusCode::OK)
+        );
+
+        server.abort();
+    }
+
+    #[test_case::test_case(60, 60, StatusCode::OK ; "default limit")]
+    #[test_case::test_case(60, 61, StatusCode::BAD_REQUEST ; "exceeds default limit")]
+    #[test_case::test_case(200, 200, StatusCode::OK ; "custom limit")]
+    #[test_case::test_case(200, 201, StatusCode::BAD_REQUEST ; "exceeds custom limit")]
+    #[tokio::test]
+    async fn test_notes_max_len(notes_max_len: usize, notes_len: usize, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.notes_max_len.lock().unwrap() = notes_max_len;
+        let (server, addr, mock_backend, mock_configuration) =
+            init_with_configuration(mock_configuration).await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", password)
+            .json(&AddTimeslotRequest {
+                datetime: Utc::now() + chrono::Duration::hours(1),
+                notes: "a".repeat(notes_len),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::BAD_REQUEST {
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["fields"]["notes"][0]["code"], "length");
+        }
+        assert_backend_calls(
+            mock_backend,
+            "add",
+            u64::from(status_code == StatusCode::OK),
+        );
+        server.abort();
+    }
+
+    #[test]
+    fn test_custom_notes_pattern() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.notes_pattern.lock().unwrap() = Some("^[a-z]+$".into());
+        let text_rules = TextRules::new(&mock_configuration).unwrap();
+        let context = ValidationContext {
+            earliest: Utc::now(),
+            latest: None,
+            text_rules,
+            business_hours: None,
+            display_timezone: Tz::UTC,
+            allowed_categories: vec![],
+        };
+        assert!(validate_notes("workshop", &context).is_ok());
+        assert!(validate_notes("Workshop", &context).is_err());
+
+        *mock_configuration.0.notes_pattern.lock().unwrap() = Some("[a-z".into());
+        assert!(TextRules::new(&mock_configuration).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_timeslot_limit_conflict() {
+        let mock_configuration = MockConfiguration::new();
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        let app = create_app(
+            LocalTimeslots::new(chrono::Duration::days(1), true, Some(1), false),
+            mock_configuration,
+            ShutdownSignal::new(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        let client = Client::new();
+        let add_timeslot = || {
+            client
+                .post(format!("http://{addr}/add"))
+                .header("x-admin-password", password.clone())
+                .json(&AddTimeslotRequest {
+                    datetime: Utc::now() + chrono::Duration::hours(1),
+                    notes: "Example Notes".into(),
+                    capacity: 1,
+                    duration_minutes: 30,
+                    internal_notes: None,
+                    category: None,
+                })
+                .send()
+        };
+        assert_eq!(
+            add_timeslot().await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        let response = add_timeslot().await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "error": "Timeslot limit reached", "code": "conflict" })
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_restore_last() {
+        let mock_configuration = MockConfiguration::new();
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        let backend = LocalTimeslots::new(chrono::Duration::days(1), true, None, false);
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Example Notes".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let app = create_app(backend.clone(), mock_configuration, ShutdownSignal::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        let client = Client::new();
+        let post = |path: &'static str| {
+            client
+                .post(format!("http://{addr}/{path}"))
+                .header("x-admin-password", password.clone())
+                .send()
+        };
+        assert_eq!(
+            post("remove_all").await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        assert!(backend.current_timeslots().unwrap().is_empty());
+
+        assert_eq!(
+            post("restore_last").await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        assert_eq!(backend.current_timeslots().unwrap().len(), 1);
+
+        let response = post("restore_last").await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "error": "Nothing to restore", "code": "conflict" })
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_archived_timeslots() {
+        let mock_configuration = MockConfiguration::new();
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        let backend = LocalTimeslots::new(chrono::Duration::days(1), true, None, false);
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Example Notes".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = backend.current_timeslots().unwrap()[0].id;
+        let app = create_app(backend, mock_configuration, ShutdownSignal::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        let client = Client::new();
+        let response = client
+            .delete(format!("http://{addr}/remove"))
+            .header("x-admin-password", password.clone())
+            .json(&DeleteTimeslotRequest { id: timeslot_id })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        let get = |path: &'static str| {
+            client
+                .get(format!("http://{addr}/{path}"))
+                .header("x-admin-password", password.clone())
+                .send()
+        };
+        let timeslots: Vec<Timeslot> = get("admin_timeslots").await.unwrap().json().await.unwrap();
+        assert!(timeslots.is_empty());
+
+        let archived: Vec<Timeslot> = get("archived").await.unwrap().json().await.unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, timeslot_id);
+        assert!(archived[0].archived);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_backend_error_json() {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        mock_backend.0.success.store(false, Ordering::SeqCst);
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/cancel"))
+            .header("x-admin-password", password)
+            .json(&CancelBookingRequest { id: Uuid::new_v4() })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.status(),
+            StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+        );
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "error": "Supposed to fail", "code": "backend_error" })
+        );
+
+        server.abort();
+    }
+
+    #[test_case::test_case(Some("stefan@example.com"), Some("stefan@example.com"))]
+    #[test_case::test_case(Some(""), None)]
+    #[test_case::test_case(None, None)]
+    #[tokio::test]
+    async fn test_book_timeslot_with_client_email(
+        client_email: Option<&str>,
+        expected_client_email: Option<&str>,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: client_email.map(String::from),
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            mock_backend.0.last_client_email.lock().unwrap().as_deref(),
+            expected_client_email
+        );
+
+        server.abort();
+    }
+
+    #[test_case::test_case(Some("+436641234567"), 200, Some("+436641234567") ; "e164")]
+    #[test_case::test_case(Some("0664 1234567"), 400, None ; "local format")]
+    #[test_case::test_case(Some("+0664123"), 400, None ; "invalid country code")]
+    #[test_case::test_case(Some("+4366412345678901"), 400, None ; "too long")]
+    #[test_case::test_case(Some(""), 200, None ; "empty")]
+    #[test_case::test_case(None, 200, None ; "absent")]
+    #[tokio::test]
+    async fn test_book_timeslot_with_client_phone(
+        client_phone: Option<&str>,
+        expected_status: u16,
+        expected_client_phone: Option<&str>,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: client_phone.map(String::from),
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), expected_status);
+        if expected_status == StatusCode::BAD_REQUEST.as_u16() {
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["fields"]["client_phone"][0]["code"], "regex");
+            assert_eq!(
+                mock_backend.0.calls_to_book_timeslot.load(Ordering::SeqCst),
+                0
+            );
+        }
+        assert_eq!(
+            mock_backend.0.last_client_phone.lock().unwrap().as_deref(),
+            expected_client_phone
+        );
+
+        server.abort();
+    }
+
+    #[test_case::test_case(0, 180, StatusCode::OK, Some("First") ; "earliest timeslot")]
+    #[test_case::test_case(90, 240, StatusCode::OK, Some("Second") ; "later window")]
+    #[test_case::test_case(150, 240, StatusCode::NOT_FOUND, None ; "only booked timeslots")]
+    #[test_case::test_case(300, 360, StatusCode::NOT_FOUND, None ; "empty window")]
+    #[test_case::test_case(180, 0, StatusCode::BAD_REQUEST, None ; "reversed window")]
+    #[tokio::test]
+    async fn test_book_any(
+        from_minutes: i64,
+        to_minutes: i64,
+        status_code: StatusCode,
+        expected_notes: Option<&str>,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+        let now = Utc::now();
+        let timeslot = |minutes: i64, notes: &str, available: bool| Timeslot {
+            id: Uuid::new_v4(),
+            datetime: now + chrono::Duration::minutes(minutes),
+            available,
+            booker_name: String::new(),
+            notes: notes.into(),
+            capacity: 1,
+            booked_count: i32::from(!available),
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+        mock_backend.0.timeslot_sender.send_replace(vec![
+            timeslot(60, "First", true),
+            timeslot(120, "Second", true),
+            timeslot(180, "Booked", false),
+        ]);
+
+        let response = Client::new()
+            .post(format!("http://{addr}/book_any"))
+            .json(&serde_json::json!({
+                "from": now + chrono::Duration::minutes(from_minutes),
+                "to": now + chrono::Duration::minutes(to_minutes),
+                "client_name": "Stefan",
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        match expected_notes {
+            Some(expected_notes) => {
+                assert_eq!(body["timeslot"]["notes"], expected_notes);
+                assert_eq!(body["confirmation_code"], MOCK_CONFIRMATION_CODE);
+                // The booked timeslot is sent like to any client
+                assert!(body["timeslot"]["confirmation_code"].is_null());
+            }
+            None if status_code == StatusCode::BAD_REQUEST => {
+                assert_eq!(body["code"], "invalid_window");
+                assert_backend_calls(mock_backend, "book_any", 0);
+            }
+            None => assert_eq!(body["code"], "not_found"),
+        }
+        server.abort();
+    }
+
+    #[test_case::test_case(Some("Please bring a charger"), 200, Some("Please bring a charger") ; "valid")]
+    #[test_case::test_case(Some("<script>"), 400, None ; "invalid characters")]
+    #[test_case::test_case(Some(""), 200, None ; "empty")]
+    #[test_case::test_case(None, 200, None ; "absent")]
+    #[tokio::test]
+    async fn test_book_timeslot_with_booker_notes(
+        booker_notes: Option<&str>,
+        expected_status: u16,
+        expected_booker_notes: Option<&str>,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: booker_notes.map(String::from),
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), expected_status);
+        if expected_status == StatusCode::BAD_REQUEST.as_u16() {
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["fields"]["booker_notes"][0]["code"], "regex");
+            assert_eq!(
+                mock_backend.0.calls_to_book_timeslot.load(Ordering::SeqCst),
+                0
+            );
+        }
+        assert_eq!(
+            mock_backend.0.last_booker_notes.lock().unwrap().as_deref(),
+            expected_booker_notes
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_booker_notes_only_visible_to_admins() {
+        let mock_configuration = MockConfiguration::new();
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id,
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: Some("Please bring a charger".into()),
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        let bookings: Vec<Timeslot> = client
+            .get(format!("http://{addr}/bookings"))
+            .header("x-admin-password", password)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            bookings[0].bookings[0].booker_notes.as_deref(),
+            Some("Please bring a charger")
+        );
+
+        let timeslots = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap()
+            .json::<Snapshot>()
+            .await
+            .unwrap()
+            .timeslots;
+        assert_eq!(timeslots[0].bookings, vec![]);
+
+        server.abort();
+    }
+
+    async fn init_webhook_receiver() -> (
+        JoinHandle<Result<(), std::io::Error>>,
+        String,
+        tokio::sync::mpsc::UnboundedReceiver<Timeslot>,
+    ) {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/webhook",
+            post(move |Json(timeslot): Json<Timeslot>| async move {
+                sender.send(timeslot).unwrap();
+                StatusCode::OK
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let join = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        (join, format!("http://{addr}/webhook"), receiver)
+    }
+
+    #[tokio::test]
+    async fn test_booking_webhook() {
+        let (webhook_server, webhook_url, mut webhook_receiver) = init_webhook_receiver().await;
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.webhook_url.lock().unwrap() = Some(webhook_url);
+        let (server, addr, mock_backend, _) = init_with_configuration(mock_configuration).await;
+
+        let timeslot = Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: false,
+            booker_name: "Stefan".into(),
+            notes: "First Timeslot".into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(vec![timeslot.clone()]);
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: timeslot.id,
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        let payload = timeout(Duration::from_secs(1), webhook_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut booked_timeslot = timeslot.clone();
+        booked_timeslot.bookings.push(mock_booking("Stefan"));
+        assert_eq!(payload, booked_timeslot);
+
+        // A failed booking doesn't trigger the webhook
+        mock_backend.0.success.store(false, Ordering::SeqCst);
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: timeslot.id,
+                client_name: "Peter".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+        );
+        timeout(Duration::from_millis(100), webhook_receiver.recv())
+            .await
+            .unwrap_err();
+
+        server.abort();
+        webhook_server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_booking_with_unreachable_webhook() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.webhook_url.lock().unwrap() =
+            Some("http://127.0.0.1:1/webhook".into());
+        let (server, addr, mock_backend, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_backend_calls(mock_backend, "book", 1);
+        server.abort();
+    }
+
+    #[test_case::test_case("stef", StatusCode::OK, &["Stefan Dangl", "Anna, stefanie"] ; "matching substring")]
+    #[test_case::test_case("Peter", StatusCode::OK, &[] ; "no match")]
+    #[test_case::test_case("anna, stef", StatusCode::OK, &[] ; "across bookers")]
+    #[test_case::test_case("%", StatusCode::OK, &[] ; "wildcard")]
+    #[test_case::test_case(" ", StatusCode::BAD_REQUEST, &[] ; "empty name")]
+    #[tokio::test]
+    async fn test_search_bookings(name: &str, status_code: StatusCode, expected_names: &[&str]) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        let start = Utc::now() + chrono::Duration::hours(1);
+        for (hour, notes) in [(2, "Second Timeslot"), (1, "First Timeslot"), (3, "Free")] {
+            backend
+                .add_timeslot(
+                    start + chrono::Duration::hours(hour),
+                    notes.into(),
+                    2,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let ids: Vec<Uuid> = backend
+            .current_timeslots()
+            .unwrap()
+            .iter()
+            .map(|timeslot| timeslot.id)
+            .collect();
+        for (id, client_name) in [
+            (ids[0], "Stefan Dangl"),
+            (ids[1], "Anna"),
+            (ids[1], "stefanie"),
+        ] {
+            backend
+                .book_timeslot(id, client_name.into(), None, None, None)
+                .unwrap();
+        }
+
+        let response = Client::new()
+            .get(format!("http://{addr}/search"))
+            .query(&[("name", name)])
+            .header("x-admin-password", "123")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::OK {
+            let timeslots: Vec<Timeslot> = response.json().await.unwrap();
+            assert_eq!(
+                timeslots
+                    .iter()
+                    .map(|timeslot| timeslot.booker_name.as_str())
+                    .collect::<Vec<_>>(),
+                expected_names
+            );
+        } else {
+            let body: ApiErrorBody = response.json().await.unwrap();
+            assert_eq!(body.code, "empty_search");
+        }
+        server.abort();
+    }
+
+    #[test_case::test_case(None, &["First Booking", "Partially Booked", "Last Booking"]; "all bookings")]
+    #[test_case::test_case(Some("2030-01-02"), &["Partially Booked", "Last Booking"]; "bookings of a day")]
+    #[test_case::test_case(Some("2030-01-03"), &[]; "no bookings")]
+    #[tokio::test]
+    async fn test_get_bookings(date: Option<&str>, expected_notes: &[&str]) {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let timeslot = |hour: u32, day: u32, notes: &str, booked_count: i32| Timeslot {
+            id: Uuid::new_v4(),
+            datetime: "2030-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+                + chrono::Duration::days(day.into())
+                + chrono::Duration::hours(hour.into()),
+            available: booked_count < 2,
+            booker_name: if booked_count > 0 {
+                "Stefan".into()
+            } else {
+                String::new()
+            },
+            notes: notes.into(),
+            capacity: 2,
+            booked_count,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+        // Not sorted by datetime
+        mock_backend.0.timeslot_sender.send_replace(vec![
+            timeslot(12, 1, "Last Booking", 2),
+            timeslot(10, 1, "Available", 0),
+            timeslot(9, 0, "First Booking", 2),
+            timeslot(8, 1, "Partially Booked", 1),
+        ]);
+
+        let client = Client::new();
+        let mut request = client
+            .get(format!("http://{addr}/bookings"))
+            .header("x-admin-password", password);
+        if let Some(date) = date {
+            request = request.query(&[("date", date)]);
+        }
+        let response = request.send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let bookings: Vec<Timeslot> = response.json().await.unwrap();
+        assert_eq!(
+            bookings
+                .iter()
+                .map(|timeslot| timeslot.notes.as_str())
+                .collect::<Vec<_>>(),
+            expected_notes
+        );
+        assert!(bookings
+            .iter()
+            .all(|timeslot| timeslot.booker_name == "Stefan"));
+
+        server.abort();
+    }
+
+    #[test_case::test_case(None, StatsResponse { total_slots: 4, booked_slots: 1, available_slots: 3, occupancy_percent: 25.0 }; "all timeslots")]
+    #[test_case::test_case(Some("2030-01-02"), StatsResponse { total_slots: 2, booked_slots: 1, available_slots: 1, occupancy_percent: 50.0 }; "timeslots of a day")]
+    #[test_case::test_case(Some("2030-01-03"), StatsResponse { total_slots: 0, booked_slots: 0, available_slots: 0, occupancy_percent: 0.0 }; "no timeslots")]
+    #[tokio::test]
+    async fn test_get_stats(date: Option<&str>, expected_stats: StatsResponse) {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let timeslot = |day: u32, booked_count: i32| Timeslot {
+            id: Uuid::new_v4(),
+            datetime: "2030-01-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap()
+                + chrono::Duration::days(day.into()),
+            available: booked_count < 2,
+            booker_name: String::new(),
+            notes: String::new(),
+            capacity: 2,
+            booked_count,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+        mock_backend.0.timeslot_sender.send_replace(vec![
+            timeslot(0, 0),
+            timeslot(0, 1),
+            timeslot(1, 2),
+            timeslot(1, 1),
+        ]);
+
+        let client = Client::new();
+        let mut request = client
+            .get(format!("http://{addr}/stats"))
+            .header("x-admin-password", password);
+        if let Some(date) = date {
+            request = request.query(&[("date", date)]);
+        }
+        let response = request.send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let stats: StatsResponse = response.json().await.unwrap();
+        assert_eq!(stats, expected_stats);
+
+        server.abort();
+    }
+
+    async fn init_with_local_backend(
+        mock_configuration: MockConfiguration,
+    ) -> (
+        JoinHandle<Result<(), std::io::Error>>,
+        SocketAddr,
+        LocalTimeslots,
+    ) {
+        let backend = LocalTimeslots::new(chrono::Duration::days(1), false, None, false);
+        let app = create_app(backend.clone(), mock_configuration, ShutdownSignal::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+        (server, addr, backend)
+    }
+
+    #[tokio::test]
+    async fn test_swap_bookings() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        for hours in [1, 2] {
+            backend
+                .add_timeslot(
+                    Utc::now() + chrono::Duration::hours(hours),
+                    "Workshop".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let timeslots = backend.current_timeslots().unwrap();
+        let (first_id, second_id) = (timeslots[0].id, timeslots[1].id);
+        backend
+            .book_timeslot(first_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        backend
+            .book_timeslot(second_id, "Peter".into(), None, None, None)
+            .unwrap();
+
+        let client = Client::new();
+        let swap = |first_id, second_id| {
+            client
+                .post(format!("http://{addr}/swap"))
+                .header("x-admin-password", "123")
+                .json(&SwapBookingsRequest {
+                    first_id,
+                    second_id,
+                })
+                .send()
+        };
+        assert_eq!(
+            swap(first_id, second_id).await.unwrap().status(),
+            StatusCode::OK.as_u16()
+        );
+        let timeslots = backend.current_timeslots().unwrap();
+        assert_eq!(timeslots[0].booker_name, "Peter");
+        assert_eq!(timeslots[1].booker_name, "Stefan");
+
+        assert_eq!(
+            swap(first_id, Uuid::new_v4()).await.unwrap().status(),
+            StatusCode::NOT_FOUND.as_u16()
+        );
+        assert_eq!(
+            swap(first_id, first_id).await.unwrap().status(),
+            StatusCode::CONFLICT.as_u16()
+        );
+        assert_eq!(backend.current_timeslots().unwrap(), timeslots);
+
+        // Two bookings don't fit into a timeslot with a single place
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(3),
+                "Group".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let group_id = backend.current_timeslots().unwrap()[2].id;
+        for client_name in ["Anna", "Maria"] {
+            backend
+                .book_timeslot(group_id, client_name.into(), None, None, None)
+                .unwrap();
+        }
+        let timeslots = backend.current_timeslots().unwrap();
+        let response = swap(first_id, group_id).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        let body: ApiErrorBody = response.json().await.unwrap();
+        assert_eq!(body.code, "conflict");
+        assert_eq!(backend.current_timeslots().unwrap(), timeslots);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_update_notes() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        let datetime = Utc::now() + chrono::Duration::hours(1);
+        backend
+            .add_timeslot(datetime, "Workshop".into(), 1, 30, None, None)
+            .unwrap();
+        let timeslot = backend.current_timeslots().unwrap().remove(0);
+
+        let response = Client::new()
+            .patch(format!("http://{addr}/notes"))
+            .header("x-admin-password", "123")
+            .json(&UpdateNotesRequest {
+                id: timeslot.id,
+                notes: "Advanced Workshop".into(),
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let updated = backend.current_timeslots().unwrap().remove(0);
+        assert_eq!(updated.notes, "Advanced Workshop");
+        assert_eq!(updated.datetime, datetime);
+        assert_eq!(updated.version, timeslot.version + 1);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_import_timeslots() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/import.csv"))
+            .header("x-admin-password", "123")
+            .header("content-type", "text/csv")
+            .body(
+                "datetime,notes,capacity\n\
+                2030-01-31T09:00:00Z,Workshop\n\
+                2030-01-31T10:00:00Z,\"Room 1 (Lab)\",3\n",
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: AddTimeslotsBulkResponse = response.json().await.unwrap();
+        assert_eq!(data.count, 2);
+        let timeslots = backend.current_timeslots().unwrap();
+        assert_eq!(timeslots.len(), 2);
+        assert_eq!(timeslots[1].notes, "Room 1 (Lab)");
+        assert_eq!(timeslots[1].capacity, 3);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_import_timeslots_invalid_row() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let client = Client::new();
+        for (csv, code, error) in [
+            (
+                "datetime,notes\n\
+                2030-01-31T09:00:00Z,Workshop\n\
+                2030-01-31T10:00:00Z,Workshop\n\
+                31.01.2030 11:00,Workshop\n",
+                "invalid_csv",
+                "Row 3: invalid datetime \"31.01.2030 11:00\"",
+            ),
+            (
+                "datetime,notes\n\
+                2030-01-31T09:00:00Z,Workshop\n\
+                2030-01-31T10:00:00Z,Workshop\n\
+                2030-01-31T11:00:00Z,'\n",
+                "invalid_input",
+                "Row 3: Invalid input",
+            ),
+            ("datetime,notes\n", "invalid_csv", "No timeslots to import"),
+        ] {
+            let response = client
+                .post(format!("http://{addr}/import.csv"))
+                .header("x-admin-password", "123")
+                .header("content-type", "text/csv")
+                .body(csv)
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["code"], code);
+            assert_eq!(body["error"], error);
+        }
+        // None of the valid rows were added
+        assert!(backend.current_timeslots().unwrap().is_empty());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.max_concurrent_requests.lock().unwrap() = 2;
+        let (server, addr, mock_backend, _) = init_with_configuration(mock_configuration).await;
+        *mock_backend.0.delay.lock().unwrap() = Duration::from_millis(500);
+
+        let client = Client::new();
+        let slow_requests = futures::future::join(
+            client.get(format!("http://{addr}/ready")).send(),
+            client.get(format!("http://{addr}/ready")).send(),
+        );
+        let overflow_request = async {
+            // Lets the slow requests take up the limit first
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            client
+                .get(format!("http://{addr}/health"))
+                .send()
+                .await
+                .unwrap()
+        };
+        let ((first, second), overflow) = tokio::join!(slow_requests, overflow_request);
+
+        assert_eq!(first.unwrap().status(), StatusCode::OK.as_u16());
+        assert_eq!(second.unwrap().status(), StatusCode::OK.as_u16());
+        assert_eq!(overflow.status(), StatusCode::SERVICE_UNAVAILABLE.as_u16());
+        let body: serde_json::Value = overflow.json().await.unwrap();
+        assert_eq!(body["code"], "overloaded");
+
+        // Once the slow requests are done, requests are accepted again. Open streams don't count
+        let mut streams = vec![];
+        for _ in 0..3 {
+            let response = client
+                .get(format!("http://{addr}/timeslots"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK.as_u16());
+            streams.push(response);
+        }
+        let response = client
+            .get(format!("http://{addr}/health"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_slow_backend_does_not_block_runtime() {
+        // The test runtime has a single thread, which a blocking backend call would stall
+        let (server, addr, mock_backend, _) = init().await;
+        *mock_backend.0.delay.lock().unwrap() = Duration::from_millis(500);
+
+        let client = Client::new();
+        let slow_request = client.get(format!("http://{addr}/ready")).send();
+        let fast_request = async {
+            // Lets the slow request reach the backend first
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let start = std::time::Instant::now();
+            let response = client
+                .get(format!("http://{addr}/health"))
+                .send()
+                .await
+                .unwrap();
+            (response.status(), start.elapsed())
+        };
+        let (slow_response, (status, elapsed)) = tokio::join!(slow_request, fast_request);
+
+        assert_eq!(slow_response.unwrap().status(), StatusCode::OK.as_u16());
+        assert_eq!(status, StatusCode::OK.as_u16());
+        assert!(
+            elapsed < Duration::from_millis(250),
+            "Health check took {elapsed:?}"
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_export() {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let timeslot = |notes: &str| Timeslot {
+            id: Uuid::new_v4(),
+            datetime: "2030-01-31T09:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+            available: false,
+            booker_name: "Stefan".into(),
+            notes: notes.into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+        let timeslots = vec![timeslot("Workshop"), timeslot("Room 1, Lab\nSecond floor")];
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(timeslots.clone());
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/export.csv"))
+            .header("x-admin-password", password)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/csv; charset=utf-8"
+        );
+        assert_eq!(
+            response.headers()["content-disposition"],
+            "attachment; filename=\"bookings.csv\""
+        );
+        let body = response.text().await.unwrap();
+        let mut reader = csv::Reader::from_reader(body.as_bytes());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["id", "datetime", "available", "booker_name", "notes"]
+        );
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][0], timeslots[1].id.to_string());
+        assert_eq!(rows[1][4], *"Room 1, Lab\nSecond floor");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_booking() {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let timeslot = Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: false,
+            booker_name: "Stefan".into(),
+            notes: "First Timeslot".into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![mock_booking("Stefan")],
+        };
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(vec![timeslot.clone()]);
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/booking/{MOCK_CONFIRMATION_CODE}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: Timeslot = response.json().await.unwrap();
+        assert_eq!(data, timeslot);
+
+        let response = client
+            .get(format!("http://{addr}/booking/UNKNOWN1"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND.as_u16());
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_find_by_confirmation
+                .load(Ordering::SeqCst),
+            2
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_booking_hides_internal_notes() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                Some("Pays in cash".into()),
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        let code = backend
+            .book_timeslot(id, "Stefan".into(), None, None, None)
+            .unwrap()
+            .bookings
+            .remove(0)
+            .confirmation_code;
+
+        let response = Client::new()
+            .get(format!("http://{addr}/booking/{code}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["internal_notes"], serde_json::Value::Null);
+        assert!(!body.to_string().contains("Pays in cash"));
+        assert_eq!(body["bookings"][0]["confirmation_code"], *code);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_booking_hides_other_bookers() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Group Workshop".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        backend
+            .book_timeslot(
+                id,
+                "Stefan".into(),
+                Some("stefan@example.com".into()),
+                Some("+436641234567".into()),
+                Some("Vegetarian".into()),
+            )
+            .unwrap();
+        let code = backend
+            .book_timeslot(id, "Peter".into(), None, None, None)
+            .unwrap()
+            .bookings
+            .remove(1)
+            .confirmation_code;
+
+        let response = Client::new()
+            .get(format!("http://{addr}/booking/{code}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let body = response.text().await.unwrap();
+        for detail in [
+            "Stefan",
+            "stefan@example.com",
+            "+436641234567",
+            "Vegetarian",
+        ] {
+            assert!(!body.contains(detail), "{detail} exposed in {body}");
+        }
+        let data: Timeslot = serde_json::from_str(&body).unwrap();
+        assert_eq!(data.booker_name, "Peter");
+        assert_eq!(data.bookings.len(), 1);
+        assert_eq!(data.bookings[0].confirmation_code, code);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_own_booking_keeps_other_bookers() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Group Workshop".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        let code = backend
+            .book_timeslot(
+                id,
+                "Stefan".into(),
+                Some("stefan@example.com".into()),
+                Some("+436641234567".into()),
+                None,
+            )
+            .unwrap()
+            .bookings
+            .remove(0)
+            .confirmation_code;
+        let peter = backend
+            .book_timeslot(
+                id,
+                "Peter".into(),
+                Some("peter@example.com".into()),
+                Some("+436649876543".into()),
+                None,
+            )
+            .unwrap()
+            .bookings
+            .remove(1);
+
+        let response = Client::new()
+            .post(format!("http://{addr}/cancel_own"))
+            .json(&CancelOwnBookingRequest { code })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+
+        let timeslot = backend.current_timeslots().unwrap().remove(0);
+        assert_eq!(timeslot.booked_count, 1);
+        assert_eq!(timeslot.booker_name, "Peter");
+        assert!(timeslot.available);
+        assert_eq!(timeslot.bookings, vec![peter]);
+
+        server.abort();
+    }
+
+    #[test_case::test_case(MOCK_CONFIRMATION_CODE, 1, StatusCode::OK, 1)]
+    #[test_case::test_case(MOCK_CONFIRMATION_CODE, 0, StatusCode::CONFLICT, 0)]
+    #[test_case::test_case("UNKNOWN1", 1, StatusCode::NOT_FOUND, 0)]
+    #[tokio::test]
+    async fn test_cancel_own_booking(
+        code: &str,
+        booked_count: i32,
+        status_code: StatusCode,
+        expected_cancellations: u64,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        mock_backend.0.timeslot_sender.send_replace(vec![Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: booked_count == 0,
+            booker_name: String::new(),
+            notes: "First Timeslot".into(),
+            capacity: 1,
+            booked_count,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![mock_booking("Stefan")],
+        }]);
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/cancel_own"))
+            .json(&CancelOwnBookingRequest { code: code.into() })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_backend_calls(mock_backend, "cancel_own", expected_cancellations);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_timeslots_snapshot() {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let timeslots = vec![Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: true,
+            booker_name: String::new(),
+            notes: "First Timeslot".into(),
+            capacity: 1,
+            booked_count: 0,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        }];
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(timeslots.clone());
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data = response.json::<Snapshot>().await.unwrap().timeslots;
+        assert_eq!(data, timeslots);
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_current_timeslots
+                .load(Ordering::SeqCst),
+            1
+        );
+
+        mock_backend.0.success.store(false, Ordering::SeqCst);
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+        );
+
+        server.abort();
+    }
+
+    // 22:30 and 23:30 UTC are 23:30 and 00:30 of the next day in Vienna (UTC+1 in winter)
+    #[test_case::test_case("UTC", &[("2030-01-10", &[0, 1, 2])] ; "utc")]
+    #[test_case::test_case("Europe/Vienna", &[("2030-01-10", &[0, 1]), ("2030-01-11", &[2])] ; "vienna")]
+    #[tokio::test]
+    async fn test_get_timeslots_by_day(timezone: &str, expected_days: &[(&str, &[usize])]) {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        *mock_configuration.0.display_timezone.lock().unwrap() = timezone.into();
+
+        let timeslots: Vec<Timeslot> = [
+            "2030-01-10T09:00:00Z",
+            "2030-01-10T22:30:00Z",
+            "2030-01-10T23:30:00Z",
+        ]
+        .into_iter()
+        .map(|datetime| Timeslot {
+            id: Uuid::new_v4(),
+            datetime: datetime.parse().unwrap(),
+            available: true,
+            booker_name: String::new(),
+            notes: String::new(),
+            capacity: 1,
+            booked_count: 0,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        })
+        .collect();
+        // The days are sorted regardless of the order of the backend
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(timeslots.iter().rev().cloned().collect());
+
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots_by_day"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let days: BTreeMap<String, Vec<Timeslot>> = response.json().await.unwrap();
+        let expected_days: BTreeMap<String, Vec<Timeslot>> = expected_days
+            .iter()
+            .map(|(day, indices)| {
+                (
+                    day.to_string(),
+                    indices
+                        .iter()
+                        .map(|index| timeslots[*index].clone())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(days, expected_days);
+
+        server.abort();
+    }
+
+    #[test_case::test_case(None, "Stefan", "Timeslot booked successfully" ; "default")]
+    #[test_case::test_case(Some("en"), "Stefan", "Timeslot booked successfully" ; "english")]
+    #[test_case::test_case(Some("de-AT,de;q=0.9"), "Stefan", "Termin erfolgreich gebucht" ; "german")]
+    #[test_case::test_case(Some("en"), "", "Invalid input" ; "english error")]
+    #[test_case::test_case(Some("de"), "", "Ungültige Eingabe" ; "german error")]
+    #[tokio::test]
+    async fn test_book_language(accept_language: Option<&str>, client_name: &str, expected: &str) {
+        let (server, addr, _, _) = init().await;
+
+        let mut request_builder = Client::new().post(format!("http://{addr}/book"));
+        if let Some(accept_language) = accept_language {
+            request_builder = request_builder.header("Accept-Language", accept_language);
+        }
+        let response = request_builder
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: client_name.into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        let message = if response.status() == StatusCode::OK.as_u16() {
+            response.json::<BookingResponse>().await.unwrap().message
+        } else {
+            response.json::<ApiErrorBody>().await.unwrap().error
+        };
+        assert_eq!(message, expected);
+
+        server.abort();
+    }
+
+    #[test_case::test_case(Some(2), "Peter", StatusCode::OK ; "other client")]
+    #[test_case::test_case(Some(2), " stefan ", StatusCode::CONFLICT ; "limit reached")]
+    #[test_case::test_case(Some(3), "Stefan", StatusCode::OK ; "below limit")]
+    #[test_case::test_case(None, "Stefan", StatusCode::OK ; "unlimited")]
+    #[tokio::test]
+    async fn test_max_bookings_per_client(
+        max_bookings: Option<u32>,
+        client_name: &str,
+        status_code: StatusCode,
+    ) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.max_bookings_per_client.lock().unwrap() = max_bookings;
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        let start = Utc::now() + chrono::Duration::hours(1);
+        for hours in 0..3 {
+            backend
+                .add_timeslot(
+                    start + chrono::Duration::hours(hours),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let timeslots = backend.current_timeslots().unwrap();
+        for timeslot in &timeslots[..2] {
+            backend
+                .book_timeslot(timeslot.id, "Stefan".into(), None, None, None)
+                .unwrap();
+        }
+
+        let response = Client::new()
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: timeslots[2].id,
+                client_name: client_name.into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_eq!(
+            backend.current_timeslots().unwrap()[2].booked_count,
+            i32::from(status_code == StatusCode::OK)
+        );
+        server.abort();
+    }
+
+    #[test_case::test_case(120, 119, StatusCode::CONFLICT ; "inside lead window")]
+    #[test_case::test_case(120, 121, StatusCode::OK ; "outside lead window")]
+    #[test_case::test_case(0, 1, StatusCode::OK ; "no lead time")]
+    #[tokio::test]
+    async fn test_min_booking_lead(lead_minutes: i64, minutes_ahead: i64, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration
+            .0
+            .min_booking_lead_minutes
+            .lock()
+            .unwrap() = lead_minutes;
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::minutes(minutes_ahead),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id,
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        if status_code == StatusCode::CONFLICT {
+            let body: ApiErrorBody = response.json().await.unwrap();
+            assert_eq!(body.code, "booking_closed");
+        }
+        assert_eq!(
+            backend.current_timeslots().unwrap()[0].booked_count,
+            i32::from(status_code == StatusCode::OK)
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_min_booking_lead_looks_up_single_timeslot() {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        *mock_configuration
+            .0
+            .min_booking_lead_minutes
+            .lock()
+            .unwrap() = 120;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            mock_backend.0.calls_to_find_timeslot.load(Ordering::SeqCst),
+            1
+        );
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_current_timeslots
+                .load(Ordering::SeqCst),
+            0
+        );
+        server.abort();
+    }
+
+    #[test_case::test_case ("", 2 ; "all timeslots")]
+    #[test_case::test_case ("?available_only=true", 1 ; "available only")]
+    #[tokio::test]
+    async fn test_get_count(query: &str, expected: i64) {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        let datetime = Utc::now() + chrono::Duration::hours(1);
+        backend
+            .add_timeslot(datetime, "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        backend
+            .add_timeslot(
+                datetime + chrono::Duration::hours(1),
+                "Second Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        backend
+            .book_timeslot(id, "Stefan".into(), None, None, None)
+            .unwrap();
+
+        let response = Client::new()
+            .get(format!("http://{addr}/count{query}"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: CountResponse = response.json().await.unwrap();
+        assert_eq!(data.count, expected);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_seed_examples() {
+        let backend = LocalTimeslots::new(chrono::Duration::days(1), false, None, false);
+        backend.seed_examples(12).unwrap();
+        let app = create_app(backend, MockConfiguration::new(), ShutdownSignal::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        let response = Client::new()
+            .get(format!("http://{addr}/count?available_only=true"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data: CountResponse = response.json().await.unwrap();
+        assert_eq!(data.count, 12);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_count_backend_failure() {
+        let (server, addr, mock_backend, _) = init().await;
+        mock_backend.0.success.store(false, Ordering::SeqCst);
+
+        let response = Client::new()
+            .get(format!("http://{addr}/count"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.status(),
+            StatusCode::INTERNAL_SERVER_ERROR.as_u16()
+        );
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_count_timeslots
+                .load(Ordering::SeqCst),
+            1
+        );
+        server.abort();
+    }
+
+    #[test_case::test_case ("", vec![0, 1, 2, 3] ; "default")]
+    #[test_case::test_case ("?sort=datetime_asc", vec![0, 1, 2, 3] ; "datetime ascending")]
+    #[test_case::test_case ("?sort=datetime_desc", vec![3, 2, 1, 0] ; "datetime descending")]
+    #[test_case::test_case ("?sort=booker", vec![1, 2, 3, 0] ; "booker")]
+    #[test_case::test_case ("?sort=datetime_desc&limit=2", vec![3, 2] ; "sorted before pagination")]
+    #[tokio::test]
+    async fn test_get_timeslots_snapshot_sorted(query: &str, expected_indices: Vec<usize>) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let start = Utc::now();
+        let timeslots: Vec<Timeslot> = ["Peter", "", "anna", "Bob"]
+            .into_iter()
+            .zip(0..)
+            .map(|(booker_name, hours)| Timeslot {
+                id: Uuid::new_v4(),
+                datetime: start + chrono::Duration::hours(hours),
+                available: booker_name.is_empty(),
+                booker_name: booker_name.into(),
+                notes: format!("Timeslot {hours}"),
+                capacity: 1,
+                booked_count: i32::from(!booker_name.is_empty()),
+                duration_minutes: 30,
+                archived: false,
+                waitlist: vec![],
+                version: 0,
+                internal_notes: None,
+                category: None,
+                bookings: vec![],
+            })
+            .collect();
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(timeslots.clone());
+
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots_snapshot{query}"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data = response.json::<Snapshot>().await.unwrap().timeslots;
+        let expected: Vec<Timeslot> = expected_indices
+            .into_iter()
+            .map(|index| timeslots[index].clone())
+            .collect();
+        assert_eq!(data, expected);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_timeslots_snapshot_invalid_sort() {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots_snapshot?sort=notes"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_current_timeslots
+                .load(Ordering::SeqCst),
+            0
+        );
+
+        server.abort();
+    }
+
+    #[test_case::test_case (Some(1), Some(2), None, None, vec![1, 2] ; "inclusive range")]
+    #[test_case::test_case (Some(4), Some(6), None, None, vec![] ; "out of range")]
+    #[test_case::test_case (None, Some(0), None, None, vec![0] ; "only upper bound")]
+    #[test_case::test_case (None, None, Some(2), Some(1), vec![1, 2] ; "limit and offset")]
+    #[test_case::test_case (Some(1), None, Some(5), Some(1), vec![2, 3] ; "range and pagination")]
+    #[tokio::test]
+    async fn test_get_timeslots_snapshot_filtered(
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        expected_indices: Vec<usize>,
+    ) {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let start = Utc::now();
+        let timeslots: Vec<Timeslot> = (0..4)
+            .map(|hours| Timeslot {
+                id: Uuid::new_v4(),
+                datetime: start + chrono::Duration::hours(hours),
+                available: true,
+                booker_name: String::new(),
+                notes: format!("Timeslot {hours}"),
+                capacity: 1,
+                booked_count: 0,
+                duration_minutes: 30,
+                archived: false,
+                waitlist: vec![],
+                version: 0,
+                internal_notes: None,
+                category: None,
+                bookings: vec![],
+            })
+            .collect();
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(timeslots.clone());
+
+        let query = SnapshotQuery {
+            from: from.map(|hours| start + chrono::Duration::hours(hours)),
+            to: to.map(|hours| start + chrono::Duration::hours(hours)),
+            limit,
+            offset,
+            sort: SortOrder::default(),
+            category: None,
+        };
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .query(&query)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let data = response.json::<Snapshot>().await.unwrap().timeslots;
+        let expected_timeslots: Vec<Timeslot> = expected_indices
+            .into_iter()
+            .map(|index| timeslots[index].clone())
+            .collect();
+        assert_eq!(data, expected_timeslots);
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_timeslots_between
+                .load(Ordering::SeqCst),
+            u64::from(from.is_some() || to.is_some())
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_timeslots_snapshot_invalid_date() {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot?from=yesterday"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        assert_eq!(
+            mock_backend
+                .0
+                .calls_to_current_timeslots
+                .load(Ordering::SeqCst),
+            0
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_calendar() {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let timeslots: Vec<Timeslot> = (0..3)
+            .map(|hours| Timeslot {
+                id: Uuid::new_v4(),
+                datetime: Utc::now() + chrono::Duration::hours(hours),
+                available: hours != 0,
+                booker_name: if hours == 0 {
+                    "Stefan".into()
+                } else {
+                    String::new()
+                },
+                notes: format!("Timeslot {hours}"),
+                capacity: 1,
+                booked_count: if hours == 0 { 1 } else { 0 },
+                duration_minutes: 30,
+                archived: false,
+                waitlist: vec![],
+                version: 0,
+                internal_notes: None,
+                category: None,
+                bookings: vec![],
+            })
+            .collect();
+        mock_backend.0.timeslot_sender.send_replace(timeslots);
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/calendar.ics"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response
+                .headers()
+                .get("content-type")
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "text/calendar; charset=utf-8"
+        );
+        let calendar = response.text().await.unwrap();
+        assert!(calendar.contains("BEGIN:VCALENDAR"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 3);
+        assert!(calendar.contains("DESCRIPTION:Booked by Stefan"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_confirm_unbooked_timeslot() {
+        let mock_configuration = MockConfiguration::new();
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+
+        let response = Client::new()
+            .post(format!("http://{addr}/confirm"))
+            .header("x-admin-password", password)
+            .json(&ConfirmBookingRequest { id })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT.as_u16());
+        let body: ApiErrorBody = response.json().await.unwrap();
+        assert_eq!(body.code, "conflict");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_code_only_visible_to_admin() {
+        let (server, addr, mock_backend, mock_configuration) = init().await;
+        let password = String::from("123");
+        *mock_configuration.0.password.lock().unwrap() = password.clone();
+
+        let timeslot = Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: false,
+            booker_name: "Stefan".into(),
+            notes: "First Timeslot".into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![mock_booking("Stefan")],
+        };
+        mock_backend
+            .0
+            .timeslot_sender
+            .send_replace(vec![timeslot.clone()]);
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap();
+        let data = response.json::<Snapshot>().await.unwrap().timeslots;
+        assert_eq!(data, vec![timeslot.clone().into_public()]);
+        assert!(data[0].bookings.is_empty());
+
+        let response = client
+            .get(format!("http://{addr}/admin_timeslots"))
+            .header("x-admin-password", password)
+            .send()
+            .await
+            .unwrap();
+        let data: Vec<Timeslot> = response.json().await.unwrap();
+        assert_eq!(data, vec![timeslot]);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_internal_notes_only_visible_to_admin() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        let client = Client::new();
+        let response = client
+            .post(format!("http://{addr}/add"))
+            .header("x-admin-password", "123")
+            .json(&AddTimeslotRequest {
+                datetime: Utc::now() + chrono::Duration::hours(1),
+                notes: "Workshop".into(),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: Some("Bring the projector".into()),
+                category: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let id = backend.current_timeslots().unwrap()[0].id;
+        backend
+            .book_timeslot(id, "Stefan".into(), None, None, None)
+            .unwrap();
+
+        let response = client
+            .get(format!("http://{addr}/timeslots"))
+            .send()
+            .await
+            .unwrap();
+        let timeslots = read_from_sse(&mut response.bytes_stream()).await;
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].internal_notes, None);
+
+        let response = client
+            .get(format!("http://{addr}/timeslots_snapshot"))
+            .send()
+            .await
+            .unwrap();
+        let body = response.text().await.unwrap();
+        assert!(!body.contains("Bring the projector"));
+
+        for admin_route in ["admin_timeslots", "bookings"] {
+            let response = client
+                .get(format!("http://{addr}/{admin_route}"))
+                .header("x-admin-password", "123")
+                .send()
+                .await
+                .unwrap();
+            let timeslots: Vec<Timeslot> = response.json().await.unwrap();
+            assert_eq!(
+                timeslots[0].internal_notes.as_deref(),
+                Some("Bring the projector")
+            );
+        }
+
+        server.abort();
+    }
+
+    /// Reads the next event and returns its id and timeslots
+    async fn read_event_from_sse(
+        stream: &mut (impl Stream<Item = Result<Bytes, Error>> + Unpin),
+    ) -> (u64, Vec<Timeslot>) {
+        let raw_data = timeout(Duration::from_millis(100), stream.next())
+            .await
+            .unwrap();
+        let data = String::from_utf8(raw_data.unwrap().unwrap().to_vec()).unwrap();
+        let field = |name: &str| {
+            data.lines()
+                .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+                .unwrap()
+        };
+        (
+            field("id").parse().unwrap(),
+            serde_json::from_str(field("data")).unwrap(),
+        )
+    }
+
+    async fn read_from_sse(
+        stream: &mut (impl Stream<Item = Result<Bytes, Error>> + Unpin),
+    ) -> Vec<Timeslot> {
+        read_event_from_sse(stream).await.1
+    }
+
+    #[test_case::test_case("add", 100, StatusCode::OK ; "add within limit")]
+    #[test_case::test_case("add", 10_000, StatusCode::PAYLOAD_TOO_LARGE ; "add too large")]
+    #[test_case::test_case("import.csv", 10_000, StatusCode::PAYLOAD_TOO_LARGE ; "import too large")]
+    #[test_case::test_case("book", 10_000, StatusCode::PAYLOAD_TOO_LARGE ; "book too large")]
+    #[tokio::test]
+    async fn test_body_limit(path: &str, body_bytes: usize, status_code: StatusCode) {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        *mock_configuration.0.max_body_bytes.lock().unwrap() = 1000;
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+
+        // Padded with whitespace, which leaves the content valid
+        let body = match path {
+            "import.csv" => "datetime,notes\n2030-01-31T09:00:00Z,Workshop\n".to_string(),
+            "book" => serde_json::to_string(&BookingRequest {
+                id: Uuid::new_v4(),
+                client_name: String::from("Stefan"),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .unwrap(),
+            _ => serde_json::to_string(&AddTimeslotRequest {
+                datetime: Utc::now() + chrono::Duration::hours(1),
+                notes: String::from("Workshop"),
+                capacity: 1,
+                duration_minutes: 30,
+                internal_notes: None,
+                category: None,
+            })
+            .unwrap(),
+        };
+        let body = format!("{body:<body_bytes$}");
+        let content_type = match path {
+            "import.csv" => "text/csv",
+            _ => "application/json",
+        };
+
+        let response = Client::new()
+            .post(format!("http://{addr}/{path}"))
+            .header("x-admin-password", "123")
+            .header("content-type", content_type)
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status_code.as_u16());
+        assert_eq!(
+            backend.current_timeslots().unwrap().len(),
+            usize::from(status_code == StatusCode::OK)
+        );
+        server.abort();
     }
 
-    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan") }, true)]
-    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan") }, false)]
-    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes") }, true)]
-    #[test_case::test_case ("remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, true)]
-    #[test_case::test_case ("remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, false)]
-    #[test_case::test_case ("remove_all", EmptyRequest {  }, true)]
+    #[test_case::test_case(Some("gzip"), Some("gzip") ; "gzip")]
+    #[test_case::test_case(Some("deflate"), Some("deflate") ; "deflate")]
+    #[test_case::test_case(Some("br"), None ; "unsupported encoding")]
+    #[test_case::test_case(None, None ; "no encoding")]
     #[tokio::test]
-    async fn test_access_backend<T>(path: &str, request: T, backend_success: bool)
-    where
-        T: Serialize,
-    {
-        let (server, addr, mock_backend, mock_configuration) = init().await;
-        let password = String::from("123");
-        *mock_configuration.0.password.lock().unwrap() = password.clone();
-        mock_backend
-            .0
-            .success
-            .store(backend_success, Ordering::SeqCst);
-
-        let client = Client::new();
-
-        let request_builder = if path == "remove" {
-            client.delete(format!("http://{addr}/{path}"))
-        } else {
-            client.post(format!("http://{addr}/{path}"))
-        }
-        .header("x-admin-password", password);
-        let response = request_builder.json(&request).send().await.unwrap();
+    async fn test_compressed_snapshot(
+        accept_encoding: Option<&str>,
+        expected_encoding: Option<&str>,
+    ) {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
 
-        if backend_success {
-            assert_eq!(response.status(), StatusCode::OK.as_u16());
-        } else {
-            assert_eq!(
-                response.status(),
-                StatusCode::INTERNAL_SERVER_ERROR.as_u16()
-            );
+        let mut request = Client::new().get(format!("http://{addr}/timeslots_snapshot"));
+        if let Some(accept_encoding) = accept_encoding {
+            request = request.header("Accept-Encoding", accept_encoding);
         }
+        let response = request.send().await.unwrap();
 
-        assert_backend_calls(mock_backend, path, 1);
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert_eq!(
+            response
+                .headers()
+                .get("content-encoding")
+                .map(|encoding| encoding.to_str().unwrap()),
+            expected_encoding
+        );
         server.abort();
     }
 
-    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("\n") })]
-    #[test_case::test_case ("book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("") })]
-    #[test_case::test_case ("add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("'") })]
     #[tokio::test]
-    async fn test_invalid_input<T>(path: &str, request: T)
-    where
-        T: Serialize,
-    {
-        let (server, addr, mock_backend, mock_configuration) = init().await;
-        let password = String::from("123");
-        *mock_configuration.0.password.lock().unwrap() = password.clone();
-        mock_backend.0.success.store(false, Ordering::SeqCst);
+    async fn test_event_stream() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.password.lock().unwrap() = "123".into();
+        let (server, addr, backend) = init_with_local_backend(mock_configuration).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
 
         let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/events"))
+            .header("x-admin-password", "123")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let mut stream = response.bytes_stream();
 
-        let request_builder = client
-            .post(format!("http://{addr}/{path}"))
-            .header("x-admin-password", password);
-        let response = request_builder.json(&request).send().await.unwrap();
+        let response = client
+            .post(format!("http://{addr}/book"))
+            .json(&BookingRequest {
+                id,
+                client_name: "Stefan".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST.as_u16());
+        // The timeslot added before connecting is no event of the stream
+        let raw_data = timeout(Duration::from_millis(100), stream.next())
+            .await
+            .unwrap();
+        let data = String::from_utf8(raw_data.unwrap().unwrap().to_vec()).unwrap();
+        let field = |name: &str| {
+            data.lines()
+                .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+                .unwrap()
+        };
+        assert_eq!(field("event"), "booked");
+        let event: TimeslotEvent = serde_json::from_str(field("data")).unwrap();
+        assert_eq!(event.kind, TimeslotEventKind::Booked);
+        assert_eq!(event.timeslot.id, id);
+        assert_eq!(event.timeslot.booker_name, "Stefan");
 
-        assert_backend_calls(mock_backend, path, 0);
         server.abort();
     }
 
-    enum Authorization {
-        None,
-        Invalid,
-        Valid,
+    #[tokio::test]
+    async fn test_timeslot_stream_not_compressed() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots"))
+            .header("Accept-Encoding", "gzip")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.headers().get("content-encoding").is_none());
+
+        // Each event arrives right away instead of waiting in a compression buffer
+        let mut stream = response.bytes_stream();
+        assert!(read_from_sse(&mut stream).await.is_empty());
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(read_from_sse(&mut stream).await.len(), 1);
+        server.abort();
     }
 
-    #[test_case::test_case ("post", "book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan") }, Authorization::None, 1, StatusCode::OK)]
-    #[test_case::test_case ("post", "book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan") }, Authorization::Invalid, 1, StatusCode::OK)]
-    #[test_case::test_case ("post", "book", BookingRequest { id: Uuid::new_v4(), client_name: String::from("Stefan") }, Authorization::Valid, 1, StatusCode::OK)]
-    #[test_case::test_case ("post", "add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes") }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
-    #[test_case::test_case ("post", "add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes") }, Authorization::Invalid, 0, StatusCode::UNAUTHORIZED)]
-    #[test_case::test_case ("post", "add", AddTimeslotRequest { datetime: Utc::now(), notes: String::from("Example Notes") }, Authorization::Valid, 1, StatusCode::OK)]
-    #[test_case::test_case ("delete", "remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
-    #[test_case::test_case ("delete", "remove", DeleteTimeslotRequest { id: Uuid::new_v4() }, Authorization::Valid, 1, StatusCode::OK)]
-    #[test_case::test_case ("post", "remove_all", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
-    #[test_case::test_case ("post", "remove_all", EmptyRequest {  }, Authorization::Valid, 1, StatusCode::OK)]
-    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::None, 0, StatusCode::UNAUTHORIZED)]
-    #[test_case::test_case ("get", "admin_page", EmptyRequest {  }, Authorization::Valid, 0,StatusCode::OK)]
     #[tokio::test]
-    async fn test_authorization<T>(
-        method: &str,
-        path: &str,
-        request: T,
-        authorization: Authorization,
-        expected_backend_calls: u64,
-        status_code: StatusCode,
-    ) where
-        T: Serialize,
-    {
-        let (server, addr, mock_backend, mock_configuration) = init().await;
-        let password = String::from("123");
-        let wrong_password = String::from("xyz");
-        *mock_configuration.0.password.lock().unwrap() = password.clone();
+    async fn test_confirmation_page() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        for (hours, notes) in [(1, "Workshop"), (2, "Other Workshop")] {
+            backend
+                .add_timeslot(
+                    Utc::now() + chrono::Duration::hours(hours),
+                    notes.into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let timeslots = backend.current_timeslots().unwrap();
+        let booked = timeslots
+            .iter()
+            .find(|timeslot| timeslot.notes == "Workshop")
+            .unwrap();
+        let unbooked = timeslots
+            .iter()
+            .find(|timeslot| timeslot.notes == "Other Workshop")
+            .unwrap();
+        let code = backend
+            .book_timeslot(booked.id, "Stefan".into(), None, None, None)
+            .unwrap()
+            .bookings
+            .remove(0)
+            .confirmation_code;
 
         let client = Client::new();
-        let mut request_builder = match method.to_lowercase().as_str() {
-            "get" => client.get(format!("http://{addr}/{path}")),
-            "post" => client.post(format!("http://{addr}/{path}")),
-            "delete" => client.delete(format!("http://{addr}/{path}")),
-            _ => panic!("Unsupported HTTP method: {method}"),
-        };
-        request_builder = match authorization {
-            Authorization::None => request_builder,
-            Authorization::Invalid => request_builder.header("x-admin-password", wrong_password),
-            Authorization::Valid => request_builder.header("x-admin-password", password),
+        let get_confirmation = |id: Uuid, code: &str| {
+            client
+                .get(format!("http://{addr}/confirmation/{id}?code={code}"))
+                .send()
         };
-        let response = request_builder.json(&request).send().await.unwrap();
 
-        assert_eq!(response.status(), status_code.as_u16());
-        assert_backend_calls(mock_backend, path, expected_backend_calls);
+        let response = get_confirmation(booked.id, &code).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        let page = response.text().await.unwrap();
+        assert!(page.contains("<td>Stefan</td>"));
+        assert!(page.contains("<td>Workshop</td>"));
+        assert!(page.contains(&format!("<td class=\"code\">{code}</td>")));
+
+        // The code of another booking, a wrong code and a missing timeslot
+        for (id, code) in [
+            (unbooked.id, code.as_str()),
+            (booked.id, "WRONG234"),
+            (Uuid::new_v4(), code.as_str()),
+        ] {
+            let response = get_confirmation(id, code).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND.as_u16());
+        }
         server.abort();
     }
 
     #[tokio::test]
-    async fn test_get_frontend() {
-        let (server, addr, _, mock_configuration) = init().await;
+    async fn test_timeslot_stream_remaining() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                5,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = backend.current_timeslots().unwrap()[0].id;
+        backend
+            .book_timeslot(id, "Stefan".into(), None, None, None)
+            .unwrap();
+        backend
+            .book_timeslot(id, "Peter".into(), None, None, None)
+            .unwrap();
 
-        let mut tmp_file = NamedTempFile::new().unwrap();
-        let expected_html = r#"<!DOCTYPE html>
-<html>
-<head><title>Test</title></head>
-<body><h1>Test</h1></body>
-</html>"#;
-        write!(tmp_file, "{expected_html}").unwrap();
-        *mock_configuration.0.frontend_path.lock().unwrap() = tmp_file.path().to_path_buf();
+        let response = Client::new()
+            .get(format!("http://{addr}/timeslots"))
+            .send()
+            .await
+            .unwrap();
+        let raw_data = timeout(Duration::from_millis(100), response.bytes_stream().next())
+            .await
+            .unwrap();
+        let data = String::from_utf8(raw_data.unwrap().unwrap().to_vec()).unwrap();
+        let json = data
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .unwrap();
+        let timeslots: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(timeslots[0]["capacity"], 5);
+        assert_eq!(timeslots[0]["booked_count"], 2);
+        assert_eq!(timeslots[0]["remaining"], 3);
 
-        let client = Client::new();
-        let response = client.get(format!("http://{addr}/")).send().await.unwrap();
+        server.abort();
+    }
 
-        assert_eq!(response.status(), StatusCode::OK.as_u16());
-        assert_eq!(
-            response
-                .headers()
-                .get("content-type")
-                .unwrap()
-                .to_str()
-                .unwrap(),
-            "text/html; charset=utf-8"
-        );
+    #[tokio::test]
+    async fn test_resume_timeslot_stream() {
+        let (server, addr, backend) = init_with_local_backend(MockConfiguration::new()).await;
 
-        let actual_html = response.text().await.unwrap();
-        assert_eq!(actual_html, expected_html);
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots"))
+            .send()
+            .await
+            .unwrap();
+        let mut stream = response.bytes_stream();
+        let (first_id, timeslots) = read_event_from_sse(&mut stream).await;
+        assert!(timeslots.is_empty());
 
-        server.abort();
-    }
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(1),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let (second_id, _) = read_event_from_sse(&mut stream).await;
+        assert!(second_id > first_id);
+        drop(stream);
 
-    async fn read_from_sse(
-        stream: &mut (impl Stream<Item = Result<Bytes, Error>> + Unpin),
-    ) -> Vec<Timeslot> {
-        let raw_data = timeout(Duration::from_millis(100), stream.next())
+        // The client missed an update while it was disconnected
+        backend
+            .add_timeslot(
+                Utc::now() + chrono::Duration::hours(2),
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let response = client
+            .get(format!("http://{addr}/timeslots"))
+            .header("Last-Event-ID", second_id.to_string())
+            .send()
             .await
             .unwrap();
-        let data = String::from_utf8(raw_data.unwrap().unwrap().to_vec()).unwrap();
-        let json_str = data.strip_prefix("data: ").unwrap();
-        serde_json::from_str(json_str.trim()).unwrap()
+        let (resumed_id, timeslots) = read_event_from_sse(&mut response.bytes_stream()).await;
+        assert!(resumed_id > second_id);
+        assert_eq!(timeslots.len(), 2);
+
+        server.abort();
     }
 
     #[tokio::test]
@@ -451,6 +7321,15 @@ mod test {
                 available: true,
                 booker_name: String::new(),
                 notes: "First Timeslot".into(),
+                capacity: 1,
+                booked_count: 0,
+                duration_minutes: 30,
+                archived: false,
+                waitlist: vec![],
+                version: 0,
+                internal_notes: None,
+                category: None,
+                bookings: vec![],
             },
             Timeslot {
                 id: Uuid::new_v4(),
@@ -458,6 +7337,15 @@ mod test {
                 available: false,
                 booker_name: "Stefan".into(),
                 notes: "Second Timeslot".into(),
+                capacity: 1,
+                booked_count: 1,
+                duration_minutes: 30,
+                archived: false,
+                waitlist: vec![],
+                version: 0,
+                internal_notes: None,
+                category: None,
+                bookings: vec![],
             },
         ];
 
@@ -495,4 +7383,156 @@ mod test {
 
         server.abort();
     }
+
+    #[tokio::test]
+    async fn test_get_available_timeslots_only() {
+        let (server, addr, mock_backend, _) = init().await;
+
+        let available_timeslot = Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: true,
+            booker_name: String::new(),
+            notes: "Available Timeslot".into(),
+            capacity: 1,
+            booked_count: 0,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+        let booked_timeslot = Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now() + Duration::from_secs(1),
+            available: false,
+            booker_name: "Stefan".into(),
+            notes: "Booked Timeslot".into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        };
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots?available_only=true"))
+            .header("Accept", "text/event-stream")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK.as_u16());
+        let mut stream = response.bytes_stream();
+        assert!(read_from_sse(&mut stream).await.is_empty());
+
+        mock_backend
+            .0
+            .timeslot_sender
+            .send(vec![available_timeslot.clone(), booked_timeslot.clone()])
+            .unwrap();
+        assert_eq!(
+            read_from_sse(&mut stream).await,
+            vec![available_timeslot.clone()]
+        );
+
+        // A booking removes the timeslot from the stream
+        let mut now_booked_timeslot = available_timeslot;
+        now_booked_timeslot.available = false;
+        now_booked_timeslot.booked_count = 1;
+        mock_backend
+            .0
+            .timeslot_sender
+            .send(vec![now_booked_timeslot, booked_timeslot])
+            .unwrap();
+        assert!(read_from_sse(&mut stream).await.is_empty());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_sse_keep_alive() {
+        let mock_configuration = MockConfiguration::new();
+        *mock_configuration.0.sse_keep_alive_seconds.lock().unwrap() = 1;
+        let (server, addr, mock_backend, _) = init_with_configuration(mock_configuration).await;
+
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots"))
+            .header("Accept", "text/event-stream")
+            .send()
+            .await
+            .unwrap();
+        let mut stream = response.bytes_stream();
+        let data = read_from_sse(&mut stream).await;
+        assert!(data.is_empty());
+
+        // Without any change a keep-alive comment is sent
+        let keep_alive = timeout(Duration::from_millis(1500), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(keep_alive, Bytes::from(":\n\n"));
+
+        // The stream is still open
+        mock_backend.0.timeslot_sender.send_replace(vec![]);
+        let data = read_from_sse(&mut stream).await;
+        assert!(data.is_empty());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown() {
+        let shutdown = ShutdownSignal::new();
+        let app = create_app(
+            MockTimeslotBackend::new(),
+            MockConfiguration::new(),
+            shutdown.clone(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (trigger_sender, trigger_receiver) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(async move {
+                trigger_receiver.await.unwrap();
+                shutdown.trigger();
+            })
+            .await
+        });
+
+        // An open SSE stream must not prevent the shutdown
+        let client = Client::new();
+        let response = client
+            .get(format!("http://{addr}/timeslots"))
+            .header("Accept", "text/event-stream")
+            .send()
+            .await
+            .unwrap();
+        let mut stream = response.bytes_stream();
+        let data = read_from_sse(&mut stream).await;
+        assert!(data.is_empty());
+
+        trigger_sender.send(()).unwrap();
+        timeout(Duration::from_secs(1), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(timeout(Duration::from_secs(1), stream.next())
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
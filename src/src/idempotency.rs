@@ -0,0 +1,140 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OwnedMutexGuard;
+
+/// How long the result of a request is returned for repetitions with the same key
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Keys remembered at most. Once reached, the oldest key is forgotten for every new one
+pub const MAX_IDEMPOTENCY_KEYS: usize = 10_000;
+
+type Slot<V> = Arc<tokio::sync::Mutex<Option<V>>>;
+
+struct Entries<V> {
+    slots: HashMap<String, (Instant, Slot<V>)>,
+    /// Keys by creation, oldest first. Since all keys share the same TTL, they also expire in
+    /// this order
+    order: VecDeque<(Instant, String)>,
+}
+
+/// Remembers the result of a request by its `Idempotency-Key` header, so a retried request returns
+/// the same result instead of being processed again.
+#[derive(Clone)]
+pub struct IdempotencyCache<V: Clone> {
+    ttl: Duration,
+    max_keys: usize,
+    entries: Arc<Mutex<Entries<V>>>,
+}
+
+impl<V: Clone> IdempotencyCache<V> {
+    pub fn new(ttl: Duration, max_keys: usize) -> Self {
+        Self {
+            ttl,
+            max_keys,
+            entries: Arc::new(Mutex::new(Entries {
+                slots: HashMap::new(),
+                order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Reserves the key until the returned guard is dropped, so concurrent requests with the same
+    /// key wait for the first one instead of being processed again. The guard contains the cached
+    /// result of the key, if any. Storing a result in it caches the result.
+    pub async fn reserve(&self, key: String) -> OwnedMutexGuard<Option<V>> {
+        let slot = {
+            let mut entries = self.entries.lock().unwrap();
+            let now = Instant::now();
+            match entries.slots.get(&key) {
+                Some((created, slot)) if now.duration_since(*created) < self.ttl => slot.clone(),
+                _ => {
+                    self.forget_oldest(&mut entries, now);
+                    let slot = Slot::default();
+                    entries.slots.insert(key.clone(), (now, slot.clone()));
+                    entries.order.push_back((now, key));
+                    slot
+                }
+            }
+        };
+        slot.lock_owned().await
+    }
+
+    /// Forgets expired keys and, if there are still too many, the oldest ones.
+    fn forget_oldest(&self, entries: &mut Entries<V>, now: Instant) {
+        while let Some((created, key)) = entries.order.front() {
+            if now.duration_since(*created) < self.ttl && entries.slots.len() < self.max_keys {
+                break;
+            }
+            // Keys created again after they expired are queued again, with a newer creation
+            if entries
+                .slots
+                .get(key)
+                .is_some_and(|(slot_created, _)| slot_created == created)
+            {
+                entries.slots.remove(key);
+            }
+            entries.order.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    async fn cache(cache: &IdempotencyCache<i32>, key: &str, result: i32) {
+        *cache.reserve(key.into()).await = Some(result);
+    }
+
+    #[tokio::test]
+    async fn test_cached_result() {
+        let idempotency_cache = IdempotencyCache::new(IDEMPOTENCY_TTL, MAX_IDEMPOTENCY_KEYS);
+        assert_eq!(*idempotency_cache.reserve("first".into()).await, None);
+
+        cache(&idempotency_cache, "first", 1).await;
+        cache(&idempotency_cache, "second", 2).await;
+        assert_eq!(*idempotency_cache.reserve("first".into()).await, Some(1));
+        assert_eq!(*idempotency_cache.reserve("second".into()).await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_expired_result() {
+        let idempotency_cache = IdempotencyCache::new(Duration::from_millis(50), 10);
+        cache(&idempotency_cache, "first", 1).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(*idempotency_cache.reserve("first".into()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reservation() {
+        let idempotency_cache = IdempotencyCache::new(IDEMPOTENCY_TTL, MAX_IDEMPOTENCY_KEYS);
+        let mut first = idempotency_cache.reserve("first".into()).await;
+
+        // The same key waits for the first reservation, other keys don't
+        let waiting = tokio::spawn({
+            let idempotency_cache = idempotency_cache.clone();
+            async move { *idempotency_cache.reserve("first".into()).await }
+        });
+        assert_eq!(*idempotency_cache.reserve("second".into()).await, None);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        *first = Some(1);
+        drop(first);
+        assert_eq!(waiting.await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_forget_oldest_key() {
+        let idempotency_cache = IdempotencyCache::new(IDEMPOTENCY_TTL, 2);
+        cache(&idempotency_cache, "first", 1).await;
+        cache(&idempotency_cache, "second", 2).await;
+        cache(&idempotency_cache, "third", 3).await;
+
+        assert_eq!(*idempotency_cache.reserve("second".into()).await, Some(2));
+        assert_eq!(*idempotency_cache.reserve("third".into()).await, Some(3));
+        assert_eq!(*idempotency_cache.reserve("first".into()).await, None);
+        assert_eq!(idempotency_cache.entries.lock().unwrap().slots.len(), 2);
+    }
+}
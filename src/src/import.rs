@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use tracing::error;
+
+/// A timeslot read from an uploaded CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTimeslot {
+    pub datetime: DateTime<Utc>,
+    pub notes: String,
+    pub capacity: Option<i32>,
+}
+
+/// Parses CSV with a header line and `datetime,notes[,capacity]` rows. The error names the first
+/// row (counted without the header, starting at 1) which can't be parsed.
+pub fn from_csv(csv: &str) -> Result<Vec<CsvTimeslot>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+
+    reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| {
+            let row = index + 1;
+            let record = record.map_err(|err| {
+                error!(?err, row, "Failed to read CSV row");
+                format!("Row {row}: invalid CSV")
+            })?;
+            if !(2..=3).contains(&record.len()) {
+                return Err(format!(
+                    "Row {row}: expected the columns datetime,notes[,capacity]"
+                ));
+            }
+            let datetime = record[0]
+                .parse()
+                .map_err(|_| format!("Row {row}: invalid datetime \"{}\"", &record[0]))?;
+            let capacity = record
+                .get(2)
+                .map(|capacity| {
+                    capacity
+                        .parse()
+                        .map_err(|_| format!("Row {row}: invalid capacity \"{capacity}\""))
+                })
+                .transpose()?;
+            Ok(CsvTimeslot {
+                datetime,
+                notes: record[1].to_string(),
+                capacity,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_csv() {
+        let timeslots = from_csv(
+            "datetime,notes,capacity\n\
+            2030-01-31T09:00:00Z,Workshop\n\
+            2030-01-31T10:00:00+01:00,\"Room 1, Lab\",3\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            timeslots,
+            vec![
+                CsvTimeslot {
+                    datetime: "2030-01-31T09:00:00Z".parse().unwrap(),
+                    notes: "Workshop".into(),
+                    capacity: None,
+                },
+                CsvTimeslot {
+                    datetime: "2030-01-31T09:00:00Z".parse().unwrap(),
+                    notes: "Room 1, Lab".into(),
+                    capacity: Some(3),
+                },
+            ]
+        );
+    }
+
+    #[test_case::test_case(
+        "datetime,notes\n2030-01-31T09:00:00Z\n",
+        "Row 1: expected the columns datetime,notes[,capacity]"
+    )]
+    #[test_case::test_case(
+        "datetime,notes\n2030-01-31T09:00:00Z,Workshop\n31.01.2030,Workshop\n",
+        "Row 2: invalid datetime \"31.01.2030\""
+    )]
+    #[test_case::test_case(
+        "datetime,notes,capacity\n2030-01-31T09:00:00Z,Workshop,many\n",
+        "Row 1: invalid capacity \"many\""
+    )]
+    fn test_from_csv_invalid(csv: &str, expected: &str) {
+        assert_eq!(from_csv(csv).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn test_from_csv_without_rows() {
+        assert!(from_csv("datetime,notes\n").unwrap().is_empty());
+    }
+}
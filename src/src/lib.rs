@@ -0,0 +1,53 @@
+//! Booking Manager as a library, e.g. to mount its router under a sub-path of another server.
+//!
+//! ```
+//! use booking_manager::{create_app, shutdown::ShutdownSignal, Configuration, LocalTimeslots};
+//! use chrono::Duration;
+//!
+//! fn booking_router(configuration: impl Configuration) -> axum::Router {
+//!     let backend = LocalTimeslots::new(Duration::days(1), false, None, false);
+//!     let app = create_app(backend, configuration, ShutdownSignal::new());
+//!     axum::Router::new().nest("/booking", app)
+//! }
+//! ```
+
+#[macro_use]
+extern crate diesel;
+
+mod api_error;
+mod auth_lockout;
+mod authentication;
+pub mod backend;
+mod calendar;
+pub mod cleanup;
+pub mod configuration;
+pub mod configuration_handler;
+mod confirmation;
+pub mod database_interface;
+mod export;
+pub mod file_timeslots;
+pub mod hold;
+mod http;
+mod idempotency;
+mod import;
+pub mod local_timeslots;
+mod messages;
+pub mod monitoring;
+mod rate_limit;
+mod recurrence;
+mod schema;
+pub mod shutdown;
+pub mod sqlite_interface;
+mod sqlite_schema;
+#[cfg(test)]
+mod testutils;
+pub mod tls;
+pub mod types;
+mod webhook;
+
+pub use backend::TimeslotBackend;
+pub use configuration::Configuration;
+pub use database_interface::DatabaseInterface;
+pub use http::create_app;
+pub use local_timeslots::LocalTimeslots;
+pub use types::Timeslot;
@@ -1,58 +1,150 @@
-use crate::{backend::TimeslotBackend, types::Timeslot};
+use crate::{
+    backend::{
+        drafts_overlap, event_channel, publish_timeslots, within_timeslot_limit, BackendError,
+        TimeslotBackend, NOTHING_TO_RESTORE, NO_TIMESLOT_AVAILABLE, SWAP_WITH_ITSELF,
+        TIMESLOT_AVAILABLE, TIMESLOT_LIMIT_REACHED, VERSION_CONFLICT,
+    },
+    types::{Booking, Timeslot, TimeslotDraft, TimeslotEvent},
+};
 use chrono::{DateTime, Duration, Utc};
 use std::{
     collections::HashMap,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::sync::{
+    broadcast,
+    watch::{self, Sender},
 };
-use tokio::sync::watch::{self, Sender};
-use tokio_stream::wrappers::WatchStream;
-use tracing::error;
+use tokio_stream::wrappers::{BroadcastStream, WatchStream};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Locks the mutex, recovering it if a panic poisoned it, so one failed request doesn't break
+/// all following ones.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        warn!("Recovering timeslots from a poisoned lock");
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct LocalTimeslots {
     timeslots: Arc<Mutex<HashMap<Uuid, Timeslot>>>,
+    // Timeslots removed by the last remove_all_timeslot
+    last_removed: Arc<Mutex<Option<HashMap<Uuid, Timeslot>>>>,
     sender: Sender<Vec<Timeslot>>,
+    event_sender: broadcast::Sender<TimeslotEvent>,
+    cleanup_retention: Duration,
+    allow_overlap: bool,
+    max_timeslots: Option<usize>,
+    hard_delete: bool,
 }
 
 impl LocalTimeslots {
-    pub fn default() -> LocalTimeslots {
+    pub fn new(
+        cleanup_retention: Duration,
+        allow_overlap: bool,
+        max_timeslots: Option<usize>,
+        hard_delete: bool,
+    ) -> LocalTimeslots {
+        Self::with_timeslots(
+            HashMap::new(),
+            cleanup_retention,
+            allow_overlap,
+            max_timeslots,
+            hard_delete,
+        )
+    }
+
+    /// Starts with the given timeslots, e.g. loaded from a file.
+    pub fn with_timeslots(
+        timeslots: HashMap<Uuid, Timeslot>,
+        cleanup_retention: Duration,
+        allow_overlap: bool,
+        max_timeslots: Option<usize>,
+        hard_delete: bool,
+    ) -> LocalTimeslots {
         let (sender, _) = watch::channel(vec![]);
         Self {
-            timeslots: Arc::new(Mutex::default()),
+            timeslots: Arc::new(Mutex::new(timeslots)),
+            last_removed: Arc::default(),
             sender,
+            event_sender: event_channel(),
+            cleanup_retention,
+            allow_overlap,
+            max_timeslots,
+            hard_delete,
         }
     }
 
-    fn cleanup_outdated_timeslots(&self, max_age: Duration) {
-        let current_time = Utc::now();
-        let cutoff_time = current_time - max_age;
-        let mut timeslots = self.timeslots.lock().unwrap();
-
-        timeslots.retain(|_, timeslot| timeslot.datetime >= cutoff_time);
+    /// Adds `count` example timeslots from tomorrow on, eight per day starting hourly at 9:00 UTC,
+    /// e.g. to demo the frontend without a database.
+    pub fn seed_examples(&self, count: u32) -> Result<(), BackendError> {
+        let first_day = (Utc::now() + Duration::days(1)).date_naive();
+        for index in 0..i64::from(count) {
+            let start = (first_day + Duration::days(index / 8))
+                .and_hms_opt(9, 0, 0)
+                .unwrap()
+                .and_utc()
+                + Duration::hours(index % 8);
+            self.add_timeslot(
+                start,
+                format!("Example Timeslot {}", index + 1),
+                1,
+                30,
+                None,
+                None,
+            )?;
+        }
+        info!(count, "Added example timeslots");
+        Ok(())
     }
 
     fn timeslots(&self) -> Vec<Timeslot> {
-        self.cleanup_outdated_timeslots(Duration::days(1));
+        self.sorted_timeslots(false)
+    }
 
-        let mut timeslots: Vec<Timeslot> = self
-            .timeslots
-            .lock()
-            .unwrap()
-            .clone()
+    fn sorted_timeslots(&self, archived: bool) -> Vec<Timeslot> {
+        let mut timeslots: Vec<Timeslot> = lock(&self.timeslots)
             .values()
+            .filter(|timeslot| timeslot.archived == archived)
             .cloned()
             .collect();
-        timeslots.sort_unstable_by(|a, b| a.datetime.cmp(&b.datetime));
+        timeslots.sort_unstable_by_key(|timeslot| timeslot.datetime);
         timeslots
     }
 
     fn send_timeslots(&self) {
-        let timeslots = self.timeslots();
+        publish_timeslots(&self.sender, &self.event_sender, self.timeslots());
+    }
 
-        if let Err(err) = self.sender.send(timeslots) {
-            error!(?err, "Failed to send current timeslots");
+    /// Books a place of the timeslot, which the caller has locked.
+    fn book(
+        timeslot: &mut Timeslot,
+        booker_name: String,
+        client_email: Option<String>,
+        client_phone: Option<String>,
+        booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError> {
+        if !timeslot.available || timeslot.booked_count >= timeslot.capacity {
+            let err = "Timeslot is already fully booked";
+            error!(err);
+            return Err(BackendError::AlreadyBooked(err.into()));
+        }
+        if timeslot.datetime < Utc::now() {
+            let err = "Timeslot already passed";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
         }
+        timeslot.add_booking(Booking::new(
+            booker_name,
+            client_email,
+            client_phone,
+            booker_notes,
+        ));
+        Ok(timeslot.clone())
     }
 }
 
@@ -63,79 +155,533 @@ impl TimeslotBackend for LocalTimeslots {
         stream
     }
 
-    fn book_timeslot(&self, id: Uuid, booker_name: String) -> Result<(), String> {
-        if let Some(timeslot) = self.timeslots.lock().unwrap().get_mut(&id) {
-            if !timeslot.available {
-                let err = "Timeslot was already booked";
+    fn event_stream(&self) -> BroadcastStream<TimeslotEvent> {
+        // Catches up on the changes made before, so they aren't sent as events of this stream
+        self.send_timeslots();
+        BroadcastStream::new(self.event_sender.subscribe())
+    }
+
+    fn current_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        Ok(self.timeslots())
+    }
+
+    fn timeslots_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Timeslot>, BackendError> {
+        Ok(self
+            .timeslots()
+            .into_iter()
+            .filter(|timeslot| from <= timeslot.datetime && timeslot.datetime <= to)
+            .collect())
+    }
+
+    fn count_timeslots(&self, available_only: bool) -> Result<i64, BackendError> {
+        let count = lock(&self.timeslots)
+            .values()
+            .filter(|timeslot| !timeslot.archived && (!available_only || timeslot.available))
+            .count();
+        Ok(count as i64)
+    }
+
+    fn book_timeslot(
+        &self,
+        id: Uuid,
+        booker_name: String,
+        client_email: Option<String>,
+        client_phone: Option<String>,
+        booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError> {
+        let booked_timeslot = if let Some(timeslot) = lock(&self.timeslots)
+            .get_mut(&id)
+            .filter(|timeslot| !timeslot.archived)
+        {
+            Self::book(
+                timeslot,
+                booker_name,
+                client_email,
+                client_phone,
+                booker_notes,
+            )?
+        } else {
+            let err = "Timeslot does not exist and can't therefore not be booked";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        self.send_timeslots();
+        Ok(booked_timeslot)
+    }
+
+    fn book_first_available(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        booker_name: String,
+    ) -> Result<Timeslot, BackendError> {
+        let now = Utc::now();
+        let booked_timeslot = if let Some(timeslot) = lock(&self.timeslots)
+            .values_mut()
+            .filter(|timeslot| {
+                !timeslot.archived
+                    && timeslot.available
+                    && timeslot.booked_count < timeslot.capacity
+                    && from <= timeslot.datetime
+                    && timeslot.datetime <= to
+                    && now <= timeslot.datetime
+            })
+            .min_by_key(|timeslot| timeslot.datetime)
+        {
+            Self::book(timeslot, booker_name, None, None, None)?
+        } else {
+            error!(%from, %to, NO_TIMESLOT_AVAILABLE);
+            return Err(BackendError::NotFound(NO_TIMESLOT_AVAILABLE.into()));
+        };
+        self.send_timeslots();
+        Ok(booked_timeslot)
+    }
+
+    fn cancel_booking(&self, id: Uuid) -> Result<Vec<String>, BackendError> {
+        let promoted_codes = if let Some(timeslot) = lock(&self.timeslots)
+            .get_mut(&id)
+            .filter(|timeslot| !timeslot.archived)
+        {
+            if timeslot.booked_count == 0 {
+                let err = "Timeslot is not booked and can't therefore not be cancelled";
+                error!(err);
+                return Err(BackendError::Conflict(err.into()));
+            }
+            timeslot.cancel_booking()
+        } else {
+            let err = "Timeslot does not exist and can't therefore not be cancelled";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        self.send_timeslots();
+        Ok(promoted_codes)
+    }
+
+    fn cancel_own_booking(&self, confirmation_code: &str) -> Result<Vec<String>, BackendError> {
+        let promoted_codes = if let Some(timeslot) = lock(&self.timeslots)
+            .values_mut()
+            .find(|timeslot| !timeslot.archived && timeslot.booking(confirmation_code).is_some())
+        {
+            timeslot.cancel_booking_of(confirmation_code)
+        } else {
+            let err = "Booking does not exist and can't therefore not be cancelled";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        self.send_timeslots();
+        Ok(promoted_codes)
+    }
+
+    fn swap_bookings(&self, first_id: Uuid, second_id: Uuid) -> Result<(), BackendError> {
+        if first_id == second_id {
+            error!(SWAP_WITH_ITSELF);
+            return Err(BackendError::Conflict(SWAP_WITH_ITSELF.into()));
+        }
+        let mut timeslots = lock(&self.timeslots);
+        let [Some(first), Some(second)] = timeslots
+            .get_disjoint_mut([&first_id, &second_id])
+            .map(|timeslot| timeslot.filter(|timeslot| !timeslot.archived))
+        else {
+            let err = "Timeslot does not exist and its booking can't therefore not be swapped";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        if let Err(err) = first.swap_bookings(second) {
+            error!(err);
+            return Err(BackendError::Conflict(err));
+        }
+        drop(timeslots);
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn confirm_booking(&self, id: Uuid) -> Result<(), BackendError> {
+        if let Some(timeslot) = lock(&self.timeslots)
+            .get_mut(&id)
+            .filter(|timeslot| !timeslot.archived)
+        {
+            if !timeslot.confirm_bookings() {
+                let err = "Timeslot is not booked and can't therefore not be confirmed";
                 error!(err);
-                return Err(err.into());
+                return Err(BackendError::Conflict(err.into()));
+            }
+        } else {
+            let err = "Timeslot does not exist and can't therefore not be confirmed";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn release_expired_holds(&self, cutoff: DateTime<Utc>) -> Result<usize, BackendError> {
+        let mut released = 0;
+        for timeslot in lock(&self.timeslots).values_mut() {
+            if timeslot.hold_expired(cutoff) {
+                released += timeslot.release_expired_holds(cutoff);
+            }
+        }
+        if released > 0 {
+            self.send_timeslots();
+        }
+        Ok(released)
+    }
+
+    fn join_waitlist(&self, id: Uuid, client_name: String) -> Result<usize, BackendError> {
+        let position = if let Some(timeslot) = lock(&self.timeslots)
+            .get_mut(&id)
+            .filter(|timeslot| !timeslot.archived)
+        {
+            if timeslot.available {
+                error!(TIMESLOT_AVAILABLE);
+                return Err(BackendError::Conflict(TIMESLOT_AVAILABLE.into()));
             }
             if timeslot.datetime < Utc::now() {
                 let err = "Timeslot already passed";
                 error!(err);
-                return Err(err.into());
+                return Err(BackendError::Conflict(err.into()));
             }
-            timeslot.available = false;
-            timeslot.booker_name = booker_name;
+            timeslot.waitlist.push(client_name);
+            timeslot.waitlist.len()
         } else {
-            let err = "Timeslot does not exist and can't therefore not be booked";
+            let err = "Timeslot does not exist and its waitlist can't therefore not be joined";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        self.send_timeslots();
+        Ok(position)
+    }
+
+    fn add_timeslot(
+        &self,
+        datetime: DateTime<Utc>,
+        notes: String,
+        capacity: i32,
+        duration_minutes: i32,
+        internal_notes: Option<String>,
+        category: Option<String>,
+    ) -> Result<(), BackendError> {
+        let mut timeslots = lock(&self.timeslots);
+        let active_timeslots = timeslots
+            .values()
+            .filter(|timeslot| !timeslot.archived)
+            .count();
+        if !within_timeslot_limit(self.max_timeslots, active_timeslots, 1) {
+            error!(TIMESLOT_LIMIT_REACHED);
+            return Err(BackendError::Conflict(TIMESLOT_LIMIT_REACHED.into()));
+        }
+        let end = datetime + Duration::minutes(duration_minutes.into());
+        if !self.allow_overlap
+            && timeslots
+                .values()
+                .any(|timeslot| !timeslot.archived && timeslot.overlaps(datetime, end))
+        {
+            let err = "Overlapping timeslot";
             error!(err);
-            return Err(err.into());
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        let timeslot = TimeslotDraft {
+            datetime,
+            notes,
+            capacity,
+            duration_minutes,
+            internal_notes,
+            category,
         }
+        .into_timeslot();
+        timeslots.insert(timeslot.id, timeslot);
+        drop(timeslots);
         self.send_timeslots();
         Ok(())
     }
 
-    fn add_timeslot(&self, datetime: DateTime<Utc>, notes: String) -> Result<(), String> {
-        let id = Uuid::new_v4();
-        self.timeslots.lock().unwrap().insert(
-            id,
-            Timeslot {
-                id,
-                datetime,
-                available: true,
-                booker_name: String::new(),
-                notes,
-            },
-        );
+    fn check_timeslots_bulk(&self, drafts: &[TimeslotDraft]) -> Result<(), BackendError> {
+        let timeslots = lock(&self.timeslots);
+        let active_timeslots: Vec<&Timeslot> = timeslots
+            .values()
+            .filter(|timeslot| !timeslot.archived)
+            .collect();
+        if !within_timeslot_limit(self.max_timeslots, active_timeslots.len(), drafts.len()) {
+            error!(TIMESLOT_LIMIT_REACHED);
+            return Err(BackendError::Conflict(TIMESLOT_LIMIT_REACHED.into()));
+        }
+        if !self.allow_overlap
+            && (drafts_overlap(drafts)
+                || drafts.iter().any(|draft| {
+                    active_timeslots
+                        .iter()
+                        .any(|timeslot| timeslot.overlaps(draft.datetime, draft.end()))
+                }))
+        {
+            let err = "Overlapping timeslot";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+        Ok(())
+    }
+
+    fn edit_timeslot(
+        &self,
+        id: Uuid,
+        datetime: DateTime<Utc>,
+        notes: String,
+        internal_notes: Option<String>,
+        version: i32,
+    ) -> Result<(), BackendError> {
+        let mut timeslots = lock(&self.timeslots);
+        let Some(timeslot) = timeslots.get(&id).filter(|timeslot| !timeslot.archived) else {
+            let err = "Timeslot does not exist and can't therefore not be edited";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        if timeslot.version != version {
+            error!(
+                expected = version,
+                actual = timeslot.version,
+                "Edit failed. Version conflict"
+            );
+            return Err(BackendError::Conflict(VERSION_CONFLICT.into()));
+        }
+        let end = datetime + Duration::minutes(timeslot.duration_minutes.into());
+        if !self.allow_overlap
+            && timeslots
+                .values()
+                .any(|other| other.id != id && !other.archived && other.overlaps(datetime, end))
+        {
+            let err = "Overlapping timeslot";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        if let Some(timeslot) = timeslots.get_mut(&id) {
+            timeslot.datetime = datetime;
+            timeslot.notes = notes;
+            timeslot.internal_notes = internal_notes;
+            timeslot.version += 1;
+        }
+        drop(timeslots);
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn update_notes(&self, id: Uuid, notes: String) -> Result<(), BackendError> {
+        if let Some(timeslot) = lock(&self.timeslots)
+            .get_mut(&id)
+            .filter(|timeslot| !timeslot.archived)
+        {
+            timeslot.notes = notes;
+            timeslot.version += 1;
+        } else {
+            let err = "Timeslot does not exist and its notes can't therefore not be updated";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn reschedule(&self, id: Uuid, datetime: DateTime<Utc>) -> Result<(), BackendError> {
+        let mut timeslots = lock(&self.timeslots);
+        let Some(timeslot) = timeslots.get(&id).filter(|timeslot| !timeslot.archived) else {
+            let err = "Timeslot does not exist and can't therefore not be rescheduled";
+            error!(err);
+            return Err(BackendError::NotFound(err.into()));
+        };
+        if timeslot.booked_count == 0 {
+            let err = "Timeslot is not booked and can't therefore not be rescheduled";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+        let end = datetime + Duration::minutes(timeslot.duration_minutes.into());
+        if !self.allow_overlap
+            && timeslots
+                .values()
+                .any(|other| other.id != id && !other.archived && other.overlaps(datetime, end))
+        {
+            let err = "Overlapping timeslot";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        if let Some(timeslot) = timeslots.get_mut(&id) {
+            timeslot.datetime = datetime;
+            timeslot.version += 1;
+        }
+        drop(timeslots);
         self.send_timeslots();
         Ok(())
     }
 
-    fn remove_timeslot(&self, id: Uuid) -> Result<(), String> {
-        if self.timeslots.lock().unwrap().remove(&id).is_none() {
+    fn remove_timeslot(&self, id: Uuid) -> Result<(), BackendError> {
+        let mut timeslots = lock(&self.timeslots);
+        let removed = match timeslots.get_mut(&id).filter(|timeslot| !timeslot.archived) {
+            Some(_) if self.hard_delete => timeslots.remove(&id).is_some(),
+            Some(timeslot) => {
+                timeslot.archived = true;
+                true
+            }
+            None => false,
+        };
+        drop(timeslots);
+        if !removed {
             let err = "Timeslot does not exist and can't therefore not be removed";
             error!(err);
-            return Err(err.into());
+            return Err(BackendError::NotFound(err.into()));
         }
         self.send_timeslots();
         Ok(())
     }
 
-    fn remove_all_timeslot(&self) -> Result<(), String> {
-        self.timeslots.lock().unwrap().clear();
+    fn archived_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        Ok(self.sorted_timeslots(true))
+    }
+
+    fn remove_all_timeslot(&self) -> Result<(), BackendError> {
+        let removed = std::mem::take(&mut *lock(&self.timeslots));
+        // Removing nothing must not overwrite a removal which can still be restored
+        if !removed.is_empty() {
+            *lock(&self.last_removed) = Some(removed);
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn restore_last(&self) -> Result<(), BackendError> {
+        let Some(removed) = lock(&self.last_removed).take() else {
+            error!(NOTHING_TO_RESTORE);
+            return Err(BackendError::Conflict(NOTHING_TO_RESTORE.into()));
+        };
+        lock(&self.timeslots).extend(removed);
         self.send_timeslots();
         Ok(())
     }
+
+    fn count_bookings(&self, client_name: &str) -> Result<usize, BackendError> {
+        Ok(self
+            .timeslots()
+            .iter()
+            .map(|timeslot| timeslot.bookings_of(client_name))
+            .sum())
+    }
+
+    fn remove_outdated_timeslots(&self) -> Result<usize, BackendError> {
+        let cutoff_time = Utc::now() - self.cleanup_retention;
+        let removed = {
+            let mut timeslots = lock(&self.timeslots);
+            let count = timeslots.len();
+            timeslots.retain(|_, timeslot| timeslot.end() >= cutoff_time);
+            count - timeslots.len()
+        };
+
+        if removed > 0 {
+            self.send_timeslots();
+        }
+        Ok(removed)
+    }
+
+    fn search_bookings(&self, search: &str) -> Result<Vec<Timeslot>, BackendError> {
+        Ok(self
+            .timeslots()
+            .into_iter()
+            .filter(|timeslot| timeslot.booker_name_contains(search))
+            .collect())
+    }
+
+    fn find_timeslot(&self, id: Uuid) -> Result<Option<Timeslot>, BackendError> {
+        Ok(lock(&self.timeslots)
+            .get(&id)
+            .filter(|timeslot| !timeslot.archived)
+            .cloned())
+    }
+
+    fn find_by_confirmation(&self, code: &str) -> Result<Option<Timeslot>, BackendError> {
+        Ok(self
+            .timeslots()
+            .into_iter()
+            .find(|timeslot| timeslot.booking(code).is_some()))
+    }
+
+    fn health_check(&self) -> Result<(), BackendError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{
-        backend::TimeslotBackend, local_timeslots::LocalTimeslots,
-        testutils::read_from_timeslot_stream,
+        backend::TimeslotBackend,
+        local_timeslots::LocalTimeslots,
+        testutils::{assert_no_timeslot_event, read_from_event_stream, read_from_timeslot_stream},
+        types::{TimeslotDraft, TimeslotEventKind},
     };
 
+    #[test]
+    fn test_archive_removed_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        local_timeslots.remove_all_timeslot().unwrap();
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+        local_timeslots
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+
+        local_timeslots.remove_timeslot(timeslot_id).unwrap();
+        assert!(local_timeslots.timeslots().is_empty());
+        let archived_timeslots = local_timeslots.archived_timeslots().unwrap();
+        assert_eq!(archived_timeslots.len(), 1);
+        assert!(archived_timeslots[0].archived);
+        assert_eq!(archived_timeslots[0].booker_name, "Stefan");
+
+        // Archived timeslots can't be changed anymore
+        local_timeslots.cancel_booking(timeslot_id).unwrap_err();
+        local_timeslots.remove_timeslot(timeslot_id).unwrap_err();
+    }
+
+    #[test]
+    fn test_hard_delete() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, true);
+        local_timeslots.remove_all_timeslot().unwrap();
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+
+        local_timeslots.remove_timeslot(timeslot_id).unwrap();
+        assert!(local_timeslots.timeslots().is_empty());
+        assert!(local_timeslots.archived_timeslots().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_add_book_remove_single_timeslot() {
-        let local_timeslots = LocalTimeslots::default();
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
         let mut timeslot_stream = local_timeslots.timeslot_stream();
 
         let datetime = Utc::now() + Duration::hours(1);
         let notes = String::from("First Timeslot");
         local_timeslots
-            .add_timeslot(datetime, notes.clone())
+            .add_timeslot(datetime, notes.clone(), 1, 30, None, None)
             .unwrap();
 
         let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
@@ -147,7 +693,7 @@ mod test {
 
         let booker_name = String::from("Stefan");
         local_timeslots
-            .book_timeslot(timeslot_id, booker_name.clone())
+            .book_timeslot(timeslot_id, booker_name.clone(), None, None, None)
             .unwrap();
 
         let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
@@ -157,7 +703,7 @@ mod test {
 
         let booker_name = String::from("Peter");
         local_timeslots
-            .book_timeslot(timeslot_id, booker_name.clone())
+            .book_timeslot(timeslot_id, booker_name.clone(), None, None, None)
             .unwrap_err();
 
         local_timeslots.remove_timeslot(timeslot_id).unwrap();
@@ -167,85 +713,1274 @@ mod test {
         local_timeslots.remove_timeslot(timeslot_id).unwrap_err();
     }
 
-    #[test]
-    fn test_try_book_outdated_timeslot() {
-        let local_timeslots = LocalTimeslots::default();
+    #[tokio::test]
+    async fn test_event_stream() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Existing Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
 
-        let datetime = Utc::now() - Duration::hours(2);
+        // Only changes after subscribing are sent
+        let mut event_stream = local_timeslots.event_stream();
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(2),
+                "New Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let event = read_from_event_stream(&mut event_stream).await;
+        assert_eq!(event.kind, TimeslotEventKind::Added);
+        assert_eq!(event.timeslot.notes, "New Timeslot");
+        let timeslot_id = event.timeslot.id;
+
+        local_timeslots
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        let event = read_from_event_stream(&mut event_stream).await;
+        assert_eq!(event.kind, TimeslotEventKind::Booked);
+        assert_eq!(event.timeslot.booker_name, "Stefan");
+
+        // The booking handed to the waitlist is both a cancellation and a booking
+        local_timeslots
+            .join_waitlist(timeslot_id, "Peter".into())
+            .unwrap();
+        local_timeslots.cancel_booking(timeslot_id).unwrap();
+        let event = read_from_event_stream(&mut event_stream).await;
+        assert_eq!(event.kind, TimeslotEventKind::Cancelled);
+        assert_eq!(event.timeslot.booker_name, "Stefan");
+        let event = read_from_event_stream(&mut event_stream).await;
+        assert_eq!(event.kind, TimeslotEventKind::Booked);
+        assert_eq!(event.timeslot.booker_name, "Peter");
+
+        local_timeslots.cancel_booking(timeslot_id).unwrap();
+        let event = read_from_event_stream(&mut event_stream).await;
+        assert_eq!(event.kind, TimeslotEventKind::Cancelled);
+        assert_eq!(event.timeslot.booked_count, 0);
+
+        local_timeslots.remove_timeslot(timeslot_id).unwrap();
+        let event = read_from_event_stream(&mut event_stream).await;
+        assert_eq!(event.kind, TimeslotEventKind::Removed);
+        assert_eq!(event.timeslot.id, timeslot_id);
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_timeslots_not_sent_again() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        let mut timeslot_stream = local_timeslots.timeslot_stream();
+        assert!(read_from_timeslot_stream(&mut timeslot_stream)
+            .await
+            .is_empty());
+
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "First Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = read_from_timeslot_stream(&mut timeslot_stream).await[0].id;
+        local_timeslots
+            .book_timeslot(timeslot_id, String::from("Stefan"), None, None, None)
+            .unwrap();
+        local_timeslots.confirm_booking(timeslot_id).unwrap();
+        assert!(read_from_timeslot_stream(&mut timeslot_stream).await[0].bookings[0].confirmed);
+
+        // Confirming again changes nothing
+        local_timeslots.confirm_booking(timeslot_id).unwrap();
+        assert_no_timeslot_event(&mut timeslot_stream).await;
+
+        // A new stream starts with the current timeslots without notifying the existing ones
+        let mut second_stream = local_timeslots.timeslot_stream();
+        assert_eq!(read_from_timeslot_stream(&mut second_stream).await.len(), 1);
+        assert_no_timeslot_event(&mut timeslot_stream).await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_booking() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        let mut timeslot_stream = local_timeslots.timeslot_stream();
+
+        let datetime = Utc::now() + Duration::hours(1);
         let notes = String::from("First Timeslot");
         local_timeslots
-            .add_timeslot(datetime, notes.clone())
+            .add_timeslot(datetime, notes.clone(), 1, 30, None, None)
             .unwrap();
 
-        let timeslots = local_timeslots.timeslots();
+        let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
         let timeslot_id = timeslots[0].id;
+        assert!(matches!(
+            local_timeslots.cancel_booking(timeslot_id),
+            Err(BackendError::Conflict(_))
+        )); // not booked yet
+
+        local_timeslots
+            .book_timeslot(timeslot_id, String::from("Stefan"), None, None, None)
+            .unwrap();
+        let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert!(!timeslots[0].available);
+
+        local_timeslots.cancel_booking(timeslot_id).unwrap();
+        let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
         assert_eq!(timeslots.len(), 1);
         assert!(timeslots[0].available);
+        assert_eq!(timeslots[0].booker_name, "");
 
-        let booker_name = String::from("Stefan");
         local_timeslots
-            .book_timeslot(timeslot_id, booker_name.clone())
-            .unwrap_err();
+            .book_timeslot(timeslot_id, String::from("Peter"), None, None, None)
+            .unwrap();
+        let timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(timeslots[0].booker_name, "Peter");
+
+        local_timeslots.cancel_booking(Uuid::new_v4()).unwrap_err(); // not existing timeslot
     }
 
     #[test]
-    fn test_remove_multiple_timeslots() {
-        let local_timeslots = LocalTimeslots::default();
+    fn test_book_timeslot_with_capacity() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
 
-        let datetime_1 = Utc::now();
-        let notes_1 = String::from("First Timeslot");
-        let datetime_2 = Utc::now();
-        let notes_2 = String::from("Seconds Timeslot");
-        let datetime_3 = Utc::now();
-        let notes_3 = String::from("Third Timeslot");
+        let datetime = Utc::now() + Duration::hours(1);
+        let notes = String::from("Group Timeslot");
+        local_timeslots
+            .add_timeslot(datetime, notes.clone(), 3, 30, None, None)
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
 
         local_timeslots
-            .add_timeslot(datetime_1, notes_1.clone())
+            .book_timeslot(timeslot_id, String::from("Stefan"), None, None, None)
             .unwrap();
+        let timeslots = local_timeslots.timeslots();
+        assert!(timeslots[0].available);
+        assert_eq!(timeslots[0].booked_count, 1);
+        assert_eq!(timeslots[0].booker_name, "Stefan");
+
         local_timeslots
-            .add_timeslot(datetime_2, notes_2.clone())
+            .book_timeslot(timeslot_id, String::from("Peter"), None, None, None)
             .unwrap();
         local_timeslots
-            .add_timeslot(datetime_3, notes_3.clone())
+            .book_timeslot(timeslot_id, String::from("Anna"), None, None, None)
             .unwrap();
-
-        local_timeslots.remove_timeslot(Uuid::new_v4()).unwrap_err(); // try to delete not existing timeslot
-        let timeslots = local_timeslots.timeslots();
-        assert_eq!(timeslots.len(), 3);
-
-        local_timeslots.remove_timeslot(timeslots[0].id).unwrap();
         let timeslots = local_timeslots.timeslots();
-        assert_eq!(timeslots.len(), 2);
+        assert!(!timeslots[0].available);
+        assert_eq!(timeslots[0].booked_count, 3);
+        assert_eq!(timeslots[0].booker_name, "Stefan, Peter, Anna");
 
-        local_timeslots.remove_all_timeslot().unwrap();
+        let err = local_timeslots
+            .book_timeslot(timeslot_id, String::from("Maria"), None, None, None)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BackendError::AlreadyBooked("Timeslot is already fully booked".into())
+        );
         let timeslots = local_timeslots.timeslots();
-        assert_eq!(timeslots.len(), 0);
+        assert_eq!(timeslots[0].booked_count, 3);
     }
 
     #[test]
-    fn cleanup_outdated_timeslots() {
-        let local_timeslots = LocalTimeslots::default();
-
-        let datetime_1 = Utc::now();
-        let notes_1 = String::from("First Timeslot");
-        let datetime_2 = Utc::now() - Duration::hours(2);
-        let notes_2 = String::from("Seconds Timeslot");
-        let datetime_3 = Utc::now() - Duration::days(2);
-        let notes_3 = String::from("Third Timeslot");
+    fn test_release_expired_holds() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
 
+        let current_time = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(current_time, "Unconfirmed".into(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(
+                current_time + Duration::hours(1),
+                "Confirmed".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let current_timeslots = local_timeslots.timeslots();
+        for timeslot in &current_timeslots {
+            local_timeslots
+                .book_timeslot(timeslot.id, "Stefan".into(), None, None, None)
+                .unwrap();
+        }
         local_timeslots
-            .add_timeslot(datetime_1, notes_1.clone())
+            .confirm_booking(current_timeslots[1].id)
             .unwrap();
+        // A timeslot without booking can't be confirmed
         local_timeslots
-            .add_timeslot(datetime_2, notes_2.clone())
+            .cancel_booking(current_timeslots[0].id)
             .unwrap();
+        assert!(matches!(
+            local_timeslots.confirm_booking(current_timeslots[0].id),
+            Err(BackendError::Conflict(_))
+        ));
+        assert!(matches!(
+            local_timeslots.confirm_booking(Uuid::new_v4()),
+            Err(BackendError::NotFound(_))
+        ));
+        // Bookings made after the confirmation are held again
+        for timeslot in &current_timeslots {
+            local_timeslots
+                .book_timeslot(timeslot.id, "Peter".into(), None, None, None)
+                .unwrap();
+        }
+
+        // All bookings are within the hold
+        assert_eq!(
+            local_timeslots
+                .release_expired_holds(Utc::now() - Duration::minutes(10))
+                .unwrap(),
+            0
+        );
+
+        assert_eq!(
+            local_timeslots
+                .release_expired_holds(Utc::now() + Duration::seconds(1))
+                .unwrap(),
+            2
+        );
+        let current_timeslots = local_timeslots.timeslots();
+        assert!(current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booked_count, 0);
+        assert!(current_timeslots[0].bookings.is_empty());
+        // Only the unconfirmed booking of the group timeslot is released
+        assert!(current_timeslots[1].available);
+        assert_eq!(current_timeslots[1].booked_count, 1);
+        assert_eq!(current_timeslots[1].booker_name, "Stefan");
+        assert!(current_timeslots[1].bookings[0].confirmed);
+    }
+
+    #[test]
+    fn test_waitlist() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
         local_timeslots
-            .add_timeslot(datetime_3, notes_3.clone())
+            .add_timeslot(datetime, "Workshop".into(), 1, 30, None, None)
             .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
 
-        let timeslots = local_timeslots.timeslots();
-        assert_eq!(timeslots.len(), 2);
-        assert_eq!(timeslots[0].notes, "Seconds Timeslot");
-        assert_eq!(timeslots[1].notes, "First Timeslot");
+        let err = local_timeslots
+            .join_waitlist(timeslot_id, "Peter".into())
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict(TIMESLOT_AVAILABLE.into()));
+
+        local_timeslots
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        assert_eq!(
+            local_timeslots
+                .join_waitlist(timeslot_id, "Peter".into())
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            local_timeslots
+                .join_waitlist(timeslot_id, "Anna".into())
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            local_timeslots.timeslots()[0].waitlist,
+            vec!["Peter", "Anna"]
+        );
+
+        // The first client on the waitlist takes over the cancelled booking
+        let confirmation_code = local_timeslots
+            .cancel_booking(timeslot_id)
+            .unwrap()
+            .remove(0);
+        let timeslot = local_timeslots
+            .find_by_confirmation(&confirmation_code)
+            .unwrap()
+            .unwrap();
+        assert!(!timeslot.available);
+        assert_eq!(timeslot.booked_count, 1);
+        assert_eq!(timeslot.booker_name, "Peter");
+        assert_eq!(timeslot.waitlist, vec!["Anna"]);
+
+        assert_eq!(
+            local_timeslots.cancel_booking(timeslot_id).unwrap().len(),
+            1
+        );
+        assert!(local_timeslots
+            .cancel_booking(timeslot_id)
+            .unwrap()
+            .is_empty());
+        let timeslot = local_timeslots.timeslots()[0].clone();
+        assert!(timeslot.available);
+        assert_eq!(timeslot.booked_count, 0);
+        assert!(timeslot.waitlist.is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_booking() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let handles: Vec<_> = ["Stefan", "Peter"]
+            .into_iter()
+            .map(|booker_name| {
+                let local_timeslots = local_timeslots.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    local_timeslots.book_timeslot(timeslot_id, booker_name.into(), None, None, None)
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots[0].booked_count, 1);
+        assert!(["Stefan", "Peter"].contains(&timeslots[0].booker_name.as_str()));
+    }
+
+    #[test]
+    fn test_confirmation_code() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        let notes = String::from("First Timeslot");
+        local_timeslots
+            .add_timeslot(datetime, notes.clone(), 2, 30, None, None)
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+        assert!(local_timeslots.timeslots()[0].bookings.is_empty());
+
+        let booked_timeslot = local_timeslots
+            .book_timeslot(timeslot_id, String::from("Stefan"), None, None, None)
+            .unwrap();
+        assert_eq!(booked_timeslot.id, timeslot_id);
+        assert_eq!(booked_timeslot.booker_name, "Stefan");
+        let confirmation_code = booked_timeslot.bookings[0].confirmation_code.clone();
+        assert_eq!(confirmation_code.len(), 8);
+        let timeslot = local_timeslots
+            .find_by_confirmation(&confirmation_code)
+            .unwrap()
+            .unwrap();
+        assert_eq!(timeslot.id, timeslot_id);
+        assert_eq!(timeslot.booker_name, "Stefan");
+
+        // Every booking of the same timeslot gets its own code
+        let booked_timeslot = local_timeslots
+            .book_timeslot(timeslot_id, String::from("Peter"), None, None, None)
+            .unwrap();
+        let second_code = booked_timeslot.bookings[1].confirmation_code.clone();
+        assert_ne!(second_code, confirmation_code);
+        assert_eq!(
+            booked_timeslot.bookings[0].confirmation_code,
+            confirmation_code
+        );
+        assert_eq!(booked_timeslot.booker_name, "Stefan, Peter");
+        assert_eq!(booked_timeslot.booked_count, 2);
+        assert_eq!(
+            local_timeslots
+                .find_by_confirmation(&second_code)
+                .unwrap()
+                .unwrap()
+                .id,
+            timeslot_id
+        );
+
+        local_timeslots.cancel_booking(timeslot_id).unwrap();
+        assert_eq!(
+            local_timeslots
+                .find_by_confirmation(&confirmation_code)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cancel_own_booking() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Group timeslot".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+        local_timeslots
+            .book_timeslot(
+                timeslot_id,
+                "Stefan".into(),
+                Some("stefan@example.com".into()),
+                None,
+                None,
+            )
+            .unwrap();
+        let booked_timeslot = local_timeslots
+            .book_timeslot(
+                timeslot_id,
+                "Peter".into(),
+                Some("peter@example.com".into()),
+                Some("+436641234567".into()),
+                None,
+            )
+            .unwrap();
+        let stefans_code = booked_timeslot.bookings[0].confirmation_code.clone();
+
+        // Only Stefan's place is released, Peter keeps his booking and details
+        assert!(local_timeslots
+            .cancel_own_booking(&stefans_code)
+            .unwrap()
+            .is_empty());
+        let timeslot = local_timeslots.timeslots().remove(0);
+        assert_eq!(timeslot.booked_count, 1);
+        assert_eq!(timeslot.booker_name, "Peter");
+        assert!(timeslot.available);
+        assert_eq!(timeslot.bookings, booked_timeslot.bookings[1..]);
+        assert!(matches!(
+            local_timeslots.cancel_own_booking(&stefans_code),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_email() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, String::from("First Timeslot"), 3, 30, None, None)
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+
+        local_timeslots
+            .book_timeslot(
+                timeslot_id,
+                String::from("Stefan"),
+                Some(String::from("stefan@example.com")),
+                None,
+                None,
+            )
+            .unwrap();
+        local_timeslots
+            .book_timeslot(
+                timeslot_id,
+                String::from("Peter"),
+                None,
+                Some(String::from("+436641234567")),
+                None,
+            )
+            .unwrap();
+        local_timeslots
+            .book_timeslot(
+                timeslot_id,
+                String::from("Anna"),
+                Some(String::from("anna@example.com")),
+                None,
+                Some(String::from("Vegetarian")),
+            )
+            .unwrap();
+        let timeslot = local_timeslots.timeslots()[0].clone();
+        let details: Vec<_> = timeslot
+            .bookings
+            .iter()
+            .map(|booking| {
+                (
+                    booking.booker_name.as_str(),
+                    booking.client_email.as_deref(),
+                    booking.client_phone.as_deref(),
+                    booking.booker_notes.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            details,
+            [
+                ("Stefan", Some("stefan@example.com"), None, None),
+                ("Peter", None, Some("+436641234567"), None),
+                ("Anna", Some("anna@example.com"), None, Some("Vegetarian")),
+            ]
+        );
+
+        local_timeslots.cancel_booking(timeslot_id).unwrap();
+        assert!(local_timeslots.timeslots()[0].bookings.is_empty());
+    }
+
+    #[test]
+    fn test_edit_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        let notes = String::from("First Timeslot");
+        local_timeslots
+            .add_timeslot(datetime, notes.clone(), 1, 30, None, None)
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+        local_timeslots
+            .book_timeslot(timeslot_id, String::from("Stefan"), None, None, None)
+            .unwrap();
+
+        let new_datetime = Utc::now() + Duration::hours(2);
+        let new_notes = String::from("Edited Timeslot");
+        local_timeslots
+            .edit_timeslot(timeslot_id, new_datetime, new_notes.clone(), None, 0)
+            .unwrap();
+
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].id, timeslot_id);
+        assert_eq!(timeslots[0].datetime, new_datetime);
+        assert_eq!(timeslots[0].notes, new_notes);
+        assert_eq!(timeslots[0].booker_name, "Stefan");
+        assert_eq!(timeslots[0].version, 1);
+
+        // An edit based on the previous version is rejected
+        assert_eq!(
+            local_timeslots
+                .edit_timeslot(timeslot_id, datetime, "Stale Edit".into(), None, 0)
+                .unwrap_err(),
+            BackendError::Conflict(VERSION_CONFLICT.into())
+        );
+        assert_eq!(local_timeslots.timeslots()[0].notes, new_notes);
+
+        local_timeslots
+            .edit_timeslot(Uuid::new_v4(), new_datetime, new_notes, None, 1)
+            .unwrap_err(); // try to edit not existing timeslot
+    }
+
+    #[test]
+    fn test_swap_bookings() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        for hours in [1, 2] {
+            local_timeslots
+                .add_timeslot(
+                    Utc::now() + Duration::hours(hours),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let timeslots = local_timeslots.timeslots();
+        let (first_id, second_id) = (timeslots[0].id, timeslots[1].id);
+        let bookings = local_timeslots
+            .book_timeslot(first_id, "Stefan".into(), None, None, None)
+            .unwrap()
+            .bookings;
+
+        local_timeslots.swap_bookings(first_id, second_id).unwrap();
+
+        let timeslots = local_timeslots.timeslots();
+        assert!(timeslots[0].available);
+        assert_eq!(timeslots[0].booker_name, "");
+        assert!(timeslots[0].bookings.is_empty());
+        assert!(!timeslots[1].available);
+        assert_eq!(timeslots[1].booker_name, "Stefan");
+        assert_eq!(timeslots[1].bookings, bookings);
+
+        assert!(matches!(
+            local_timeslots.swap_bookings(first_id, Uuid::new_v4()),
+            Err(BackendError::NotFound(_))
+        ));
+        assert_eq!(
+            local_timeslots
+                .swap_bookings(first_id, first_id)
+                .unwrap_err(),
+            BackendError::Conflict(SWAP_WITH_ITSELF.into())
+        );
+        assert_eq!(local_timeslots.timeslots(), timeslots);
+
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(3),
+                "Group".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let group_id = local_timeslots.timeslots()[2].id;
+        for client_name in ["Anna", "Maria"] {
+            local_timeslots
+                .book_timeslot(group_id, client_name.into(), None, None, None)
+                .unwrap();
+        }
+        assert!(matches!(
+            local_timeslots.swap_bookings(second_id, group_id),
+            Err(BackendError::Conflict(_))
+        ));
+        assert_eq!(local_timeslots.timeslots()[1], timeslots[1]);
+    }
+
+    #[test]
+    fn test_update_notes() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+
+        local_timeslots
+            .update_notes(timeslot_id, "Updated Notes".into())
+            .unwrap();
+
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots[0].notes, "Updated Notes");
+        assert_eq!(timeslots[0].datetime, datetime);
+        assert_eq!(timeslots[0].duration_minutes, 30);
+        assert_eq!(timeslots[0].version, 1);
+
+        assert!(matches!(
+            local_timeslots.update_notes(Uuid::new_v4(), "Updated Notes".into()),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_internal_notes() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(
+                datetime,
+                "Workshop".into(),
+                1,
+                30,
+                Some("Bring the projector".into()),
+                None,
+            )
+            .unwrap();
+        let timeslot = local_timeslots.timeslots()[0].clone();
+        assert_eq!(
+            timeslot.internal_notes.as_deref(),
+            Some("Bring the projector")
+        );
+        assert_eq!(timeslot.clone().into_public().internal_notes, None);
+
+        local_timeslots
+            .edit_timeslot(timeslot.id, datetime, "Workshop".into(), None, 0)
+            .unwrap();
+        assert_eq!(local_timeslots.timeslots()[0].internal_notes, None);
+    }
+
+    #[test]
+    fn test_book_first_available() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), false, None, false);
+
+        let start = Utc::now() + Duration::hours(1);
+        for hours in 0..4 {
+            local_timeslots
+                .add_timeslot(
+                    start + Duration::hours(hours),
+                    format!("Timeslot {hours}"),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let removed = local_timeslots.current_timeslots().unwrap()[1].id;
+        local_timeslots.remove_timeslot(removed).unwrap();
+
+        // The earliest available timeslot of the window is booked, removed ones are skipped
+        for expected_notes in ["Timeslot 0", "Timeslot 2"] {
+            let booked_timeslot = local_timeslots
+                .book_first_available(start, start + Duration::hours(2), "Stefan".into())
+                .unwrap();
+            assert_eq!(booked_timeslot.notes, expected_notes);
+            assert_eq!(booked_timeslot.booker_name, "Stefan");
+            assert_eq!(booked_timeslot.bookings.len(), 1);
+        }
+        assert!(matches!(
+            local_timeslots.book_first_available(
+                start,
+                start + Duration::hours(2),
+                "Stefan".into()
+            ),
+            Err(BackendError::NotFound(_))
+        ));
+        assert!(matches!(
+            local_timeslots.book_first_available(
+                start + Duration::hours(5),
+                start + Duration::hours(6),
+                "Stefan".into()
+            ),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_timeslots_between() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), false, None, false);
+
+        let start = Utc::now() + Duration::hours(1);
+        for hours in 0..4 {
+            local_timeslots
+                .add_timeslot(
+                    start + Duration::hours(hours),
+                    format!("Timeslot {hours}"),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let removed = local_timeslots.current_timeslots().unwrap()[2].id;
+        local_timeslots.remove_timeslot(removed).unwrap();
+
+        let timeslots_in_range = local_timeslots
+            .timeslots_between(start + Duration::hours(1), start + Duration::hours(3))
+            .unwrap();
+        let timeslot_notes: Vec<&str> = timeslots_in_range
+            .iter()
+            .map(|timeslot| timeslot.notes.as_str())
+            .collect();
+        assert_eq!(timeslot_notes, ["Timeslot 1", "Timeslot 3"]);
+        assert!(local_timeslots
+            .timeslots_between(start + Duration::hours(5), start + Duration::hours(6))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_count_bookings() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), false, None, false);
+
+        let start = Utc::now() + Duration::hours(1);
+        for hours in 0..4 {
+            local_timeslots
+                .add_timeslot(
+                    start + Duration::hours(hours),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let current_timeslots = local_timeslots.current_timeslots().unwrap();
+        for (timeslot, client_name) in
+            current_timeslots
+                .iter()
+                .zip(["Stefan", " stefan ", "Peter", "Smith, Anna"])
+        {
+            local_timeslots
+                .book_timeslot(timeslot.id, client_name.into(), None, None, None)
+                .unwrap();
+        }
+
+        assert_eq!(local_timeslots.count_bookings("STEFAN").unwrap(), 2);
+        assert_eq!(local_timeslots.count_bookings("Peter").unwrap(), 1);
+        assert_eq!(local_timeslots.count_bookings("Anna").unwrap(), 0);
+        assert_eq!(local_timeslots.count_bookings("smith, anna").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_count_timeslots() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let start = Utc::now() + Duration::hours(1);
+        for timeslot_notes in ["First timeslot", "Second timeslot", "Third timeslot"] {
+            local_timeslots
+                .add_timeslot(start, timeslot_notes.into(), 1, 30, None, None)
+                .unwrap();
+        }
+        local_timeslots
+            .add_timeslot(
+                Utc::now() - Duration::days(2),
+                "Outdated timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(local_timeslots.remove_outdated_timeslots().unwrap(), 1);
+        let current_timeslots = local_timeslots.current_timeslots().unwrap();
+        local_timeslots
+            .book_timeslot(current_timeslots[0].id, "Stefan".into(), None, None, None)
+            .unwrap();
+        local_timeslots
+            .remove_timeslot(current_timeslots[1].id)
+            .unwrap();
+
+        assert_eq!(local_timeslots.count_timeslots(false).unwrap(), 2);
+        assert_eq!(local_timeslots.count_timeslots(true).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_reschedule() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), false, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(
+                datetime + Duration::hours(1),
+                "Second Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+
+        let err = local_timeslots
+            .reschedule(timeslot_id, datetime + Duration::hours(2))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BackendError::Conflict(
+                "Timeslot is not booked and can't therefore not be rescheduled".into()
+            )
+        );
+
+        local_timeslots
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        // Overlapping with itself is fine, but not with the second timeslot
+        local_timeslots
+            .reschedule(timeslot_id, datetime + Duration::minutes(15))
+            .unwrap();
+        local_timeslots
+            .reschedule(timeslot_id, datetime + Duration::minutes(75))
+            .unwrap_err();
+
+        let new_datetime = datetime + Duration::hours(2);
+        local_timeslots
+            .reschedule(timeslot_id, new_datetime)
+            .unwrap();
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots[1].id, timeslot_id);
+        assert_eq!(timeslots[1].datetime, new_datetime);
+        assert_eq!(timeslots[1].booker_name, "Stefan");
+        assert!(!timeslots[1].available);
+
+        local_timeslots
+            .reschedule(Uuid::new_v4(), new_datetime)
+            .unwrap_err(); // try to reschedule not existing timeslot
+    }
+
+    #[test]
+    fn test_try_book_outdated_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() - Duration::hours(2);
+        let notes = String::from("First Timeslot");
+        local_timeslots
+            .add_timeslot(datetime, notes.clone(), 1, 30, None, None)
+            .unwrap();
+
+        let timeslots = local_timeslots.timeslots();
+        let timeslot_id = timeslots[0].id;
+        assert_eq!(timeslots.len(), 1);
+        assert!(timeslots[0].available);
+
+        let booker_name = String::from("Stefan");
+        local_timeslots
+            .book_timeslot(timeslot_id, booker_name.clone(), None, None, None)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_remove_multiple_timeslots() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime_1 = Utc::now();
+        let notes_1 = String::from("First Timeslot");
+        let datetime_2 = Utc::now();
+        let notes_2 = String::from("Seconds Timeslot");
+        let datetime_3 = Utc::now();
+        let notes_3 = String::from("Third Timeslot");
+
+        local_timeslots
+            .add_timeslot(datetime_1, notes_1.clone(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(datetime_2, notes_2.clone(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(datetime_3, notes_3.clone(), 1, 30, None, None)
+            .unwrap();
+
+        local_timeslots.remove_timeslot(Uuid::new_v4()).unwrap_err(); // try to delete not existing timeslot
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 3);
+
+        local_timeslots.remove_timeslot(timeslots[0].id).unwrap();
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 2);
+
+        local_timeslots.remove_all_timeslot().unwrap();
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 0);
+    }
+
+    #[test]
+    fn cleanup_outdated_timeslots() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime_1 = Utc::now();
+        let notes_1 = String::from("First Timeslot");
+        let datetime_2 = Utc::now() - Duration::hours(2);
+        let notes_2 = String::from("Seconds Timeslot");
+        let datetime_3 = Utc::now() - Duration::days(2);
+        let notes_3 = String::from("Third Timeslot");
+
+        local_timeslots
+            .add_timeslot(datetime_1, notes_1.clone(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(datetime_2, notes_2.clone(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(datetime_3, notes_3.clone(), 1, 30, None, None)
+            .unwrap();
+
+        assert_eq!(local_timeslots.remove_outdated_timeslots().unwrap(), 1);
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 2);
+        assert_eq!(timeslots[0].notes, "Seconds Timeslot");
+        assert_eq!(timeslots[1].notes, "First Timeslot");
+    }
+
+    #[test]
+    fn cleanup_outdated_timeslots_with_configured_retention() {
+        let local_timeslots = LocalTimeslots::new(Duration::hours(168), true, None, false);
+
+        let datetime_1 = Utc::now() - Duration::days(2);
+        let notes_1 = String::from("First Timeslot");
+        let datetime_2 = Utc::now() - Duration::days(8);
+        let notes_2 = String::from("Seconds Timeslot");
+
+        local_timeslots
+            .add_timeslot(datetime_1, notes_1.clone(), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(datetime_2, notes_2.clone(), 1, 30, None, None)
+            .unwrap();
+
+        assert_eq!(local_timeslots.remove_outdated_timeslots().unwrap(), 1);
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].notes, "First Timeslot");
+    }
+
+    #[test]
+    fn cleanup_outdated_timeslots_by_end_time() {
+        let local_timeslots = LocalTimeslots::new(Duration::zero(), true, None, false);
+
+        let datetime = Utc::now() - Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, String::from("Long Timeslot"), 1, 180, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(datetime, String::from("Short Timeslot"), 1, 30, None, None)
+            .unwrap();
+
+        assert_eq!(local_timeslots.remove_outdated_timeslots().unwrap(), 1);
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 1);
+        assert_eq!(timeslots[0].notes, "Long Timeslot");
+        assert_eq!(timeslots[0].duration_minutes, 180);
+    }
+
+    #[test]
+    fn test_reject_overlapping_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), false, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, String::from("First Timeslot"), 1, 60, None, None)
+            .unwrap();
+        let err = local_timeslots
+            .add_timeslot(
+                datetime + Duration::minutes(30),
+                String::from("Overlapping Timeslot"),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict("Overlapping timeslot".into()));
+        local_timeslots
+            .add_timeslot(
+                datetime + Duration::minutes(60),
+                String::from("Adjacent Timeslot"),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(local_timeslots.timeslots().len(), 2);
+
+        // Edits are checked as well, though not against the edited timeslot itself
+        let timeslots = local_timeslots.timeslots();
+        let err = local_timeslots
+            .edit_timeslot(
+                timeslots[1].id,
+                datetime + Duration::minutes(30),
+                "Adjacent Timeslot".into(),
+                None,
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict("Overlapping timeslot".into()));
+        local_timeslots
+            .edit_timeslot(
+                timeslots[0].id,
+                datetime - Duration::minutes(30),
+                "First Timeslot".into(),
+                None,
+                0,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_allow_overlapping_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, String::from("First Timeslot"), 1, 60, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(
+                datetime,
+                String::from("Parallel Timeslot"),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(local_timeslots.timeslots().len(), 2);
+    }
+
+    #[test]
+    fn test_restore_last() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        assert_eq!(
+            local_timeslots.restore_last().unwrap_err(),
+            BackendError::Conflict(NOTHING_TO_RESTORE.into())
+        );
+
+        let datetime = Utc::now() + Duration::hours(1);
+        local_timeslots
+            .add_timeslot(datetime, String::from("First Timeslot"), 1, 30, None, None)
+            .unwrap();
+        local_timeslots
+            .add_timeslot(
+                datetime + Duration::hours(1),
+                String::from("Second Timeslot"),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let id = local_timeslots.timeslots()[0].id;
+        local_timeslots
+            .book_timeslot(id, String::from("Stefan"), None, None, None)
+            .unwrap();
+        let timeslots = local_timeslots.timeslots();
+
+        local_timeslots.remove_all_timeslot().unwrap();
+        // Clearing an empty schedule keeps the previous removal
+        local_timeslots.remove_all_timeslot().unwrap();
+        assert!(local_timeslots.timeslots().is_empty());
+
+        local_timeslots.restore_last().unwrap();
+        assert_eq!(local_timeslots.timeslots(), timeslots);
+        assert_eq!(
+            local_timeslots.restore_last().unwrap_err(),
+            BackendError::Conflict(NOTHING_TO_RESTORE.into())
+        );
+    }
+
+    #[test]
+    fn test_timeslot_limit() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, Some(2), false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        for index in 0..2 {
+            local_timeslots
+                .add_timeslot(datetime, format!("Timeslot {index}"), 1, 60, None, None)
+                .unwrap();
+        }
+        let err = local_timeslots
+            .add_timeslot(
+                datetime,
+                String::from("Exceeding Timeslot"),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict(TIMESLOT_LIMIT_REACHED.into()));
+
+        let id = local_timeslots.timeslots()[0].id;
+        local_timeslots.remove_timeslot(id).unwrap();
+        local_timeslots
+            .add_timeslot(
+                datetime,
+                String::from("Replacing Timeslot"),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(local_timeslots.timeslots().len(), 2);
+    }
+
+    #[test]
+    fn test_add_timeslots_bulk() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let datetime = Utc::now() + Duration::hours(1);
+        let drafts = (0..3)
+            .map(|index| TimeslotDraft {
+                datetime: datetime + Duration::hours(index),
+                notes: format!("Timeslot {index}"),
+                capacity: 1,
+                duration_minutes: 60,
+                internal_notes: None,
+                category: None,
+            })
+            .collect();
+        assert_eq!(local_timeslots.add_timeslots_bulk(drafts).unwrap(), 3);
+        assert_eq!(local_timeslots.timeslots().len(), 3);
+    }
+
+    #[test]
+    fn test_seed_examples() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), false, None, false);
+        local_timeslots.seed_examples(10).unwrap();
+
+        let timeslots = local_timeslots.timeslots();
+        assert_eq!(timeslots.len(), 10);
+        assert!(timeslots
+            .iter()
+            .all(|timeslot| timeslot.datetime > Utc::now() && timeslot.available));
+        assert_eq!(timeslots[0].notes, "Example Timeslot 1");
+    }
+
+    #[test]
+    fn test_find_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+
+        let timeslot = local_timeslots.find_timeslot(timeslot_id).unwrap().unwrap();
+        assert_eq!(timeslot.notes, "Timeslot");
+        assert_eq!(local_timeslots.find_timeslot(Uuid::new_v4()).unwrap(), None);
+
+        local_timeslots.remove_timeslot(timeslot_id).unwrap();
+        assert_eq!(local_timeslots.find_timeslot(timeslot_id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_health_check() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        local_timeslots.health_check().unwrap();
+    }
+
+    #[test]
+    fn test_missing_timeslot() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+
+        let timeslot_id = Uuid::new_v4();
+        assert!(matches!(
+            local_timeslots.book_timeslot(timeslot_id, "Stefan".into(), None, None, None),
+            Err(BackendError::NotFound(_))
+        ));
+        assert!(matches!(
+            local_timeslots.cancel_booking(timeslot_id),
+            Err(BackendError::NotFound(_))
+        ));
+        assert!(matches!(
+            local_timeslots.remove_timeslot(timeslot_id),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_poisoned_lock() {
+        let local_timeslots = LocalTimeslots::new(Duration::days(1), true, None, false);
+        let timeslots = local_timeslots.timeslots.clone();
+        std::thread::spawn(move || {
+            let _guard = timeslots.lock().unwrap();
+            panic!("Panic while holding the lock");
+        })
+        .join()
+        .unwrap_err();
+        assert!(local_timeslots.timeslots.is_poisoned());
+
+        local_timeslots
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(!local_timeslots.timeslots.is_poisoned());
+        let timeslot_id = local_timeslots.timeslots()[0].id;
+        local_timeslots
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        assert_eq!(local_timeslots.timeslots()[0].booker_name, "Stefan");
     }
 }
@@ -1,31 +1,53 @@
-#[macro_use]
-extern crate diesel;
-use std::time::Duration;
+use axum::Router;
+use chrono::Duration;
+use std::net::SocketAddr;
 
-use crate::{
-    configuration::Configuration, configuration_handler::ConfigurationHandler,
-    database_interface::DatabaseInterface, http::create_app, local_timeslots::LocalTimeslots,
+use booking_manager::{
+    cleanup::remove_outdated_timeslots,
+    configuration::{socket_address, Configuration, LogFormat, FILE_URL_PREFIX, SQLITE_URL_PREFIX},
+    configuration_handler::ConfigurationHandler,
+    create_app,
+    database_interface::{DatabaseInitError, DatabaseInterface},
+    file_timeslots::FileTimeslots,
+    hold::{release_expired_holds, HOLD_CHECK_INTERVAL},
+    local_timeslots::LocalTimeslots,
+    monitoring::prometheus_handle,
+    shutdown::{termination_signal, ShutdownSignal},
+    sqlite_interface::SqliteInterface,
+    tls::{load_tls_config, serve_tls},
+    TimeslotBackend,
 };
 use tokio::time::sleep;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::EnvFilter;
 
-mod backend;
-mod configuration;
-mod configuration_handler;
-mod database_interface;
-mod http;
-mod local_timeslots;
-mod schema;
-#[cfg(test)]
-mod testutils;
-mod types;
+/// Creates the app and starts the background tasks working on its backend.
+fn start_app<T: TimeslotBackend>(
+    backend: T,
+    configuration: ConfigurationHandler,
+    shutdown: ShutdownSignal,
+) -> Router {
+    tokio::spawn(remove_outdated_timeslots(
+        backend.clone(),
+        std::time::Duration::from_secs(configuration.cleanup_interval_seconds()),
+    ));
+    if let Some(booking_hold_minutes) = configuration.booking_hold_minutes() {
+        tokio::spawn(release_expired_holds(
+            backend.clone(),
+            Duration::minutes(booking_hold_minutes.into()),
+            HOLD_CHECK_INTERVAL,
+        ));
+    }
+    create_app(backend, configuration, shutdown)
+}
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .init();
+    let subscriber = tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env());
+    match ConfigurationHandler::log_format() {
+        LogFormat::Pretty => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
 
     println!("###################");
     println!("# Booking Manager #");
@@ -33,28 +55,126 @@ async fn main() {
 
     let configuration = ConfigurationHandler::parse_arguments();
 
-    let address = format!("0.0.0.0:{}", configuration.port());
-    println!("Accessable at:\n{}", address.clone());
+    let address = socket_address(&configuration.bind_address(), &configuration.port())
+        .unwrap_or_else(|err| panic!("{err}"));
+    println!("Accessable at:\n{address}");
     let listener = tokio::net::TcpListener::bind(address).await.unwrap();
 
-    let app = if let Some(database_url) = configuration.database_url() {
+    // Loaded before connecting to the database, so an invalid certificate fails fast
+    let tls_config = match (configuration.tls_cert_path(), configuration.tls_key_path()) {
+        (Some(cert_path), Some(key_path)) => Some(
+            load_tls_config(&cert_path, &key_path)
+                .await
+                .unwrap_or_else(|err| panic!("{err}")),
+        ),
+        _ => None,
+    };
+
+    let cleanup_retention = Duration::hours(configuration.cleanup_retention_hours());
+    // Real data must never be mixed with examples
+    if configuration.seed_examples() > 0 && configuration.database_url().is_some() {
+        warn!("Example timeslots are only added without a database. Ignoring --seed-examples");
+    }
+    let shutdown = ShutdownSignal::new();
+    let app = if let Some(database_path) = configuration
+        .database_url()
+        .as_deref()
+        .and_then(|database_url| database_url.strip_prefix(SQLITE_URL_PREFIX))
+    {
+        let backend = SqliteInterface::new(
+            database_path,
+            cleanup_retention,
+            configuration.allow_overlap(),
+            configuration.max_timeslots(),
+            configuration.hard_delete(),
+        )
+        .expect("Failed to open SQLite database");
+        info!("Successfully opened SQLite database");
+        start_app(backend, configuration, shutdown.clone())
+    } else if let Some(file_path) = configuration
+        .database_url()
+        .as_deref()
+        .and_then(|database_url| database_url.strip_prefix(FILE_URL_PREFIX))
+    {
+        let backend = FileTimeslots::new(
+            file_path,
+            cleanup_retention,
+            configuration.allow_overlap(),
+            configuration.max_timeslots(),
+            configuration.hard_delete(),
+        );
+        info!("Successfully loaded timeslot file");
+        start_app(backend, configuration, shutdown.clone())
+    } else if let Some(database_url) = configuration.database_url() {
         let backend = loop {
-            match DatabaseInterface::new(&database_url) {
+            match DatabaseInterface::new(
+                &database_url,
+                cleanup_retention,
+                configuration.database_pool_size(),
+                !configuration.skip_migrations(),
+                configuration.allow_overlap(),
+                configuration.max_timeslots(),
+                configuration.hard_delete(),
+            ) {
                 Ok(backend) => {
                     info!("Successfully connected to database");
                     break backend;
                 }
-                Err(err) => {
+                Err(DatabaseInitError::Migration(err)) => panic!("{err}"),
+                Err(DatabaseInitError::Connection(err)) => {
                     error!(?err, "Failed to establish database connection: {database_url}. Retry in 1 sec. You may want to restart it with database disabled (impersistent timeslots).");
-                    sleep(Duration::from_secs(1)).await;
+                    sleep(std::time::Duration::from_secs(1)).await;
                 }
             }
         };
-        create_app(backend, configuration)
+        start_app(backend, configuration, shutdown.clone())
     } else {
-        let backend = LocalTimeslots::default();
-        create_app(backend, configuration)
+        let backend = LocalTimeslots::new(
+            cleanup_retention,
+            configuration.allow_overlap(),
+            configuration.max_timeslots(),
+            configuration.hard_delete(),
+        );
+        if configuration.seed_examples() > 0 {
+            backend
+                .seed_examples(configuration.seed_examples())
+                .unwrap_or_else(|err| panic!("Failed to add example timeslots: {err}"));
+        }
+        start_app(backend, configuration, shutdown.clone())
     };
 
-    axum::serve(listener, app).await.unwrap();
+    // Histogram samples are buffered until the next upkeep, which must run even without scrapes
+    let metrics = prometheus_handle();
+    tokio::spawn(async move {
+        loop {
+            sleep(std::time::Duration::from_secs(5)).await;
+            metrics.run_upkeep();
+        }
+    });
+
+    // In-flight requests are completed before the server stops. Backends publish their changes
+    // synchronously within the request, so no timeslot update gets lost.
+    let graceful_shutdown = async move {
+        termination_signal().await;
+        shutdown.trigger();
+    };
+    if let Some(tls_config) = tls_config {
+        serve_tls(
+            listener.into_std().unwrap(),
+            app,
+            tls_config,
+            graceful_shutdown,
+        )
+        .await
+        .unwrap();
+    } else {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(graceful_shutdown)
+        .await
+        .unwrap();
+    }
+    info!("Shutdown complete");
 }
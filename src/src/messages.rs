@@ -0,0 +1,94 @@
+/// Language of the messages sent to clients, chosen via the `Accept-Language` header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    /// The first supported language of an `Accept-Language` header like "de-AT,de;q=0.9,en;q=0.8".
+    /// Falls back to English if the header is missing or names no supported language.
+    pub fn from_accept_language(accept_language: Option<&str>) -> Self {
+        let mut languages: Vec<(f32, &str)> = accept_language
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|language| {
+                let mut parts = language.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|parameter| parameter.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |quality| quality.trim().parse().ok())?;
+                Some((quality, tag))
+            })
+            .filter(|(quality, _)| *quality > 0.0)
+            .collect();
+        // Stable, so languages of the same quality keep their order
+        languages.sort_by(|(first, _), (second, _)| second.total_cmp(first));
+
+        languages
+            .into_iter()
+            .find_map(|(_, tag)| {
+                let primary = tag.split('-').next().unwrap_or_default();
+                match primary.to_ascii_lowercase().as_str() {
+                    "en" => Some(Self::English),
+                    "de" => Some(Self::German),
+                    _ => None,
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn timeslot_booked(self) -> &'static str {
+        match self {
+            Self::English => "Timeslot booked successfully",
+            Self::German => "Termin erfolgreich gebucht",
+        }
+    }
+
+    pub fn invalid_input(self) -> &'static str {
+        match self {
+            Self::English => "Invalid input",
+            Self::German => "Ungültige Eingabe",
+        }
+    }
+
+    pub fn booking_limit_reached(self, max_bookings: u32) -> String {
+        match self {
+            Self::English => format!("At most {max_bookings} bookings per client are allowed"),
+            Self::German => format!("Pro Person sind höchstens {max_bookings} Buchungen erlaubt"),
+        }
+    }
+
+    pub fn booking_closed(self, lead_minutes: i64) -> String {
+        match self {
+            Self::English => {
+                format!(
+                    "Timeslots can only be booked until {lead_minutes} minutes before they start"
+                )
+            }
+            Self::German => {
+                format!("Termine können nur bis {lead_minutes} Minuten vor Beginn gebucht werden")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(None, Language::English ; "missing header")]
+    #[test_case(Some("de"), Language::German ; "german")]
+    #[test_case(Some("de-AT"), Language::German ; "german region")]
+    #[test_case(Some("en-US,en;q=0.9"), Language::English ; "english")]
+    #[test_case(Some("fr,de;q=0.8,en;q=0.5"), Language::German ; "first supported")]
+    #[test_case(Some("en;q=0.5,de;q=0.8"), Language::German ; "highest quality")]
+    #[test_case(Some("de;q=0,en"), Language::English ; "excluded language")]
+    #[test_case(Some("fr"), Language::English ; "unsupported")]
+    #[test_case(Some("*"), Language::English ; "any language")]
+    fn test_from_accept_language(accept_language: Option<&str>, expected: Language) {
+        assert_eq!(Language::from_accept_language(accept_language), expected);
+    }
+}
@@ -0,0 +1,50 @@
+use metrics::{counter, describe_counter, describe_histogram, Unit};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::sync::OnceLock;
+
+pub const BOOKINGS_TOTAL: &str = "bookings_total";
+pub const TIMESLOTS_ADDED_TOTAL: &str = "timeslots_added_total";
+pub const AUTH_FAILURES_TOTAL: &str = "auth_failures_total";
+pub const REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+
+const REQUEST_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Returns the handle of the Prometheus recorder. The recorder is global, so it is installed on
+/// the first call and shared afterwards, e.g. by all apps created within the tests.
+pub fn prometheus_handle() -> PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+    HANDLE
+        .get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full(REQUEST_DURATION_SECONDS.into()),
+                    REQUEST_DURATION_BUCKETS,
+                )
+                .expect("Request duration buckets must not be empty")
+                .install_recorder()
+                .expect("Failed to install Prometheus recorder");
+            describe_metrics();
+            handle
+        })
+        .clone()
+}
+
+fn describe_metrics() {
+    describe_counter!(BOOKINGS_TOTAL, "Number of successful bookings");
+    describe_counter!(TIMESLOTS_ADDED_TOTAL, "Number of added timeslots");
+    describe_counter!(
+        AUTH_FAILURES_TOTAL,
+        "Number of failed admin authentications"
+    );
+    describe_histogram!(
+        REQUEST_DURATION_SECONDS,
+        Unit::Seconds,
+        "Time until the response of an HTTP request is ready"
+    );
+    // Show the counters before the first event happened
+    for name in [BOOKINGS_TOTAL, TIMESLOTS_ADDED_TOTAL, AUTH_FAILURES_TOTAL] {
+        counter!(name).increment(0);
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+// Forget clients which haven't sent a request within the window once this many are tracked
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Sliding window rate limiter counting the requests of each client IP within the last minute.
+#[derive(Clone)]
+pub struct RateLimiter {
+    max_requests_per_minute: u32,
+    requests: Arc<Mutex<HashMap<IpAddr, VecDeque<Instant>>>>,
+}
+
+impl RateLimiter {
+    /// A limit of 0 disables rate limiting.
+    pub fn new(max_requests_per_minute: u32) -> Self {
+        Self {
+            max_requests_per_minute,
+            requests: Arc::default(),
+        }
+    }
+
+    /// Records a request of the client and returns whether it is within the limit.
+    pub fn check(&self, client_ip: IpAddr) -> bool {
+        if self.max_requests_per_minute == 0 {
+            return true;
+        }
+
+        let now = Instant::now();
+        let mut requests = self.requests.lock().unwrap();
+        if requests.len() >= MAX_TRACKED_CLIENTS {
+            requests.retain(|_, timestamps| {
+                timestamps
+                    .back()
+                    .is_some_and(|latest| now.duration_since(*latest) < WINDOW)
+            });
+        }
+
+        let timestamps = requests.entry(client_ip).or_default();
+        while timestamps
+            .front()
+            .is_some_and(|oldest| now.duration_since(*oldest) >= WINDOW)
+        {
+            timestamps.pop_front();
+        }
+        if timestamps.len() >= self.max_requests_per_minute as usize {
+            return false;
+        }
+        timestamps.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_rate_limit_per_client() {
+        let rate_limiter = RateLimiter::new(2);
+        let first_client = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+        let second_client = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+        assert!(rate_limiter.check(first_client));
+        assert!(rate_limiter.check(first_client));
+        assert!(!rate_limiter.check(first_client));
+        assert!(rate_limiter.check(second_client));
+    }
+
+    #[test]
+    fn test_disabled_rate_limit() {
+        let rate_limiter = RateLimiter::new(0);
+        let client = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        assert!((0..100).all(|_| rate_limiter.check(client)));
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interval {
+    Daily,
+    Weekly,
+}
+
+impl Interval {
+    fn step(self) -> Duration {
+        match self {
+            Interval::Daily => Duration::days(1),
+            Interval::Weekly => Duration::weeks(1),
+        }
+    }
+}
+
+/// Expands a recurrence into the datetimes of its occurrences. Stepping happens on the wall clock
+/// of the timezone, so "every Tuesday at 10:00" stays at 10:00 across DST transitions.
+/// Occurrences before `now` are skipped.
+pub fn expand(
+    start: DateTime<Utc>,
+    interval: Interval,
+    count: u32,
+    timezone: Tz,
+    now: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let local_start = start.with_timezone(&timezone).naive_local();
+    (0..count)
+        .map(|index| local_start + interval.step() * index as i32)
+        .map(|local| to_utc(local, timezone))
+        .filter(|datetime| *datetime >= now)
+        .collect()
+}
+
+fn to_utc(local: NaiveDateTime, timezone: Tz) -> DateTime<Utc> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(datetime) => datetime.with_timezone(&Utc),
+        // The clock was set back, use the first of both occurrences
+        LocalResult::Ambiguous(earliest, _) => earliest.with_timezone(&Utc),
+        // The clock jumped forward and skipped this time, use the time one hour later
+        LocalResult::None => to_utc(local + Duration::hours(1), timezone),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono_tz::Europe::Vienna;
+
+    #[test]
+    fn test_weekly_expansion() {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let datetimes = expand(start, Interval::Weekly, 8, Tz::UTC, start);
+
+        assert_eq!(datetimes.len(), 8);
+        for (index, datetime) in datetimes.iter().enumerate() {
+            assert_eq!(*datetime, start + Duration::weeks(index as i64));
+        }
+    }
+
+    #[test]
+    fn test_daily_expansion() {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let datetimes = expand(start, Interval::Daily, 3, Tz::UTC, start);
+
+        assert_eq!(
+            datetimes,
+            vec![start, start + Duration::days(1), start + Duration::days(2)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_expansion_across_dst() {
+        // Tuesday 10:00 in Vienna, DST starts on Sunday 2030-03-31
+        let start = Vienna
+            .with_ymd_and_hms(2030, 3, 19, 10, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let datetimes = expand(start, Interval::Weekly, 4, Vienna, start);
+
+        assert_eq!(datetimes.len(), 4);
+        for datetime in &datetimes {
+            assert_eq!(
+                datetime.with_timezone(&Vienna).naive_local().time(),
+                chrono::NaiveTime::from_hms_opt(10, 0, 0).unwrap()
+            );
+        }
+        assert_eq!(datetimes[1] - datetimes[0], Duration::weeks(1));
+        assert_eq!(
+            datetimes[2] - datetimes[1],
+            Duration::weeks(1) - Duration::hours(1)
+        );
+    }
+
+    #[test]
+    fn test_daily_expansion_into_dst_gap() {
+        // 02:30 doesn't exist in Vienna on 2030-03-31
+        let start = Vienna
+            .with_ymd_and_hms(2030, 3, 30, 2, 30, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let datetimes = expand(start, Interval::Daily, 3, Vienna, start);
+
+        let local_times: Vec<_> = datetimes
+            .iter()
+            .map(|datetime| datetime.with_timezone(&Vienna).naive_local().time())
+            .collect();
+        assert_eq!(
+            local_times,
+            vec![
+                chrono::NaiveTime::from_hms_opt(2, 30, 0).unwrap(),
+                chrono::NaiveTime::from_hms_opt(3, 30, 0).unwrap(),
+                chrono::NaiveTime::from_hms_opt(2, 30, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skip_past_occurrences() {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let now = start + Duration::weeks(2) + Duration::hours(1);
+        let datetimes = expand(start, Interval::Weekly, 8, Tz::UTC, now);
+
+        assert_eq!(datetimes.len(), 5);
+        assert_eq!(datetimes[0], start + Duration::weeks(3));
+    }
+}
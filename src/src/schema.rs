@@ -7,5 +7,14 @@ diesel::table! {
         available -> Bool,
         booker_name -> Varchar,
         notes -> Varchar,
+        capacity -> Int4,
+        booked_count -> Int4,
+        duration_minutes -> Int4,
+        archived -> Bool,
+        waitlist -> Array<Text>,
+        version -> Int4,
+        internal_notes -> Nullable<Varchar>,
+        category -> Nullable<Varchar>,
+        bookings -> Jsonb,
     }
 }
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use tokio::sync::watch::{self, Sender};
+use tracing::info;
+
+/// Notifies long-living connections (e.g. SSE streams) about a shutdown, so that a graceful
+/// shutdown doesn't wait for them forever.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    sender: Arc<Sender<bool>>,
+}
+
+impl ShutdownSignal {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Completes as soon as the shutdown is triggered.
+    pub async fn triggered(&self) {
+        let mut receiver = self.sender.subscribe();
+        // The sender lives as long as self, therefore waiting can't fail
+        let _ = receiver.wait_for(|triggered| *triggered).await;
+    }
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Completes on Ctrl-C or SIGTERM.
+pub async fn termination_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => info!("Received Ctrl-C"),
+        () = terminate => info!("Received SIGTERM"),
+    }
+    info!("Shutting down gracefully");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn test_shutdown_signal() {
+        let shutdown = ShutdownSignal::new();
+        let waiting = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.triggered().await }
+        });
+
+        timeout(Duration::from_millis(50), shutdown.triggered())
+            .await
+            .unwrap_err();
+        shutdown.trigger();
+        timeout(Duration::from_millis(100), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+        // Also completes when subscribing after the trigger
+        timeout(Duration::from_millis(100), shutdown.triggered())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,2559 @@
+use crate::sqlite_schema::timeslots::dsl::*;
+use crate::types::{generate_confirmation_code, Booking, Timeslot, TimeslotDraft, TimeslotEvent};
+use crate::{
+    backend::{
+        drafts_overlap, event_channel, publish_timeslots, within_timeslot_limit, BackendError,
+        TimeslotBackend, NOTHING_TO_RESTORE, NO_TIMESLOT_AVAILABLE, SWAP_WITH_ITSELF,
+        TIMESLOT_AVAILABLE, TIMESLOT_LIMIT_REACHED, VERSION_CONFLICT,
+    },
+    sqlite_schema::timeslots,
+};
+use chrono::{DateTime, Duration, Utc};
+use diesel::dsl::sql;
+use diesel::sql_types::Bool;
+use diesel::{
+    Connection, ConnectionError, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
+    SqliteConnection,
+};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{
+    broadcast,
+    watch::{self, Sender},
+};
+use tokio_stream::wrappers::{BroadcastStream, WatchStream};
+use tracing::error;
+use uuid::Uuid;
+
+const CREATE_TIMESLOTS_TABLE: &str = "CREATE TABLE IF NOT EXISTS timeslots (
+    id TEXT PRIMARY KEY NOT NULL,
+    datetime TEXT NOT NULL,
+    available BOOLEAN NOT NULL DEFAULT 1,
+    booker_name TEXT NOT NULL DEFAULT '',
+    notes TEXT NOT NULL,
+    capacity INTEGER NOT NULL DEFAULT 1 CHECK (capacity > 0),
+    booked_count INTEGER NOT NULL DEFAULT 0,
+    duration_minutes INTEGER NOT NULL DEFAULT 30 CHECK (duration_minutes > 0),
+    archived BOOLEAN NOT NULL DEFAULT 0,
+    waitlist TEXT NOT NULL DEFAULT '[]',
+    version INTEGER NOT NULL DEFAULT 0,
+    internal_notes TEXT,
+    category TEXT,
+    bookings TEXT NOT NULL DEFAULT '[]'
+)";
+
+// Columns added after the initial table layout, databases created before lack them
+const ADDED_COLUMNS: &[(&str, &str)] = &[
+    (
+        "duration_minutes",
+        "ALTER TABLE timeslots ADD COLUMN duration_minutes INTEGER NOT NULL DEFAULT 30 \
+            CHECK (duration_minutes > 0)",
+    ),
+    (
+        "archived",
+        "ALTER TABLE timeslots ADD COLUMN archived BOOLEAN NOT NULL DEFAULT 0",
+    ),
+    (
+        "waitlist",
+        "ALTER TABLE timeslots ADD COLUMN waitlist TEXT NOT NULL DEFAULT '[]'",
+    ),
+    (
+        "version",
+        "ALTER TABLE timeslots ADD COLUMN version INTEGER NOT NULL DEFAULT 0",
+    ),
+    (
+        "internal_notes",
+        "ALTER TABLE timeslots ADD COLUMN internal_notes TEXT",
+    ),
+    ("category", "ALTER TABLE timeslots ADD COLUMN category TEXT"),
+    (
+        "bookings",
+        "ALTER TABLE timeslots ADD COLUMN bookings TEXT NOT NULL DEFAULT '[]'",
+    ),
+];
+// Details of all bookers of a timeslot, before every booking was stored separately in `bookings`
+const LEGACY_BOOKING_COLUMNS: &[(&str, &str)] = &[
+    (
+        "confirmation_code",
+        "ALTER TABLE timeslots ADD COLUMN confirmation_code TEXT",
+    ),
+    (
+        "client_email",
+        "ALTER TABLE timeslots ADD COLUMN client_email TEXT",
+    ),
+    (
+        "client_phone",
+        "ALTER TABLE timeslots ADD COLUMN client_phone TEXT",
+    ),
+    (
+        "booker_notes",
+        "ALTER TABLE timeslots ADD COLUMN booker_notes TEXT",
+    ),
+];
+// Hold of all bookings of a timeslot, before every booking was held separately
+const LEGACY_HOLD_COLUMNS: &[(&str, &str)] = &[
+    (
+        "booked_at",
+        "ALTER TABLE timeslots ADD COLUMN booked_at TEXT",
+    ),
+    (
+        "confirmed",
+        "ALTER TABLE timeslots ADD COLUMN confirmed BOOLEAN NOT NULL DEFAULT 0",
+    ),
+];
+const SELECT_LEGACY_HOLDS: &str =
+    "SELECT id, booked_at, confirmed, bookings FROM timeslots WHERE bookings <> '[]'";
+const SELECT_LEGACY_BOOKINGS: &str = "SELECT id, booker_name, confirmation_code, client_email, \
+    client_phone, booker_notes FROM timeslots WHERE booked_count > 0 AND booker_name <> ''";
+const DROP_CONFIRMATION_CODE_INDEX: &str = "DROP INDEX IF EXISTS timeslots_confirmation_code_key";
+const COUNT_COLUMN: &str =
+    "SELECT COUNT(*) AS count FROM pragma_table_info('timeslots') WHERE name = ?";
+// Booker names of the bookings of all active timeslots
+const SELECT_BOOKER_NAMES: &str = "SELECT json_extract(json_each.value, '$.booker_name') AS name \
+    FROM timeslots, json_each(timeslots.bookings) WHERE NOT archived";
+// Matches timeslots with a booking of the bound confirmation code
+const HAS_CONFIRMATION_CODE: &str = "EXISTS (SELECT 1 FROM json_each(bookings) \
+    WHERE json_extract(json_each.value, '$.confirmation_code') = ";
+
+#[derive(QueryableByName)]
+struct ColumnCount {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct BookerName {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    name: String,
+}
+
+#[derive(QueryableByName)]
+struct LegacyBookings {
+    #[diesel(sql_type = diesel::sql_types::Text, column_name = id)]
+    timeslot_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text, column_name = booker_name)]
+    names: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    confirmation_code: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    client_email: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    client_phone: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    booker_notes: Option<String>,
+}
+
+#[derive(QueryableByName)]
+struct LegacyHold {
+    #[diesel(sql_type = diesel::sql_types::Text, column_name = id)]
+    timeslot_id: String,
+    #[diesel(
+        sql_type = diesel::sql_types::Nullable<diesel::sql_types::TimestamptzSqlite>,
+        column_name = booked_at
+    )]
+    held_since: Option<DateTime<Utc>>,
+    #[diesel(sql_type = Bool, column_name = confirmed)]
+    held_confirmed: bool,
+    #[diesel(sql_type = diesel::sql_types::Text, column_name = bookings)]
+    held_bookings: String,
+}
+
+impl LegacyBookings {
+    /// A booking per booker name. The details were joined with ", ", leaving out the bookers
+    /// without one, so they are only assigned by their position if every booker left one. The
+    /// first booker keeps the confirmation code of the timeslot, the others get new ones.
+    fn into_bookings(self) -> Vec<Booking> {
+        let names: Vec<&str> = self.names.split(", ").collect();
+        let detail = |details: &Option<String>, index: usize| {
+            let details: Vec<&str> = details.as_deref()?.split(", ").collect();
+            (details.len() == names.len() && !details[index].is_empty())
+                .then(|| details[index].to_string())
+        };
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| Booking {
+                booker_name: name.to_string(),
+                confirmation_code: self
+                    .confirmation_code
+                    .clone()
+                    .filter(|_| index == 0)
+                    .unwrap_or_else(generate_confirmation_code),
+                client_email: detail(&self.client_email, index),
+                client_phone: detail(&self.client_phone, index),
+                booker_notes: detail(&self.booker_notes, index),
+                // Taken over from the timeslot afterwards, see `hold_bookings_separately`
+                booked_at: None,
+                confirmed: false,
+            })
+            .collect()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = timeslots)]
+pub struct NewSqliteTimeslot {
+    pub id: String,
+    pub datetime: DateTime<Utc>,
+    pub notes: String,
+    pub capacity: i32,
+    pub duration_minutes: i32,
+    pub internal_notes: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Clone, Queryable, Insertable)]
+#[diesel(table_name = timeslots)]
+pub struct SqliteTimeslot {
+    pub id: String,
+    pub datetime: DateTime<Utc>,
+    pub available: bool,
+    pub booker_name: String,
+    pub notes: String,
+    pub capacity: i32,
+    pub booked_count: i32,
+    pub duration_minutes: i32,
+    pub archived: bool,
+    /// JSON array of the names
+    pub waitlist: String,
+    pub version: i32,
+    pub internal_notes: Option<String>,
+    pub category: Option<String>,
+    /// JSON array of the bookings
+    pub bookings: String,
+}
+
+impl TryFrom<SqliteTimeslot> for Timeslot {
+    type Error = BackendError;
+
+    fn try_from(timeslot: SqliteTimeslot) -> Result<Self, Self::Error> {
+        let timeslot_id = Uuid::parse_str(&timeslot.id).map_err(|err| {
+            error!(?err, "Invalid timeslot id in Database");
+            "Invalid timeslot id in Database".to_string()
+        })?;
+        let timeslot_waitlist = serde_json::from_str(&timeslot.waitlist).map_err(|err| {
+            error!(?err, "Invalid waitlist in Database");
+            "Invalid waitlist in Database".to_string()
+        })?;
+        let timeslot_bookings = serde_json::from_str(&timeslot.bookings).map_err(|err| {
+            error!(?err, "Invalid bookings in Database");
+            "Invalid bookings in Database".to_string()
+        })?;
+        Ok(Timeslot {
+            id: timeslot_id,
+            datetime: timeslot.datetime,
+            available: timeslot.available,
+            booker_name: timeslot.booker_name,
+            notes: timeslot.notes,
+            capacity: timeslot.capacity,
+            booked_count: timeslot.booked_count,
+            duration_minutes: timeslot.duration_minutes,
+            archived: timeslot.archived,
+            waitlist: timeslot_waitlist,
+            version: timeslot.version,
+            internal_notes: timeslot.internal_notes,
+            category: timeslot.category,
+            bookings: timeslot_bookings,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteInterface {
+    connection: Arc<Mutex<SqliteConnection>>,
+    // Timeslots removed by the last remove_all_timeslot
+    last_removed: Arc<Mutex<Option<Vec<SqliteTimeslot>>>>,
+    sender: Sender<Vec<Timeslot>>,
+    event_sender: broadcast::Sender<TimeslotEvent>,
+    cleanup_retention: Duration,
+    allow_overlap: bool,
+    max_timeslots: Option<usize>,
+    hard_delete: bool,
+}
+
+impl SqliteInterface {
+    pub fn new(
+        database_path: &str,
+        cleanup_retention: Duration,
+        allow_overlap: bool,
+        max_timeslots: Option<usize>,
+        hard_delete: bool,
+    ) -> Result<Self, ConnectionError> {
+        let mut connection = SqliteConnection::establish(database_path)?;
+        Self::create_schema(&mut connection)
+            .map_err(|err| ConnectionError::BadConnection(err.to_string()))?;
+
+        let (sender, _) = watch::channel(vec![]);
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            last_removed: Arc::default(),
+            sender,
+            event_sender: event_channel(),
+            cleanup_retention,
+            allow_overlap,
+            max_timeslots,
+            hard_delete,
+        })
+    }
+
+    fn create_schema(connection: &mut SqliteConnection) -> Result<(), diesel::result::Error> {
+        diesel::sql_query(CREATE_TIMESLOTS_TABLE).execute(connection)?;
+        let legacy_bookings = !Self::has_column(connection, "bookings")?;
+        let legacy_holds = Self::has_column(connection, "booked_at")?
+            || Self::has_column(connection, "confirmed")?;
+
+        Self::add_missing_columns(connection, ADDED_COLUMNS)?;
+        if legacy_bookings {
+            connection.transaction(Self::split_legacy_bookings)?;
+        }
+        if legacy_holds {
+            connection.transaction(Self::hold_bookings_separately)?;
+        }
+        Ok(())
+    }
+
+    /// The stored timeslot with a booking of the confirmation code.
+    fn booked_with(
+        connection: &mut SqliteConnection,
+        confirmation_code: &str,
+    ) -> Result<Option<SqliteTimeslot>, diesel::result::Error> {
+        timeslots
+            .filter(
+                sql::<Bool>(HAS_CONFIRMATION_CODE)
+                    .bind::<diesel::sql_types::Text, _>(confirmation_code)
+                    .sql(")"),
+            )
+            .filter(archived.eq(false))
+            .first::<SqliteTimeslot>(connection)
+            .optional()
+    }
+
+    fn has_column(
+        connection: &mut SqliteConnection,
+        column: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        let ColumnCount { count } = diesel::sql_query(COUNT_COLUMN)
+            .bind::<diesel::sql_types::Text, _>(column)
+            .get_result(connection)?;
+        Ok(count > 0)
+    }
+
+    fn add_missing_columns(
+        connection: &mut SqliteConnection,
+        columns: &[(&str, &str)],
+    ) -> Result<(), diesel::result::Error> {
+        for (column, add_column) in columns {
+            if !Self::has_column(connection, column)? {
+                diesel::sql_query(*add_column).execute(connection)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the details of the bookers into a booking each and drops the columns they were
+    /// joined in before.
+    fn split_legacy_bookings(
+        connection: &mut SqliteConnection,
+    ) -> Result<(), diesel::result::Error> {
+        // Databases created before some of the columns existed are read the same way
+        Self::add_missing_columns(connection, LEGACY_BOOKING_COLUMNS)?;
+        let booked_timeslots =
+            diesel::sql_query(SELECT_LEGACY_BOOKINGS).load::<LegacyBookings>(connection)?;
+        for timeslot in booked_timeslots {
+            diesel::update(timeslots.find(timeslot.timeslot_id.clone()))
+                .set(bookings.eq(serde_json::json!(timeslot.into_bookings()).to_string()))
+                .execute(connection)?;
+        }
+
+        diesel::sql_query(DROP_CONFIRMATION_CODE_INDEX).execute(connection)?;
+        for (column, _) in LEGACY_BOOKING_COLUMNS {
+            diesel::sql_query(format!("ALTER TABLE timeslots DROP COLUMN {column}"))
+                .execute(connection)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the hold of each timeslot into its bookings and drops the columns it was stored in
+    /// before.
+    fn hold_bookings_separately(
+        connection: &mut SqliteConnection,
+    ) -> Result<(), diesel::result::Error> {
+        Self::add_missing_columns(connection, LEGACY_HOLD_COLUMNS)?;
+        let held_timeslots =
+            diesel::sql_query(SELECT_LEGACY_HOLDS).load::<LegacyHold>(connection)?;
+        for timeslot in held_timeslots {
+            let Ok(mut held) = serde_json::from_str::<Vec<Booking>>(&timeslot.held_bookings) else {
+                error!(
+                    timeslot.timeslot_id,
+                    "Invalid bookings in Database. Hold is dropped"
+                );
+                continue;
+            };
+            for booking in &mut held {
+                booking.booked_at = timeslot.held_since;
+                booking.confirmed = timeslot.held_confirmed;
+            }
+            diesel::update(timeslots.find(timeslot.timeslot_id))
+                .set(bookings.eq(serde_json::json!(held).to_string()))
+                .execute(connection)?;
+        }
+
+        for (column, _) in LEGACY_HOLD_COLUMNS {
+            diesel::sql_query(format!("ALTER TABLE timeslots DROP COLUMN {column}"))
+                .execute(connection)?;
+        }
+        Ok(())
+    }
+
+    /// Removes timeslots which ended before the cutoff time. SQLite can't do date arithmetic
+    /// on the stored datetimes, therefore the end is calculated here.
+    fn cleanup_outdated_timeslots(
+        connection: &mut SqliteConnection,
+        cutoff_time: DateTime<Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        let started_before_cutoff = timeslots
+            .filter(datetime.lt(cutoff_time))
+            .select((id, datetime, duration_minutes))
+            .load::<(String, DateTime<Utc>, i32)>(connection)?;
+        let outdated_ids: Vec<String> = started_before_cutoff
+            .into_iter()
+            .filter(|(_, start, minutes)| {
+                *start + Duration::minutes((*minutes).into()) < cutoff_time
+            })
+            .map(|(timeslot_id, _, _)| timeslot_id)
+            .collect();
+
+        diesel::delete(timeslots.filter(id.eq_any(outdated_ids))).execute(connection)
+    }
+
+    fn timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.sorted_timeslots(false)
+    }
+
+    fn sorted_timeslots(&self, archived_timeslots: bool) -> Result<Vec<Timeslot>, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let result = timeslots
+            .filter(archived.eq(archived_timeslots))
+            .order(datetime.asc())
+            .load::<SqliteTimeslot>(&mut *connection);
+
+        match result {
+            Ok(current_timeslots) => current_timeslots
+                .into_iter()
+                .map(Timeslot::try_from)
+                .collect(),
+            Err(err) => {
+                error!(?err, "Failed to read timeslots from Database");
+                Err("Failed to read timeslots from Database".into())
+            }
+        }
+    }
+
+    fn timeslot(
+        connection: &mut SqliteConnection,
+        timeslot_id: Uuid,
+    ) -> Result<Timeslot, BackendError> {
+        let result = timeslots
+            .find(timeslot_id.to_string())
+            .filter(archived.eq(false))
+            .first::<SqliteTimeslot>(connection);
+
+        match result {
+            Ok(timeslot) => Timeslot::try_from(timeslot),
+            Err(diesel::result::Error::NotFound) => {
+                let err = "Timeslot does not exist";
+                error!(err);
+                Err(BackendError::NotFound(err.into()))
+            }
+            Err(err) => {
+                error!(?err, "Failed to read timeslot from Database");
+                Err("Failed to read timeslot from Database".into())
+            }
+        }
+    }
+
+    /// Writes the booking related fields of the timeslot, e.g. after its booking was cancelled.
+    fn update_booking(
+        connection: &mut SqliteConnection,
+        timeslot: Timeslot,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::update(timeslots.find(timeslot.id.to_string()))
+            .set((
+                available.eq(timeslot.available),
+                booked_count.eq(timeslot.booked_count),
+                booker_name.eq(timeslot.booker_name),
+                bookings.eq(serde_json::json!(timeslot.bookings).to_string()),
+                waitlist.eq(serde_json::json!(timeslot.waitlist).to_string()),
+            ))
+            .execute(connection)
+    }
+
+    fn send_timeslots(&self) {
+        let Ok(current_timeslots) = self.timeslots() else {
+            return;
+        };
+        publish_timeslots(&self.sender, &self.event_sender, current_timeslots);
+    }
+
+    /// Whether an active timeslot other than `ignored_id` (e.g. the one being moved) overlaps
+    /// with the window from start to end.
+    fn overlaps_existing_timeslot(
+        connection: &mut SqliteConnection,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ignored_id: Option<Uuid>,
+    ) -> Result<bool, diesel::result::Error> {
+        let ignored_id = ignored_id.map(|ignored_id| ignored_id.to_string());
+        let started_before_end = timeslots
+            .filter(datetime.lt(end))
+            .filter(archived.eq(false))
+            .select((id, datetime, duration_minutes))
+            .load::<(String, DateTime<Utc>, i32)>(connection)?;
+        Ok(started_before_end
+            .into_iter()
+            .filter(|(other_id, _, _)| ignored_id.as_ref() != Some(other_id))
+            .any(|(_, other_start, minutes)| {
+                start < other_start + Duration::minutes(minutes.into())
+            }))
+    }
+
+    fn try_add_timeslot(&self, timeslot: NewSqliteTimeslot) -> Result<(), BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+
+        if self.max_timeslots.is_some() {
+            match timeslots
+                .filter(archived.eq(false))
+                .count()
+                .get_result::<i64>(&mut *connection)
+            {
+                Ok(count) if within_timeslot_limit(self.max_timeslots, count as usize, 1) => {}
+                Ok(_) => {
+                    error!(TIMESLOT_LIMIT_REACHED);
+                    return Err(BackendError::Conflict(TIMESLOT_LIMIT_REACHED.into()));
+                }
+                Err(err) => {
+                    error!(?err, "Failed to count timeslots");
+                    return Err("Database Error. Timeslot can't be added".into());
+                }
+            }
+        }
+
+        if !self.allow_overlap {
+            let end = timeslot.datetime + Duration::minutes(timeslot.duration_minutes.into());
+            match Self::overlaps_existing_timeslot(&mut connection, timeslot.datetime, end, None) {
+                Ok(false) => {}
+                Ok(true) => {
+                    let err = "Overlapping timeslot";
+                    error!(err);
+                    return Err(BackendError::Conflict(err.into()));
+                }
+                Err(err) => {
+                    error!(?err, "Failed to check for overlapping timeslots");
+                    return Err("Database Error. Timeslot can't be added".into());
+                }
+            }
+        }
+
+        let result = diesel::insert_into(timeslots::table)
+            .values(&timeslot)
+            .execute(&mut *connection);
+
+        if let Err(err) = result {
+            error!(?err, "Timeslot can't be added");
+            return Err("Database Error. Timeslot can't be added".into());
+        }
+        Ok(())
+    }
+
+    /// Books a place of the timeslot. The caller holds the lock of the connection, so the check
+    /// for a free place and the booking can't interleave with another booking.
+    fn try_book_timeslot(
+        connection: &mut SqliteConnection,
+        timeslot_id: Uuid,
+        new_booker_name: String,
+        new_client_email: Option<String>,
+        new_client_phone: Option<String>,
+        new_booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError> {
+        let mut timeslot = Self::timeslot(connection, timeslot_id)?;
+
+        if !timeslot.available || timeslot.booked_count >= timeslot.capacity {
+            let err = "Timeslot is already fully booked";
+            error!(err);
+            return Err(BackendError::AlreadyBooked(err.into()));
+        }
+        if timeslot.datetime < Utc::now() {
+            let err = "Timeslot already passed";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        timeslot.add_booking(Booking::new(
+            new_booker_name,
+            new_client_email,
+            new_client_phone,
+            new_booker_notes,
+        ));
+        // RETURNING isn't available for SQLite, so the booked timeslot is read again while the
+        // connection is still locked
+        let result = Self::update_booking(connection, timeslot);
+
+        if let Err(err) = result {
+            error!(?err, "Timeslot can't be booked");
+            return Err("Database Error. Timeslot can't be booked".into());
+        }
+        Self::timeslot(connection, timeslot_id)
+    }
+}
+
+impl TimeslotBackend for SqliteInterface {
+    fn timeslot_stream(&self) -> WatchStream<Vec<Timeslot>> {
+        let stream = WatchStream::new(self.sender.subscribe());
+        self.send_timeslots();
+        stream
+    }
+
+    fn event_stream(&self) -> BroadcastStream<TimeslotEvent> {
+        // Catches up on the changes made before, so they aren't sent as events of this stream
+        self.send_timeslots();
+        BroadcastStream::new(self.event_sender.subscribe())
+    }
+
+    fn current_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.timeslots()
+    }
+
+    fn timeslots_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Timeslot>, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let result = timeslots
+            .filter(archived.eq(false))
+            .filter(datetime.ge(from))
+            .filter(datetime.le(to))
+            .order(datetime.asc())
+            .load::<SqliteTimeslot>(&mut *connection);
+
+        match result {
+            Ok(timeslots_in_range) => timeslots_in_range
+                .into_iter()
+                .map(Timeslot::try_from)
+                .collect(),
+            Err(err) => {
+                error!(?err, "Failed to read timeslots from Database");
+                Err("Failed to read timeslots from Database".into())
+            }
+        }
+    }
+
+    fn count_timeslots(&self, available_only: bool) -> Result<i64, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut current = timeslots.filter(archived.eq(false)).into_boxed();
+        if available_only {
+            current = current.filter(available.eq(true));
+        }
+        current.count().get_result(&mut *connection).map_err(|err| {
+            error!(?err, "Failed to count timeslots in Database");
+            "Failed to count timeslots in Database".into()
+        })
+    }
+
+    fn book_timeslot(
+        &self,
+        timeslot_id: Uuid,
+        new_booker_name: String,
+        new_client_email: Option<String>,
+        new_client_phone: Option<String>,
+        new_booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError> {
+        let booked_timeslot = Self::try_book_timeslot(
+            &mut self.connection.lock().unwrap(),
+            timeslot_id,
+            new_booker_name,
+            new_client_email,
+            new_client_phone,
+            new_booker_notes,
+        )?;
+        self.send_timeslots();
+        Ok(booked_timeslot)
+    }
+
+    fn book_first_available(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        new_booker_name: String,
+    ) -> Result<Timeslot, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let result = timeslots
+            .filter(archived.eq(false))
+            .filter(available.eq(true))
+            .filter(booked_count.lt(capacity))
+            .filter(datetime.ge(from.max(Utc::now())))
+            .filter(datetime.le(to))
+            .order(datetime.asc())
+            .select(id)
+            .first::<String>(&mut *connection)
+            .optional();
+
+        let timeslot_id = match result {
+            Ok(Some(timeslot_id)) => Uuid::parse_str(&timeslot_id).map_err(|err| {
+                error!(?err, "Invalid timeslot id in Database");
+                "Invalid timeslot id in Database".to_string()
+            })?,
+            Ok(None) => {
+                error!(%from, %to, NO_TIMESLOT_AVAILABLE);
+                return Err(BackendError::NotFound(NO_TIMESLOT_AVAILABLE.into()));
+            }
+            Err(err) => {
+                error!(?err, "Failed to find an available timeslot in Database");
+                return Err("Database Error. Timeslot can't be booked".into());
+            }
+        };
+        let booked_timeslot = Self::try_book_timeslot(
+            &mut connection,
+            timeslot_id,
+            new_booker_name,
+            None,
+            None,
+            None,
+        )?;
+        drop(connection);
+        self.send_timeslots();
+        Ok(booked_timeslot)
+    }
+
+    fn cancel_booking(&self, timeslot_id: Uuid) -> Result<Vec<String>, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut timeslot = Self::timeslot(&mut connection, timeslot_id)?;
+        if timeslot.booked_count == 0 {
+            let err = "Timeslot is not booked and can't therefore not be cancelled";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        let promoted_codes = timeslot.cancel_booking();
+        let result = Self::update_booking(&mut connection, timeslot);
+        drop(connection);
+
+        if let Err(err) = result {
+            error!(?err, "Booking can't be cancelled");
+            return Err("Database Error. Booking can't be cancelled".into());
+        }
+        self.send_timeslots();
+        Ok(promoted_codes)
+    }
+
+    fn cancel_own_booking(&self, confirmation_code: &str) -> Result<Vec<String>, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut timeslot = match Self::booked_with(&mut connection, confirmation_code) {
+            Ok(Some(timeslot)) => Timeslot::try_from(timeslot)?,
+            Ok(None) => {
+                let err = "Database Error. Booking does not exist";
+                error!(err);
+                return Err(BackendError::NotFound(err.into()));
+            }
+            Err(err) => {
+                error!(?err, "Failed to find timeslot by confirmation code");
+                return Err("Database Error. Failed to find timeslot by confirmation code".into());
+            }
+        };
+
+        let promoted_codes = timeslot.cancel_booking_of(confirmation_code);
+        let result = Self::update_booking(&mut connection, timeslot);
+        drop(connection);
+
+        if let Err(err) = result {
+            error!(?err, "Booking can't be cancelled");
+            return Err("Database Error. Booking can't be cancelled".into());
+        }
+        self.send_timeslots();
+        Ok(promoted_codes)
+    }
+
+    fn swap_bookings(&self, first_id: Uuid, second_id: Uuid) -> Result<(), BackendError> {
+        if first_id == second_id {
+            error!(SWAP_WITH_ITSELF);
+            return Err(BackendError::Conflict(SWAP_WITH_ITSELF.into()));
+        }
+        let mut connection = self.connection.lock().unwrap();
+        let mut first = Self::timeslot(&mut connection, first_id)?;
+        let mut second = Self::timeslot(&mut connection, second_id)?;
+        if let Err(err) = first.swap_bookings(&mut second) {
+            error!(err);
+            return Err(BackendError::Conflict(err));
+        }
+        let result = connection.transaction(|connection| {
+            Self::update_booking(connection, first)?;
+            Self::update_booking(connection, second)
+        });
+        drop(connection);
+
+        if let Err(err) = result {
+            error!(?err, "Bookings can't be swapped");
+            return Err("Database Error. Bookings can't be swapped".into());
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn confirm_booking(&self, timeslot_id: Uuid) -> Result<(), BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut timeslot = Self::timeslot(&mut connection, timeslot_id)?;
+        if !timeslot.confirm_bookings() {
+            let err = "Timeslot is not booked and can't therefore not be confirmed";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+        let result = Self::update_booking(&mut connection, timeslot);
+        drop(connection);
+
+        if let Err(err) = result {
+            error!(?err, "Booking can't be confirmed");
+            return Err("Database Error. Booking can't be confirmed".into());
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn release_expired_holds(&self, cutoff: DateTime<Utc>) -> Result<usize, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        // Compared after loading, as the datetimes are stored as text
+        let result = connection.transaction(|connection| {
+            let booked_timeslots = timeslots
+                .filter(archived.eq(false))
+                .filter(booked_count.gt(0))
+                .load::<SqliteTimeslot>(connection)?;
+            let mut released = 0;
+            for timeslot in booked_timeslots {
+                let Ok(mut timeslot) = Timeslot::try_from(timeslot) else {
+                    continue;
+                };
+                if timeslot.hold_expired(cutoff) {
+                    released += timeslot.release_expired_holds(cutoff);
+                    Self::update_booking(connection, timeslot)?;
+                }
+            }
+            Ok::<_, diesel::result::Error>(released)
+        });
+        drop(connection);
+
+        match result {
+            Ok(released) => {
+                if released > 0 {
+                    self.send_timeslots();
+                }
+                Ok(released)
+            }
+            Err(err) => {
+                error!(?err, "Expired holds can't be released");
+                Err("Database Error. Expired holds can't be released".into())
+            }
+        }
+    }
+
+    fn join_waitlist(&self, timeslot_id: Uuid, client_name: String) -> Result<usize, BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let mut timeslot = Self::timeslot(&mut connection, timeslot_id)?;
+        if timeslot.available {
+            error!(TIMESLOT_AVAILABLE);
+            return Err(BackendError::Conflict(TIMESLOT_AVAILABLE.into()));
+        }
+        if timeslot.datetime < Utc::now() {
+            let err = "Timeslot already passed";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        timeslot.waitlist.push(client_name);
+        let result = diesel::update(timeslots.find(timeslot_id.to_string()))
+            .set(waitlist.eq(serde_json::json!(timeslot.waitlist).to_string()))
+            .execute(&mut *connection);
+        drop(connection);
+
+        if let Err(err) = result {
+            error!(?err, "Waitlist can't be joined");
+            return Err("Database Error. Waitlist can't be joined".into());
+        }
+        self.send_timeslots();
+        Ok(timeslot.waitlist.len())
+    }
+
+    fn add_timeslot(
+        &self,
+        new_datetime: DateTime<Utc>,
+        new_notes: String,
+        new_capacity: i32,
+        new_duration_minutes: i32,
+        new_internal_notes: Option<String>,
+        new_category: Option<String>,
+    ) -> Result<(), BackendError> {
+        let timeslot = NewSqliteTimeslot {
+            id: Uuid::new_v4().to_string(),
+            datetime: new_datetime,
+            notes: new_notes,
+            capacity: new_capacity,
+            duration_minutes: new_duration_minutes,
+            internal_notes: new_internal_notes,
+            category: new_category,
+        };
+
+        self.try_add_timeslot(timeslot)?;
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn check_timeslots_bulk(&self, drafts: &[TimeslotDraft]) -> Result<(), BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let result = connection.transaction(|connection| {
+            if self.max_timeslots.is_some() {
+                let count: i64 = timeslots
+                    .filter(archived.eq(false))
+                    .count()
+                    .get_result(connection)?;
+                if !within_timeslot_limit(self.max_timeslots, count as usize, drafts.len()) {
+                    return Ok(Err(BackendError::Conflict(TIMESLOT_LIMIT_REACHED.into())));
+                }
+            }
+            if !self.allow_overlap {
+                if drafts_overlap(drafts) {
+                    return Ok(Err(BackendError::Conflict("Overlapping timeslot".into())));
+                }
+                for draft in drafts {
+                    if Self::overlaps_existing_timeslot(
+                        connection,
+                        draft.datetime,
+                        draft.end(),
+                        None,
+                    )? {
+                        return Ok(Err(BackendError::Conflict("Overlapping timeslot".into())));
+                    }
+                }
+            }
+            Ok::<_, diesel::result::Error>(Ok(()))
+        });
+
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(err)) => {
+                error!(%err);
+                Err(err)
+            }
+            Err(err) => {
+                error!(?err, "Failed to check timeslots");
+                Err("Database Error. Timeslots can't be checked".into())
+            }
+        }
+    }
+
+    fn edit_timeslot(
+        &self,
+        timeslot_id: Uuid,
+        new_datetime: DateTime<Utc>,
+        new_notes: String,
+        new_internal_notes: Option<String>,
+        expected_version: i32,
+    ) -> Result<(), BackendError> {
+        let active_timeslot = timeslots
+            .find(timeslot_id.to_string())
+            .filter(archived.eq(false));
+        let mut connection = self.connection.lock().unwrap();
+        if !self.allow_overlap {
+            let timeslot = Self::timeslot(&mut connection, timeslot_id)?;
+            let new_end = new_datetime + Duration::minutes(timeslot.duration_minutes.into());
+            match Self::overlaps_existing_timeslot(
+                &mut connection,
+                new_datetime,
+                new_end,
+                Some(timeslot_id),
+            ) {
+                Ok(false) => {}
+                Ok(true) => {
+                    let err = "Overlapping timeslot";
+                    error!(err);
+                    return Err(BackendError::Conflict(err.into()));
+                }
+                Err(err) => {
+                    error!(?err, "Failed to check for overlapping timeslots");
+                    return Err("Database Error. Timeslot can't be edited".into());
+                }
+            }
+        }
+        let result = diesel::update(active_timeslot.clone().filter(version.eq(expected_version)))
+            .set((
+                datetime.eq(new_datetime),
+                notes.eq(new_notes),
+                internal_notes.eq(new_internal_notes),
+                version.eq(version + 1),
+            ))
+            .execute(&mut *connection);
+        let existing = match result {
+            Ok(0) => active_timeslot.count().get_result::<i64>(&mut *connection),
+            _ => Ok(0),
+        };
+        drop(connection);
+
+        match result {
+            // Either the timeslot doesn't exist or it was edited in the meantime
+            Ok(0) => match existing {
+                Ok(0) => {
+                    error!("Edit failed. 0 database lines were changed");
+                    Err(BackendError::NotFound(
+                        "Database Error. Timeslot does not exist".into(),
+                    ))
+                }
+                Ok(_) => {
+                    error!(expected_version, "Edit failed. Version conflict");
+                    Err(BackendError::Conflict(VERSION_CONFLICT.into()))
+                }
+                Err(err) => {
+                    error!(?err, "Timeslot can't be edited");
+                    Err("Database Error. Timeslot can't be edited".into())
+                }
+            },
+            Ok(_) => {
+                self.send_timeslots();
+                Ok(())
+            }
+            Err(err) => {
+                error!(?err, "Timeslot can't be edited");
+                Err("Database Error. Timeslot can't be edited".into())
+            }
+        }
+    }
+
+    fn update_notes(&self, timeslot_id: Uuid, new_notes: String) -> Result<(), BackendError> {
+        let result = diesel::update(
+            timeslots
+                .find(timeslot_id.to_string())
+                .filter(archived.eq(false)),
+        )
+        .set((notes.eq(new_notes), version.eq(version + 1)))
+        .execute(&mut *self.connection.lock().unwrap());
+
+        match result {
+            Ok(0) => {
+                error!("Updating notes failed. 0 database lines were changed");
+                Err(BackendError::NotFound(
+                    "Database Error. Timeslot does not exist".into(),
+                ))
+            }
+            Ok(_) => {
+                self.send_timeslots();
+                Ok(())
+            }
+            Err(err) => {
+                error!(?err, "Notes can't be updated");
+                Err("Database Error. Notes can't be updated".into())
+            }
+        }
+    }
+
+    fn reschedule(
+        &self,
+        timeslot_id: Uuid,
+        new_datetime: DateTime<Utc>,
+    ) -> Result<(), BackendError> {
+        let mut connection = self.connection.lock().unwrap();
+        let timeslot = Self::timeslot(&mut connection, timeslot_id)?;
+        if timeslot.booked_count == 0 {
+            let err = "Timeslot is not booked and can't therefore not be rescheduled";
+            error!(err);
+            return Err(BackendError::Conflict(err.into()));
+        }
+
+        if !self.allow_overlap {
+            let new_end = new_datetime + Duration::minutes(timeslot.duration_minutes.into());
+            match Self::overlaps_existing_timeslot(
+                &mut connection,
+                new_datetime,
+                new_end,
+                Some(timeslot_id),
+            ) {
+                Ok(false) => {}
+                Ok(true) => {
+                    let err = "Overlapping timeslot";
+                    error!(err);
+                    return Err(BackendError::Conflict(err.into()));
+                }
+                Err(err) => {
+                    error!(?err, "Failed to check for overlapping timeslots");
+                    return Err("Database Error. Timeslot can't be rescheduled".into());
+                }
+            }
+        }
+
+        let result = diesel::update(timeslots.find(timeslot_id.to_string()))
+            .set((datetime.eq(new_datetime), version.eq(version + 1)))
+            .execute(&mut *connection);
+        drop(connection);
+
+        if let Err(err) = result {
+            error!(?err, "Timeslot can't be rescheduled");
+            return Err("Database Error. Timeslot can't be rescheduled".into());
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn remove_timeslot(&self, timeslot_id: Uuid) -> Result<(), BackendError> {
+        let active_timeslot = timeslots
+            .find(timeslot_id.to_string())
+            .filter(archived.eq(false));
+        let mut connection = self.connection.lock().unwrap();
+        let result = if self.hard_delete {
+            diesel::delete(active_timeslot).execute(&mut *connection)
+        } else {
+            diesel::update(active_timeslot)
+                .set(archived.eq(true))
+                .execute(&mut *connection)
+        };
+        drop(connection);
+
+        match result {
+            Ok(0) => {
+                error!("Deletion failed. 0 database lines were changed");
+                Err(BackendError::NotFound(
+                    "Database Error. Timeslot does not exist".into(),
+                ))
+            }
+            Ok(_) => {
+                self.send_timeslots();
+                Ok(())
+            }
+            Err(err) => {
+                error!(?err, "Deletion of timeslot failed");
+                Err("Database Error. Deletion of timeslot failed".into())
+            }
+        }
+    }
+
+    fn archived_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.sorted_timeslots(true)
+    }
+
+    fn remove_all_timeslot(&self) -> Result<(), BackendError> {
+        let result = self.connection.lock().unwrap().transaction(|connection| {
+            let removed = timeslots.load::<SqliteTimeslot>(connection)?;
+            diesel::delete(timeslots::table).execute(connection)?;
+            Ok::<_, diesel::result::Error>(removed)
+        });
+
+        match result {
+            Ok(removed) => {
+                // Removing nothing must not overwrite a removal which can still be restored
+                if !removed.is_empty() {
+                    *self.last_removed.lock().unwrap() = Some(removed);
+                }
+            }
+            Err(err) => {
+                error!(?err, "Failed to clear Database");
+                return Err("Failed to clear Database".into());
+            }
+        }
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn restore_last(&self) -> Result<(), BackendError> {
+        let mut last_removed = self.last_removed.lock().unwrap();
+        let Some(removed) = last_removed.as_ref() else {
+            error!(NOTHING_TO_RESTORE);
+            return Err(BackendError::Conflict(NOTHING_TO_RESTORE.into()));
+        };
+        let result = diesel::insert_into(timeslots::table)
+            .values(removed)
+            .execute(&mut *self.connection.lock().unwrap());
+
+        if let Err(err) = result {
+            error!(?err, "Failed to restore timeslots");
+            return Err("Database Error. Timeslots can't be restored".into());
+        }
+        *last_removed = None;
+        drop(last_removed);
+        self.send_timeslots();
+        Ok(())
+    }
+
+    fn count_bookings(&self, client_name: &str) -> Result<usize, BackendError> {
+        let result = diesel::sql_query(SELECT_BOOKER_NAMES)
+            .load::<BookerName>(&mut *self.connection.lock().unwrap());
+
+        // Compared here like in `Timeslot::bookings_of`, since lower() of SQLite only folds ASCII
+        // letters
+        let client_name = client_name.trim().to_lowercase();
+        result
+            .map(|names| {
+                names
+                    .iter()
+                    .filter(|BookerName { name }| name.trim().to_lowercase() == client_name)
+                    .count()
+            })
+            .map_err(|err| {
+                error!(?err, "Failed to count bookings");
+                "Database Error. Failed to count bookings".into()
+            })
+    }
+
+    fn remove_outdated_timeslots(&self) -> Result<usize, BackendError> {
+        let cutoff_time = Utc::now() - self.cleanup_retention;
+        let mut connection = self.connection.lock().unwrap();
+        let result = Self::cleanup_outdated_timeslots(&mut connection, cutoff_time);
+        drop(connection);
+
+        match result {
+            Ok(0) => Ok(0),
+            Ok(removed) => {
+                self.send_timeslots();
+                Ok(removed)
+            }
+            Err(err) => {
+                error!(?err, "Cleanup failed");
+                Err("Database Error. Outdated timeslots can't be removed".into())
+            }
+        }
+    }
+
+    fn search_bookings(&self, search: &str) -> Result<Vec<Timeslot>, BackendError> {
+        // Filtered here, since LIKE of SQLite only ignores the case of ASCII letters
+        Ok(self
+            .timeslots()?
+            .into_iter()
+            .filter(|timeslot| timeslot.booker_name_contains(search))
+            .collect())
+    }
+
+    fn find_timeslot(&self, timeslot_id: Uuid) -> Result<Option<Timeslot>, BackendError> {
+        let result = timeslots
+            .find(timeslot_id.to_string())
+            .filter(archived.eq(false))
+            .first::<SqliteTimeslot>(&mut *self.connection.lock().unwrap())
+            .optional();
+
+        match result {
+            Ok(timeslot) => timeslot.map(Timeslot::try_from).transpose(),
+            Err(err) => {
+                error!(?err, "Failed to find timeslot");
+                Err("Database Error. Failed to find timeslot".into())
+            }
+        }
+    }
+
+    fn find_by_confirmation(&self, code: &str) -> Result<Option<Timeslot>, BackendError> {
+        let result = Self::booked_with(&mut self.connection.lock().unwrap(), code);
+
+        match result {
+            Ok(timeslot) => timeslot.map(Timeslot::try_from).transpose(),
+            Err(err) => {
+                error!(?err, "Failed to find timeslot by confirmation code");
+                Err("Database Error. Failed to find timeslot by confirmation code".into())
+            }
+        }
+    }
+
+    fn health_check(&self) -> Result<(), BackendError> {
+        let result = diesel::sql_query("SELECT 1").execute(&mut *self.connection.lock().unwrap());
+
+        if let Err(err) = result {
+            error!(?err, "Health check failed");
+            return Err("Database Error. Health check failed".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    //! Integration tests running against an in-memory SQLite database.
+
+    use super::*;
+    use crate::testutils::{assert_no_timeslot_event, read_from_timeslot_stream};
+
+    const TEST_DATABASE_PATH: &str = ":memory:";
+
+    #[tokio::test]
+    async fn test_unchanged_timeslots_not_sent_again() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        let mut timeslot_stream = sqlite_interface.timeslot_stream();
+        assert!(read_from_timeslot_stream(&mut timeslot_stream)
+            .await
+            .is_empty());
+
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "First Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = read_from_timeslot_stream(&mut timeslot_stream).await[0].id;
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        sqlite_interface.confirm_booking(timeslot_id).unwrap();
+        assert!(read_from_timeslot_stream(&mut timeslot_stream).await[0].bookings[0].confirmed);
+
+        // Confirming again changes nothing
+        sqlite_interface.confirm_booking(timeslot_id).unwrap();
+        assert_no_timeslot_event(&mut timeslot_stream).await;
+
+        let mut second_stream = sqlite_interface.timeslot_stream();
+        assert_eq!(read_from_timeslot_stream(&mut second_stream).await.len(), 1);
+        assert_no_timeslot_event(&mut timeslot_stream).await;
+    }
+
+    #[tokio::test]
+    async fn test_add_book_remove_single_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        let mut timeslot_stream = sqlite_interface.timeslot_stream();
+        let current_timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(current_timeslots.len(), 0);
+
+        let current_time = Utc::now() + Duration::hours(1);
+        let example_notes = "Test timeslot";
+        sqlite_interface
+            .add_timeslot(current_time, example_notes.into(), 1, 30, None, None)
+            .unwrap();
+
+        let current_timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(current_timeslots.len(), 1);
+        assert!(current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booker_name, "");
+        assert_eq!(current_timeslots[0].datetime, current_time);
+        let new_timeslot_id = current_timeslots[0].id;
+
+        sqlite_interface
+            .book_timeslot(new_timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+
+        let current_timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(current_timeslots.len(), 1);
+        assert!(!current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booker_name, "Stefan");
+        assert_eq!(current_timeslots[0].id, new_timeslot_id);
+
+        sqlite_interface
+            .book_timeslot(new_timeslot_id, "Peter".into(), None, None, None)
+            .unwrap_err();
+
+        sqlite_interface.remove_timeslot(new_timeslot_id).unwrap();
+        let current_timeslots = read_from_timeslot_stream(&mut timeslot_stream).await;
+        assert_eq!(current_timeslots.len(), 0);
+
+        sqlite_interface
+            .remove_timeslot(new_timeslot_id)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_book_timeslot_with_capacity() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "Group timeslot".into(), 2, 30, None, None)
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert!(current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booked_count, 1);
+
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Peter".into(), None, None, None)
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert!(!current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booker_name, "Stefan, Peter");
+
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Anna".into(), None, None, None)
+            .unwrap_err();
+
+        sqlite_interface.cancel_booking(timeslot_id).unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert!(current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booked_count, 0);
+        assert_eq!(current_timeslots[0].booker_name, "");
+    }
+
+    #[test]
+    fn test_book_first_available() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), false, None, false)
+                .unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        for hours in 0..4 {
+            sqlite_interface
+                .add_timeslot(
+                    start + Duration::hours(hours),
+                    format!("Timeslot {hours}"),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let removed = sqlite_interface.current_timeslots().unwrap()[1].id;
+        sqlite_interface.remove_timeslot(removed).unwrap();
+
+        // The earliest available timeslot of the window is booked, removed ones are skipped
+        for expected_notes in ["Timeslot 0", "Timeslot 2"] {
+            let booked_timeslot = sqlite_interface
+                .book_first_available(start, start + Duration::hours(2), "Stefan".into())
+                .unwrap();
+            assert_eq!(booked_timeslot.notes, expected_notes);
+            assert_eq!(booked_timeslot.booker_name, "Stefan");
+            assert_eq!(booked_timeslot.bookings.len(), 1);
+        }
+        assert!(matches!(
+            sqlite_interface.book_first_available(
+                start,
+                start + Duration::hours(2),
+                "Stefan".into()
+            ),
+            Err(BackendError::NotFound(_))
+        ));
+        assert!(matches!(
+            sqlite_interface.book_first_available(
+                start + Duration::hours(5),
+                start + Duration::hours(6),
+                "Stefan".into()
+            ),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_timeslots_between() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), false, None, false)
+                .unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        for hours in 0..4 {
+            sqlite_interface
+                .add_timeslot(
+                    start + Duration::hours(hours),
+                    format!("Timeslot {hours}"),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let removed = sqlite_interface.current_timeslots().unwrap()[2].id;
+        sqlite_interface.remove_timeslot(removed).unwrap();
+
+        let timeslots_in_range = sqlite_interface
+            .timeslots_between(start + Duration::hours(1), start + Duration::hours(3))
+            .unwrap();
+        let timeslot_notes: Vec<&str> = timeslots_in_range
+            .iter()
+            .map(|timeslot| timeslot.notes.as_str())
+            .collect();
+        assert_eq!(timeslot_notes, ["Timeslot 1", "Timeslot 3"]);
+        assert!(sqlite_interface
+            .timeslots_between(start + Duration::hours(5), start + Duration::hours(6))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_count_bookings() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), false, None, false)
+                .unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        for hours in 0..4 {
+            sqlite_interface
+                .add_timeslot(
+                    start + Duration::hours(hours),
+                    "Timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let current_timeslots = sqlite_interface.current_timeslots().unwrap();
+        for (timeslot, client_name) in
+            current_timeslots
+                .iter()
+                .zip(["Stefan", " stefan ", "Peter", "Smith, Anna"])
+        {
+            sqlite_interface
+                .book_timeslot(timeslot.id, client_name.into(), None, None, None)
+                .unwrap();
+        }
+
+        assert_eq!(sqlite_interface.count_bookings("STEFAN").unwrap(), 2);
+        assert_eq!(sqlite_interface.count_bookings("Peter").unwrap(), 1);
+        assert_eq!(sqlite_interface.count_bookings("Anna").unwrap(), 0);
+        assert_eq!(sqlite_interface.count_bookings("smith, anna").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_count_timeslots() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        for timeslot_notes in ["First timeslot", "Second timeslot", "Third timeslot"] {
+            sqlite_interface
+                .add_timeslot(start, timeslot_notes.into(), 1, 30, None, None)
+                .unwrap();
+        }
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() - Duration::days(2),
+                "Outdated timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(sqlite_interface.remove_outdated_timeslots().unwrap(), 1);
+        let current_timeslots = sqlite_interface.current_timeslots().unwrap();
+        sqlite_interface
+            .book_timeslot(current_timeslots[0].id, "Stefan".into(), None, None, None)
+            .unwrap();
+        sqlite_interface
+            .remove_timeslot(current_timeslots[1].id)
+            .unwrap();
+
+        assert_eq!(sqlite_interface.count_timeslots(false).unwrap(), 2);
+        assert_eq!(sqlite_interface.count_timeslots(true).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_reschedule() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), false, None, false)
+                .unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "First timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(
+                current_time + Duration::hours(1),
+                "Second timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        sqlite_interface // try to reschedule a timeslot without booking
+            .reschedule(timeslot_id, current_time + Duration::hours(2))
+            .unwrap_err();
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        // Overlapping with itself is fine, but not with the second timeslot
+        sqlite_interface
+            .reschedule(timeslot_id, current_time + Duration::minutes(15))
+            .unwrap();
+        sqlite_interface
+            .reschedule(timeslot_id, current_time + Duration::minutes(75))
+            .unwrap_err();
+
+        let new_datetime = current_time + Duration::hours(2);
+        sqlite_interface
+            .reschedule(timeslot_id, new_datetime)
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots[1].id, timeslot_id);
+        assert_eq!(current_timeslots[1].datetime, new_datetime);
+        assert_eq!(current_timeslots[1].booker_name, "Stefan");
+        assert!(!current_timeslots[1].available);
+    }
+
+    #[test]
+    fn test_edit_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "Test timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        let new_datetime = Utc::now() + Duration::hours(2);
+        sqlite_interface
+            .edit_timeslot(timeslot_id, new_datetime, "Edited timeslot".into(), None, 0)
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots[0].datetime, new_datetime);
+        assert_eq!(current_timeslots[0].notes, "Edited timeslot");
+        assert_eq!(current_timeslots[0].version, 1);
+
+        // An edit based on the previous version is rejected
+        assert_eq!(
+            sqlite_interface
+                .edit_timeslot(timeslot_id, current_time, "Stale edit".into(), None, 0)
+                .unwrap_err(),
+            BackendError::Conflict(VERSION_CONFLICT.into())
+        );
+        assert_eq!(
+            sqlite_interface.timeslots().unwrap()[0].notes,
+            "Edited timeslot"
+        );
+
+        sqlite_interface // try to edit not existing timeslot
+            .edit_timeslot(
+                Uuid::new_v4(),
+                new_datetime,
+                "Edited timeslot".into(),
+                None,
+                1,
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_swap_bookings() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        for hours in [1, 2] {
+            sqlite_interface
+                .add_timeslot(
+                    Utc::now() + Duration::hours(hours),
+                    "Test timeslot".into(),
+                    1,
+                    30,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        let (first_id, second_id) = (current_timeslots[0].id, current_timeslots[1].id);
+        sqlite_interface
+            .book_timeslot(first_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        sqlite_interface
+            .book_timeslot(second_id, "Peter".into(), None, None, None)
+            .unwrap();
+
+        sqlite_interface.swap_bookings(first_id, second_id).unwrap();
+
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots[0].booker_name, "Peter");
+        assert_eq!(current_timeslots[1].booker_name, "Stefan");
+
+        assert!(matches!(
+            sqlite_interface.swap_bookings(first_id, Uuid::new_v4()),
+            Err(BackendError::NotFound(_))
+        ));
+        assert_eq!(sqlite_interface.timeslots().unwrap(), current_timeslots);
+    }
+
+    #[test]
+    fn test_update_notes() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(start, "Test timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        sqlite_interface
+            .update_notes(timeslot_id, "Updated notes".into())
+            .unwrap();
+
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots[0].notes, "Updated notes");
+        assert_eq!(current_timeslots[0].datetime, start);
+        assert_eq!(current_timeslots[0].duration_minutes, 30);
+        assert_eq!(current_timeslots[0].version, 1);
+
+        assert!(matches!(
+            sqlite_interface.update_notes(Uuid::new_v4(), "Updated notes".into()),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_internal_notes() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(
+                start,
+                "Workshop".into(),
+                1,
+                30,
+                Some("Bring the projector".into()),
+                None,
+            )
+            .unwrap();
+        let timeslot = sqlite_interface.timeslots().unwrap()[0].clone();
+        assert_eq!(
+            timeslot.internal_notes.as_deref(),
+            Some("Bring the projector")
+        );
+
+        sqlite_interface
+            .edit_timeslot(timeslot.id, start, "Workshop".into(), None, 0)
+            .unwrap();
+        assert_eq!(
+            sqlite_interface.timeslots().unwrap()[0].internal_notes,
+            None
+        );
+    }
+
+    #[test]
+    fn test_category() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(
+                start,
+                "Workshop".into(),
+                1,
+                30,
+                None,
+                Some("haircut".into()),
+            )
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(start, "Workshop".into(), 1, 30, None, None)
+            .unwrap();
+        let mut categories: Vec<Option<String>> = sqlite_interface
+            .timeslots()
+            .unwrap()
+            .into_iter()
+            .map(|timeslot| timeslot.category)
+            .collect();
+        categories.sort();
+        assert_eq!(categories, vec![None, Some("haircut".into())]);
+    }
+
+    #[test]
+    fn test_release_expired_holds() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "Unconfirmed".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(
+                current_time + Duration::hours(1),
+                "Confirmed".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        for timeslot in &current_timeslots {
+            sqlite_interface
+                .book_timeslot(timeslot.id, "Stefan".into(), None, None, None)
+                .unwrap();
+        }
+        sqlite_interface
+            .confirm_booking(current_timeslots[1].id)
+            .unwrap();
+        // A timeslot without booking can't be confirmed
+        sqlite_interface
+            .cancel_booking(current_timeslots[0].id)
+            .unwrap();
+        assert!(matches!(
+            sqlite_interface.confirm_booking(current_timeslots[0].id),
+            Err(BackendError::Conflict(_))
+        ));
+        assert!(matches!(
+            sqlite_interface.confirm_booking(Uuid::new_v4()),
+            Err(BackendError::NotFound(_))
+        ));
+        // Bookings made after the confirmation are held again
+        for timeslot in &current_timeslots {
+            sqlite_interface
+                .book_timeslot(timeslot.id, "Peter".into(), None, None, None)
+                .unwrap();
+        }
+
+        // All bookings are within the hold
+        assert_eq!(
+            sqlite_interface
+                .release_expired_holds(Utc::now() - Duration::minutes(10))
+                .unwrap(),
+            0
+        );
+
+        assert_eq!(
+            sqlite_interface
+                .release_expired_holds(Utc::now() + Duration::seconds(1))
+                .unwrap(),
+            2
+        );
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert!(current_timeslots[0].available);
+        assert_eq!(current_timeslots[0].booked_count, 0);
+        assert!(current_timeslots[0].bookings.is_empty());
+        // Only the unconfirmed booking of the group timeslot is released
+        assert!(current_timeslots[1].available);
+        assert_eq!(current_timeslots[1].booked_count, 1);
+        assert_eq!(current_timeslots[1].booker_name, "Stefan");
+        assert!(current_timeslots[1].bookings[0].confirmed);
+    }
+
+    #[test]
+    fn test_waitlist() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "Workshop".into(), 1, 30, None, None)
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        let err = sqlite_interface
+            .join_waitlist(timeslot_id, "Peter".into())
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict(TIMESLOT_AVAILABLE.into()));
+
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        assert_eq!(
+            sqlite_interface
+                .join_waitlist(timeslot_id, "Peter".into())
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            sqlite_interface
+                .join_waitlist(timeslot_id, "Anna".into())
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            sqlite_interface.timeslots().unwrap()[0].waitlist,
+            vec!["Peter", "Anna"]
+        );
+
+        // The first client on the waitlist takes over the cancelled booking
+        let booking_code = sqlite_interface
+            .cancel_booking(timeslot_id)
+            .unwrap()
+            .remove(0);
+        let timeslot = sqlite_interface
+            .find_by_confirmation(&booking_code)
+            .unwrap()
+            .unwrap();
+        assert!(!timeslot.available);
+        assert_eq!(timeslot.booked_count, 1);
+        assert_eq!(timeslot.booker_name, "Peter");
+        assert_eq!(timeslot.waitlist, vec!["Anna"]);
+
+        assert_eq!(
+            sqlite_interface.cancel_booking(timeslot_id).unwrap().len(),
+            1
+        );
+        assert!(sqlite_interface
+            .cancel_booking(timeslot_id)
+            .unwrap()
+            .is_empty());
+        let timeslot = sqlite_interface.timeslots().unwrap()[0].clone();
+        assert!(timeslot.available);
+        assert_eq!(timeslot.booked_count, 0);
+        assert!(timeslot.waitlist.is_empty());
+    }
+
+    #[test]
+    fn test_confirmation_code() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "Group timeslot".into(), 2, 30, None, None)
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        let booked_timeslot = sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        assert_eq!(booked_timeslot.id, timeslot_id);
+        assert_eq!(booked_timeslot.booker_name, "Stefan");
+        let code = booked_timeslot.bookings[0].confirmation_code.clone();
+        let timeslot = sqlite_interface
+            .find_by_confirmation(&code)
+            .unwrap()
+            .unwrap();
+        assert_eq!(timeslot.id, timeslot_id);
+        assert_eq!(timeslot.bookings[0].confirmation_code, code);
+
+        // Every booking of the same timeslot gets its own code
+        let booked_timeslot = sqlite_interface
+            .book_timeslot(timeslot_id, "Peter".into(), None, None, None)
+            .unwrap();
+        let second_code = booked_timeslot.bookings[1].confirmation_code.clone();
+        assert_ne!(second_code, code);
+        assert_eq!(booked_timeslot.bookings[0].confirmation_code, code);
+        assert_eq!(booked_timeslot.booker_name, "Stefan, Peter");
+        assert_eq!(booked_timeslot.booked_count, 2);
+        assert_eq!(
+            sqlite_interface
+                .find_by_confirmation(&second_code)
+                .unwrap()
+                .unwrap()
+                .id,
+            timeslot_id
+        );
+
+        sqlite_interface.cancel_booking(timeslot_id).unwrap();
+        assert_eq!(sqlite_interface.find_by_confirmation(&code).unwrap(), None);
+        assert_eq!(
+            sqlite_interface.find_by_confirmation(&second_code).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cancel_own_booking() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Group timeslot".into(),
+                2,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+        sqlite_interface
+            .book_timeslot(
+                timeslot_id,
+                "Stefan".into(),
+                Some("stefan@example.com".into()),
+                None,
+                None,
+            )
+            .unwrap();
+        let booked_timeslot = sqlite_interface
+            .book_timeslot(
+                timeslot_id,
+                "Peter".into(),
+                Some("peter@example.com".into()),
+                Some("+436641234567".into()),
+                None,
+            )
+            .unwrap();
+        let stefans_code = booked_timeslot.bookings[0].confirmation_code.clone();
+
+        // Only Stefan's place is released, Peter keeps his booking and details
+        assert!(sqlite_interface
+            .cancel_own_booking(&stefans_code)
+            .unwrap()
+            .is_empty());
+        let timeslot = sqlite_interface.timeslots().unwrap().remove(0);
+        assert_eq!(timeslot.booked_count, 1);
+        assert_eq!(timeslot.booker_name, "Peter");
+        assert!(timeslot.available);
+        assert_eq!(timeslot.bookings, booked_timeslot.bookings[1..]);
+        assert!(matches!(
+            sqlite_interface.cancel_own_booking(&stefans_code),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_email() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(current_time, "Group timeslot".into(), 3, 30, None, None)
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        sqlite_interface
+            .book_timeslot(
+                timeslot_id,
+                "Stefan".into(),
+                Some("stefan@example.com".into()),
+                None,
+                None,
+            )
+            .unwrap();
+        sqlite_interface
+            .book_timeslot(
+                timeslot_id,
+                "Peter".into(),
+                None,
+                Some("+436641234567".into()),
+                None,
+            )
+            .unwrap();
+        sqlite_interface
+            .book_timeslot(
+                timeslot_id,
+                "Anna".into(),
+                Some("anna@example.com".into()),
+                None,
+                Some("Vegetarian".into()),
+            )
+            .unwrap();
+        let timeslot = sqlite_interface.timeslots().unwrap().remove(0);
+        let details: Vec<_> = timeslot
+            .bookings
+            .iter()
+            .map(|booking| {
+                (
+                    booking.booker_name.as_str(),
+                    booking.client_email.as_deref(),
+                    booking.client_phone.as_deref(),
+                    booking.booker_notes.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            details,
+            [
+                ("Stefan", Some("stefan@example.com"), None, None),
+                ("Peter", None, Some("+436641234567"), None),
+                ("Anna", Some("anna@example.com"), None, Some("Vegetarian")),
+            ]
+        );
+
+        sqlite_interface.cancel_booking(timeslot_id).unwrap();
+        assert!(sqlite_interface.timeslots().unwrap()[0].bookings.is_empty());
+    }
+
+    #[test]
+    fn test_split_legacy_bookings() {
+        let database = tempfile::NamedTempFile::new().unwrap();
+        let database_path = database.path().to_str().unwrap();
+        let mut connection = SqliteConnection::establish(database_path).unwrap();
+        for statement in [
+            "CREATE TABLE timeslots (
+                id TEXT PRIMARY KEY NOT NULL,
+                datetime TEXT NOT NULL,
+                available BOOLEAN NOT NULL DEFAULT 1,
+                booker_name TEXT NOT NULL DEFAULT '',
+                notes TEXT NOT NULL,
+                capacity INTEGER NOT NULL DEFAULT 1 CHECK (capacity > 0),
+                booked_count INTEGER NOT NULL DEFAULT 0,
+                confirmation_code TEXT,
+                client_email TEXT,
+                client_phone TEXT,
+                booker_notes TEXT,
+                booked_at TEXT,
+                confirmed BOOLEAN NOT NULL DEFAULT 0
+            )",
+            "CREATE UNIQUE INDEX timeslots_confirmation_code_key ON timeslots (confirmation_code)",
+            "INSERT INTO timeslots (id, datetime, available, booker_name, notes, capacity, \
+                booked_count, confirmation_code, client_email, client_phone, booker_notes, \
+                booked_at, confirmed) \
+                VALUES ('6b3a8a43-3f0c-4a55-9d0e-7c1b0f6d2a11', datetime('now', '+1 hour'), 1, \
+                'Stefan, Peter', 'Group timeslot', 3, 2, 'ABCD2345', \
+                'stefan@example.com, peter@example.com', '+436641234567', 'Vegetarian', \
+                datetime('now', '-20 minutes'), 1)",
+        ] {
+            diesel::sql_query(statement)
+                .execute(&mut connection)
+                .unwrap();
+        }
+        drop(connection);
+
+        let sqlite_interface =
+            SqliteInterface::new(database_path, Duration::days(1), true, None, false).unwrap();
+        let timeslot = sqlite_interface.timeslots().unwrap().remove(0);
+        assert_eq!(timeslot.booker_name, "Stefan, Peter");
+        let details: Vec<_> = timeslot
+            .bookings
+            .iter()
+            .map(|booking| {
+                (
+                    booking.booker_name.as_str(),
+                    booking.client_email.as_deref(),
+                    booking.client_phone.as_deref(),
+                    booking.booker_notes.as_deref(),
+                )
+            })
+            .collect();
+        // The phone number and notes can't be assigned to one of the two bookers
+        assert_eq!(
+            details,
+            [
+                ("Stefan", Some("stefan@example.com"), None, None),
+                ("Peter", Some("peter@example.com"), None, None),
+            ]
+        );
+        assert_eq!(timeslot.bookings[0].confirmation_code, "ABCD2345");
+        // Both keep the hold of the timeslot
+        for booking in &timeslot.bookings {
+            assert!(booking.booked_at.is_some_and(|held| held < Utc::now()));
+            assert!(booking.confirmed);
+        }
+        let peters_code = timeslot.bookings[1].confirmation_code.clone();
+        assert_ne!(peters_code, "ABCD2345");
+        assert_eq!(
+            sqlite_interface
+                .find_by_confirmation(&peters_code)
+                .unwrap()
+                .unwrap()
+                .id,
+            timeslot.id
+        );
+        drop(sqlite_interface);
+
+        // Opening the upgraded database again leaves the bookings as they are
+        let sqlite_interface =
+            SqliteInterface::new(database_path, Duration::days(1), true, None, false).unwrap();
+        assert_eq!(
+            sqlite_interface.timeslots().unwrap()[0].bookings,
+            timeslot.bookings
+        );
+    }
+
+    #[test]
+    fn test_try_book_outdated_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let current_time = Utc::now() - Duration::hours(2);
+        sqlite_interface
+            .add_timeslot(current_time, "Test timeslot".into(), 1, 30, None, None)
+            .unwrap();
+
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        let timeslot_id = current_timeslots[0].id;
+        assert_eq!(current_timeslots.len(), 1);
+        assert!(current_timeslots[0].available);
+
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_remove_multiple_timeslots() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        sqlite_interface
+            .add_timeslot(Utc::now(), "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(Utc::now(), "Seconds Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(Utc::now(), "Third Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+
+        sqlite_interface // try to delete not existing timeslot
+            .remove_timeslot(Uuid::new_v4())
+            .unwrap_err();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots.len(), 3);
+
+        sqlite_interface
+            .remove_timeslot(current_timeslots[0].id)
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots.len(), 2);
+
+        sqlite_interface.remove_all_timeslot().unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots.len(), 0);
+    }
+
+    #[test]
+    fn test_database_persistency() {
+        let database_file = tempfile::NamedTempFile::new().unwrap();
+        let database_path = database_file.path().to_str().unwrap();
+
+        let sqlite_interface =
+            SqliteInterface::new(database_path, Duration::days(1), true, None, false).unwrap();
+        sqlite_interface
+            .add_timeslot(Utc::now(), "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(Utc::now(), "Seconds Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        drop(sqlite_interface);
+
+        let sqlite_interface =
+            SqliteInterface::new(database_path, Duration::days(1), true, None, false).unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots.len(), 2);
+    }
+
+    #[test]
+    fn cleanup_outdated_timeslots() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        sqlite_interface
+            .add_timeslot(Utc::now(), "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() - Duration::hours(2),
+                "Seconds Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() - Duration::days(2),
+                "Third Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(sqlite_interface.remove_outdated_timeslots().unwrap(), 1);
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots.len(), 2);
+        assert_eq!(current_timeslots[0].notes, "Seconds Timeslot");
+        assert_eq!(current_timeslots[1].notes, "First Timeslot");
+    }
+
+    #[test]
+    fn cleanup_outdated_timeslots_by_end_time() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::zero(), true, None, false).unwrap();
+
+        let start = Utc::now() - Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(start, "Long Timeslot".into(), 1, 180, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(start, "Short Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+
+        assert_eq!(sqlite_interface.remove_outdated_timeslots().unwrap(), 1);
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        assert_eq!(current_timeslots.len(), 1);
+        assert_eq!(current_timeslots[0].notes, "Long Timeslot");
+        assert_eq!(current_timeslots[0].duration_minutes, 180);
+    }
+
+    #[test]
+    fn test_reject_overlapping_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), false, None, false)
+                .unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(start, "First Timeslot".into(), 1, 60, None, None)
+            .unwrap();
+        let err = sqlite_interface
+            .add_timeslot(
+                start - Duration::minutes(30),
+                "Overlapping Timeslot".into(),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict("Overlapping timeslot".into()));
+        sqlite_interface
+            .add_timeslot(
+                start + Duration::minutes(60),
+                "Adjacent Timeslot".into(),
+                1,
+                60,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(sqlite_interface.timeslots().unwrap().len(), 2);
+
+        // Edits are checked as well, though not against the edited timeslot itself
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+        let err = sqlite_interface
+            .edit_timeslot(
+                current_timeslots[1].id,
+                start + Duration::minutes(30),
+                "Adjacent Timeslot".into(),
+                None,
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict("Overlapping timeslot".into()));
+        sqlite_interface
+            .edit_timeslot(
+                current_timeslots[0].id,
+                start - Duration::minutes(30),
+                "First Timeslot".into(),
+                None,
+                0,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_archive_removed_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        sqlite_interface.remove_all_timeslot().unwrap();
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+
+        sqlite_interface.remove_timeslot(timeslot_id).unwrap();
+        assert!(sqlite_interface.timeslots().unwrap().is_empty());
+        let archived_timeslots = sqlite_interface.archived_timeslots().unwrap();
+        assert_eq!(archived_timeslots.len(), 1);
+        assert!(archived_timeslots[0].archived);
+        assert_eq!(archived_timeslots[0].booker_name, "Stefan");
+
+        // Archived timeslots can't be changed anymore
+        sqlite_interface.cancel_booking(timeslot_id).unwrap_err();
+        sqlite_interface.remove_timeslot(timeslot_id).unwrap_err();
+    }
+
+    #[test]
+    fn test_hard_delete() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, true).unwrap();
+        sqlite_interface.remove_all_timeslot().unwrap();
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        sqlite_interface.remove_timeslot(timeslot_id).unwrap();
+        assert!(sqlite_interface.timeslots().unwrap().is_empty());
+        assert!(sqlite_interface.archived_timeslots().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_check_timeslots_bulk() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), false, None, false)
+                .unwrap();
+        sqlite_interface.remove_all_timeslot().unwrap();
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(start, "Existing Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+
+        let draft = |offset_minutes: i64| TimeslotDraft {
+            datetime: start + Duration::minutes(offset_minutes),
+            notes: "Timeslot".into(),
+            capacity: 1,
+            duration_minutes: 30,
+            internal_notes: None,
+            category: None,
+        };
+        sqlite_interface
+            .check_timeslots_bulk(&[draft(30), draft(60)])
+            .unwrap();
+        assert_eq!(sqlite_interface.timeslots().unwrap().len(), 1);
+
+        let err = sqlite_interface
+            .check_timeslots_bulk(&[draft(30), draft(45)])
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict("Overlapping timeslot".into()));
+        let err = sqlite_interface
+            .check_timeslots_bulk(&[draft(15)])
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict("Overlapping timeslot".into()));
+    }
+
+    #[test]
+    fn test_restore_last() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        assert_eq!(
+            sqlite_interface.restore_last().unwrap_err(),
+            BackendError::Conflict(NOTHING_TO_RESTORE.into())
+        );
+
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(start, "First Timeslot".into(), 1, 30, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(
+                start + Duration::hours(1),
+                "Second Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+        sqlite_interface
+            .book_timeslot(timeslot_id, "Stefan".into(), None, None, None)
+            .unwrap();
+        let current_timeslots = sqlite_interface.timeslots().unwrap();
+
+        sqlite_interface.remove_all_timeslot().unwrap();
+        // Clearing an empty schedule keeps the previous removal
+        sqlite_interface.remove_all_timeslot().unwrap();
+        assert!(sqlite_interface.timeslots().unwrap().is_empty());
+
+        sqlite_interface.restore_last().unwrap();
+        assert_eq!(sqlite_interface.timeslots().unwrap(), current_timeslots);
+        assert_eq!(
+            sqlite_interface.restore_last().unwrap_err(),
+            BackendError::Conflict(NOTHING_TO_RESTORE.into())
+        );
+    }
+
+    #[test]
+    fn test_timeslot_limit() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, Some(2), false)
+                .unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        for index in 0..2 {
+            sqlite_interface
+                .add_timeslot(start, format!("Timeslot {index}"), 1, 60, None, None)
+                .unwrap();
+        }
+        let err = sqlite_interface
+            .add_timeslot(start, "Exceeding Timeslot".into(), 1, 60, None, None)
+            .unwrap_err();
+        assert_eq!(err, BackendError::Conflict(TIMESLOT_LIMIT_REACHED.into()));
+
+        let removed_id = sqlite_interface.timeslots().unwrap()[0].id;
+        sqlite_interface.remove_timeslot(removed_id).unwrap();
+        sqlite_interface
+            .add_timeslot(start, "Replacing Timeslot".into(), 1, 60, None, None)
+            .unwrap();
+        assert_eq!(sqlite_interface.timeslots().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_allow_overlapping_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let start = Utc::now() + Duration::hours(1);
+        sqlite_interface
+            .add_timeslot(start, "First Timeslot".into(), 1, 60, None, None)
+            .unwrap();
+        sqlite_interface
+            .add_timeslot(start, "Parallel Timeslot".into(), 1, 60, None, None)
+            .unwrap();
+        assert_eq!(sqlite_interface.timeslots().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_find_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        sqlite_interface
+            .add_timeslot(
+                Utc::now() + Duration::hours(1),
+                "Timeslot".into(),
+                1,
+                30,
+                None,
+                None,
+            )
+            .unwrap();
+        let timeslot_id = sqlite_interface.timeslots().unwrap()[0].id;
+
+        let timeslot = sqlite_interface
+            .find_timeslot(timeslot_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(timeslot.notes, "Timeslot");
+        assert_eq!(
+            sqlite_interface.find_timeslot(Uuid::new_v4()).unwrap(),
+            None
+        );
+
+        sqlite_interface.remove_timeslot(timeslot_id).unwrap();
+        assert_eq!(sqlite_interface.find_timeslot(timeslot_id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_health_check() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+        sqlite_interface.health_check().unwrap();
+    }
+
+    #[test]
+    fn test_missing_timeslot() {
+        let sqlite_interface =
+            SqliteInterface::new(TEST_DATABASE_PATH, Duration::days(1), true, None, false).unwrap();
+
+        let timeslot_id = Uuid::new_v4();
+        assert!(matches!(
+            sqlite_interface.book_timeslot(timeslot_id, "Stefan".into(), None, None, None),
+            Err(BackendError::NotFound(_))
+        ));
+        assert!(matches!(
+            sqlite_interface.cancel_booking(timeslot_id),
+            Err(BackendError::NotFound(_))
+        ));
+        assert!(matches!(
+            sqlite_interface.remove_timeslot(timeslot_id),
+            Err(BackendError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,21 @@
+// SQLite counterpart of `schema.rs`. SQLite has no native UUID and timezone aware timestamp
+// types, therefore ids are stored as text and datetimes as RFC 3339 like strings.
+
+diesel::table! {
+    timeslots (id) {
+        id -> Text,
+        datetime -> TimestamptzSqlite,
+        available -> Bool,
+        booker_name -> Text,
+        notes -> Text,
+        capacity -> Integer,
+        booked_count -> Integer,
+        duration_minutes -> Integer,
+        archived -> Bool,
+        waitlist -> Text,
+        version -> Integer,
+        internal_notes -> Nullable<Text>,
+        category -> Nullable<Text>,
+        bookings -> Text,
+    }
+}
@@ -1,13 +1,28 @@
-use crate::{backend::TimeslotBackend, configuration::Configuration, types::Timeslot};
+//! Mocks of the backend and the configuration, e.g. to test an app embedding the router.
+
+use crate::{
+    authentication::{Admin, AdminPassword, DEFAULT_ADMIN_NAME},
+    backend::{event_channel, BackendError, TimeslotBackend, NO_TIMESLOT_AVAILABLE},
+    configuration::{BusinessHours, Configuration},
+    types::{Booking, Timeslot, TimeslotDraft, TimeslotEvent},
+};
 use std::{
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex,
     },
+    time::Duration,
+};
+use tokio::sync::{
+    broadcast,
+    watch::{self, Sender},
+};
+use tokio_stream::{
+    wrappers::{BroadcastStream, WatchStream},
+    StreamExt,
 };
-use tokio::sync::watch::{self, Sender};
-use tokio_stream::{wrappers::WatchStream, StreamExt};
+use tracing::Level;
 
 pub async fn read_from_timeslot_stream(
     timeslot_stream: &mut WatchStream<Vec<Timeslot>>,
@@ -21,14 +36,77 @@ pub async fn read_from_timeslot_stream(
     .unwrap()
 }
 
+pub async fn read_from_event_stream(
+    event_stream: &mut BroadcastStream<TimeslotEvent>,
+) -> TimeslotEvent {
+    tokio::time::timeout(std::time::Duration::from_millis(100), event_stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap()
+}
+
+/// Panics if the timeslot stream emits an event within a short time.
+pub async fn assert_no_timeslot_event(timeslot_stream: &mut WatchStream<Vec<Timeslot>>) {
+    let event = tokio::time::timeout(
+        std::time::Duration::from_millis(100),
+        timeslot_stream.next(),
+    )
+    .await;
+    assert!(event.is_err(), "Unexpected timeslot event: {event:?}");
+}
+
+pub const MOCK_CONFIRMATION_CODE: &str = "ABCD2345";
+
+/// A booking without contact details and hold, made with the mock confirmation code
+pub fn mock_booking(booker_name: &str) -> Booking {
+    Booking {
+        booker_name: booker_name.into(),
+        confirmation_code: MOCK_CONFIRMATION_CODE.into(),
+        client_email: None,
+        client_phone: None,
+        booker_notes: None,
+        booked_at: None,
+        confirmed: false,
+    }
+}
+
 pub struct MockTimeslotBackendInner {
     pub success: AtomicBool,
+    /// Returned by each call if `success` is false
+    pub error: Mutex<BackendError>,
+    /// Blocks each call for this long, like a slow database query
+    pub delay: Mutex<Duration>,
     pub calls_to_timeslots: AtomicU64,
+    pub calls_to_current_timeslots: AtomicU64,
+    pub calls_to_timeslots_between: AtomicU64,
+    pub calls_to_count_timeslots: AtomicU64,
     pub calls_to_book_timeslot: AtomicU64,
+    pub calls_to_book_first_available: AtomicU64,
+    pub calls_to_cancel_booking: AtomicU64,
+    pub calls_to_cancel_own_booking: AtomicU64,
+    pub calls_to_join_waitlist: AtomicU64,
+    pub calls_to_swap_bookings: AtomicU64,
+    pub calls_to_confirm_booking: AtomicU64,
     pub calls_to_add_timeslot: AtomicU64,
+    pub calls_to_add_timeslots_bulk: AtomicU64,
+    pub calls_to_check_timeslots_bulk: AtomicU64,
+    pub calls_to_edit_timeslot: AtomicU64,
+    pub calls_to_update_notes: AtomicU64,
+    pub calls_to_reschedule: AtomicU64,
     pub calls_to_remove_timeslot: AtomicU64,
+    pub calls_to_archived_timeslots: AtomicU64,
     pub calls_to_remove_all_timeslot: AtomicU64,
+    pub calls_to_restore_last: AtomicU64,
+    pub calls_to_search_bookings: AtomicU64,
+    pub calls_to_find_timeslot: AtomicU64,
+    pub calls_to_find_by_confirmation: AtomicU64,
+    pub calls_to_health_check: AtomicU64,
+    pub last_client_email: Mutex<Option<String>>,
+    pub last_client_phone: Mutex<Option<String>>,
+    pub last_booker_notes: Mutex<Option<String>>,
     pub timeslot_sender: Sender<Vec<Timeslot>>,
+    pub event_sender: broadcast::Sender<TimeslotEvent>,
 }
 
 #[derive(Clone)]
@@ -39,12 +117,38 @@ impl MockTimeslotBackendInner {
         let (sender, _) = watch::channel(vec![]);
         Self {
             success: AtomicBool::new(true),
+            error: Mutex::new("Supposed to fail".into()),
+            delay: Mutex::default(),
             calls_to_timeslots: AtomicU64::default(),
+            calls_to_current_timeslots: AtomicU64::default(),
+            calls_to_timeslots_between: AtomicU64::default(),
+            calls_to_count_timeslots: AtomicU64::default(),
             calls_to_book_timeslot: AtomicU64::default(),
+            calls_to_book_first_available: AtomicU64::default(),
+            calls_to_cancel_booking: AtomicU64::default(),
+            calls_to_cancel_own_booking: AtomicU64::default(),
+            calls_to_join_waitlist: AtomicU64::default(),
+            calls_to_swap_bookings: AtomicU64::default(),
+            calls_to_confirm_booking: AtomicU64::default(),
             calls_to_add_timeslot: AtomicU64::default(),
+            calls_to_add_timeslots_bulk: AtomicU64::default(),
+            calls_to_check_timeslots_bulk: AtomicU64::default(),
+            calls_to_edit_timeslot: AtomicU64::default(),
+            calls_to_update_notes: AtomicU64::default(),
+            calls_to_reschedule: AtomicU64::default(),
             calls_to_remove_timeslot: AtomicU64::default(),
+            calls_to_archived_timeslots: AtomicU64::default(),
             calls_to_remove_all_timeslot: AtomicU64::default(),
+            calls_to_restore_last: AtomicU64::default(),
+            calls_to_search_bookings: AtomicU64::default(),
+            calls_to_find_timeslot: AtomicU64::default(),
+            calls_to_find_by_confirmation: AtomicU64::default(),
+            calls_to_health_check: AtomicU64::default(),
+            last_client_email: Mutex::default(),
+            last_client_phone: Mutex::default(),
+            last_booker_notes: Mutex::default(),
             timeslot_sender: sender,
+            event_sender: event_channel(),
         }
     }
 }
@@ -54,58 +158,406 @@ impl MockTimeslotBackend {
         Self(Arc::new(MockTimeslotBackendInner::new()))
     }
 
-    fn result(&self) -> Result<(), String> {
+    fn result(&self) -> Result<(), BackendError> {
+        std::thread::sleep(*self.0.delay.lock().unwrap());
         match self.0.success.load(Ordering::SeqCst) {
             true => Ok(()),
-            false => Err("Supposed to fail".into()),
+            false => Err(self.0.error.lock().unwrap().clone()),
         }
     }
 }
 
+impl Default for MockTimeslotBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TimeslotBackend for MockTimeslotBackend {
-    fn book_timeslot(&self, _id: uuid::Uuid, _booker_name: String) -> Result<(), String> {
+    fn book_timeslot(
+        &self,
+        id: uuid::Uuid,
+        booker_name: String,
+        client_email: Option<String>,
+        client_phone: Option<String>,
+        booker_notes: Option<String>,
+    ) -> Result<Timeslot, BackendError> {
         self.0.calls_to_book_timeslot.fetch_add(1, Ordering::SeqCst);
+        *self.0.last_client_email.lock().unwrap() = client_email.clone();
+        *self.0.last_client_phone.lock().unwrap() = client_phone.clone();
+        *self.0.last_booker_notes.lock().unwrap() = booker_notes.clone();
+        self.result()?;
+        let booking = Booking {
+            client_email,
+            client_phone,
+            booker_notes,
+            ..mock_booking(&booker_name)
+        };
+        // The timeslot provided via the stream if there is one, otherwise a booked example
+        let provided = self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .find(|timeslot| timeslot.id == id)
+            .cloned();
+        let mut booked_timeslot = provided.unwrap_or_else(|| Timeslot {
+            id,
+            datetime: chrono::Utc::now() + chrono::Duration::hours(1),
+            available: false,
+            booker_name,
+            notes: String::new(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![],
+        });
+        booked_timeslot.bookings.push(booking);
+        Ok(booked_timeslot)
+    }
+
+    fn book_first_available(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        booker_name: String,
+    ) -> Result<Timeslot, BackendError> {
+        self.0
+            .calls_to_book_first_available
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        // The earliest available timeslot of the window provided via the stream
+        self.0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .filter(|timeslot| {
+                timeslot.available && from <= timeslot.datetime && timeslot.datetime <= to
+            })
+            .min_by_key(|timeslot| timeslot.datetime)
+            .map(|timeslot| {
+                let mut booked_timeslot = timeslot.clone();
+                booked_timeslot.bookings.push(mock_booking(&booker_name));
+                booked_timeslot
+            })
+            .ok_or_else(|| BackendError::NotFound(NO_TIMESLOT_AVAILABLE.into()))
+    }
+
+    fn cancel_booking(&self, _id: uuid::Uuid) -> Result<Vec<String>, BackendError> {
+        self.0
+            .calls_to_cancel_booking
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(Vec::new())
+    }
+
+    fn cancel_own_booking(&self, _confirmation_code: &str) -> Result<Vec<String>, BackendError> {
+        self.0
+            .calls_to_cancel_own_booking
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(Vec::new())
+    }
+
+    fn swap_bookings(
+        &self,
+        _first_id: uuid::Uuid,
+        _second_id: uuid::Uuid,
+    ) -> Result<(), BackendError> {
+        self.0.calls_to_swap_bookings.fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
+
+    fn confirm_booking(&self, _id: uuid::Uuid) -> Result<(), BackendError> {
+        self.0
+            .calls_to_confirm_booking
+            .fetch_add(1, Ordering::SeqCst);
         self.result()
     }
 
+    fn release_expired_holds(
+        &self,
+        _cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, BackendError> {
+        self.result()?;
+        Ok(0)
+    }
+
+    fn join_waitlist(&self, _id: uuid::Uuid, _client_name: String) -> Result<usize, BackendError> {
+        self.0.calls_to_join_waitlist.fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(1)
+    }
+
     fn add_timeslot(
         &self,
         _datetime: chrono::DateTime<chrono::Utc>,
         _notes: String,
-    ) -> Result<(), String> {
+        _capacity: i32,
+        _duration_minutes: i32,
+        _internal_notes: Option<String>,
+        _category: Option<String>,
+    ) -> Result<(), BackendError> {
         self.0.calls_to_add_timeslot.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 
-    fn remove_timeslot(&self, _id: uuid::Uuid) -> Result<(), String> {
+    fn add_timeslots_bulk(&self, drafts: Vec<TimeslotDraft>) -> Result<usize, BackendError> {
+        self.0
+            .calls_to_add_timeslots_bulk
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(drafts.len())
+    }
+
+    fn check_timeslots_bulk(&self, _drafts: &[TimeslotDraft]) -> Result<(), BackendError> {
+        self.0
+            .calls_to_check_timeslots_bulk
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
+
+    fn edit_timeslot(
+        &self,
+        _id: uuid::Uuid,
+        _datetime: chrono::DateTime<chrono::Utc>,
+        _notes: String,
+        _internal_notes: Option<String>,
+        _version: i32,
+    ) -> Result<(), BackendError> {
+        self.0.calls_to_edit_timeslot.fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
+
+    fn update_notes(&self, _id: uuid::Uuid, _notes: String) -> Result<(), BackendError> {
+        self.0.calls_to_update_notes.fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
+
+    fn reschedule(
+        &self,
+        _id: uuid::Uuid,
+        _datetime: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), BackendError> {
+        self.0.calls_to_reschedule.fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
+
+    fn remove_timeslot(&self, _id: uuid::Uuid) -> Result<(), BackendError> {
         self.0
             .calls_to_remove_timeslot
             .fetch_add(1, Ordering::SeqCst);
         self.result()
     }
 
-    fn remove_all_timeslot(&self) -> Result<(), String> {
+    fn archived_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.0
+            .calls_to_archived_timeslots
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(vec![])
+    }
+
+    fn remove_all_timeslot(&self) -> Result<(), BackendError> {
         self.0
             .calls_to_remove_all_timeslot
             .fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 
+    fn restore_last(&self) -> Result<(), BackendError> {
+        self.0.calls_to_restore_last.fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
+
     fn timeslot_stream(&self) -> tokio_stream::wrappers::WatchStream<Vec<Timeslot>> {
         WatchStream::new(self.0.timeslot_sender.subscribe())
     }
+
+    fn event_stream(&self) -> BroadcastStream<TimeslotEvent> {
+        BroadcastStream::new(self.0.event_sender.subscribe())
+    }
+
+    fn current_timeslots(&self) -> Result<Vec<Timeslot>, BackendError> {
+        self.0
+            .calls_to_current_timeslots
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(self.0.timeslot_sender.borrow().clone())
+    }
+
+    fn timeslots_between(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Timeslot>, BackendError> {
+        self.0
+            .calls_to_timeslots_between
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .filter(|timeslot| from <= timeslot.datetime && timeslot.datetime <= to)
+            .cloned()
+            .collect())
+    }
+
+    fn count_timeslots(&self, available_only: bool) -> Result<i64, BackendError> {
+        self.0
+            .calls_to_count_timeslots
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        let count = self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .filter(|timeslot| !available_only || timeslot.available)
+            .count();
+        Ok(count as i64)
+    }
+
+    fn count_bookings(&self, client_name: &str) -> Result<usize, BackendError> {
+        self.result()?;
+        Ok(self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .map(|timeslot| timeslot.bookings_of(client_name))
+            .sum())
+    }
+
+    fn remove_outdated_timeslots(&self) -> Result<usize, BackendError> {
+        self.result()?;
+        Ok(0)
+    }
+
+    fn search_bookings(&self, search: &str) -> Result<Vec<Timeslot>, BackendError> {
+        self.0
+            .calls_to_search_bookings
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .filter(|timeslot| timeslot.booker_name_contains(search))
+            .cloned()
+            .collect())
+    }
+
+    fn find_timeslot(&self, id: uuid::Uuid) -> Result<Option<Timeslot>, BackendError> {
+        self.0.calls_to_find_timeslot.fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .find(|timeslot| timeslot.id == id)
+            .cloned())
+    }
+
+    fn find_by_confirmation(&self, code: &str) -> Result<Option<Timeslot>, BackendError> {
+        self.0
+            .calls_to_find_by_confirmation
+            .fetch_add(1, Ordering::SeqCst);
+        self.result()?;
+        Ok(self
+            .0
+            .timeslot_sender
+            .borrow()
+            .iter()
+            .find(|timeslot| timeslot.booking(code).is_some())
+            .cloned())
+    }
+
+    fn health_check(&self) -> Result<(), BackendError> {
+        self.0.calls_to_health_check.fetch_add(1, Ordering::SeqCst);
+        self.result()
+    }
 }
 
 pub struct MockConfigurationInner {
     pub password: Mutex<String>,
+    pub password_hash: Mutex<Option<String>>,
+    pub admins: Mutex<Vec<Admin>>,
+    pub auth_header_name: Mutex<String>,
+    pub auth_lockout_attempts: Mutex<u32>,
+    pub auth_lockout_window_seconds: Mutex<u64>,
+    pub auth_lockout_seconds: Mutex<u64>,
+    pub base_path: Mutex<String>,
+    pub display_timezone: Mutex<String>,
+    pub empty_schedule_message: Mutex<Option<String>>,
+    pub business_hours: Mutex<Option<BusinessHours>>,
+    pub slot_granularity_minutes: Mutex<Option<i64>>,
+    pub max_future_days: Mutex<Option<i64>>,
+    pub allowed_categories: Mutex<Vec<String>>,
+    pub reject_unaligned_slots: AtomicBool,
+    pub max_bookings_per_client: Mutex<Option<u32>>,
+    pub min_booking_lead_minutes: Mutex<i64>,
+    pub notes_max_len: Mutex<usize>,
+    pub notes_pattern: Mutex<Option<String>>,
+    pub default_notes: Mutex<String>,
     pub frontend_path: Mutex<PathBuf>,
+    pub rate_limit_per_minute: Mutex<u32>,
+    pub webhook_url: Mutex<Option<String>>,
+    pub webhook_max_attempts: Mutex<u32>,
+    pub webhook_retry_delay_ms: Mutex<u64>,
+    pub webhook_timeout_ms: Mutex<u64>,
+    pub allowed_origins: Mutex<Vec<String>>,
+    pub sse_keep_alive_seconds: Mutex<u64>,
+    pub max_body_bytes: Mutex<usize>,
+    pub max_concurrent_requests: Mutex<usize>,
+    pub access_log_level: Mutex<Level>,
+    pub read_only: AtomicBool,
 }
 
 impl MockConfigurationInner {
     fn new() -> Self {
         Self {
             password: Mutex::default(),
+            password_hash: Mutex::default(),
+            admins: Mutex::default(),
+            auth_header_name: Mutex::new("x-admin-password".into()),
+            auth_lockout_attempts: Mutex::new(5),
+            auth_lockout_window_seconds: Mutex::new(300),
+            auth_lockout_seconds: Mutex::new(900),
+            base_path: Mutex::default(),
+            display_timezone: Mutex::new("UTC".into()),
+            empty_schedule_message: Mutex::default(),
+            business_hours: Mutex::default(),
+            slot_granularity_minutes: Mutex::default(),
+            max_future_days: Mutex::default(),
+            allowed_categories: Mutex::default(),
+            reject_unaligned_slots: AtomicBool::default(),
+            max_bookings_per_client: Mutex::default(),
+            min_booking_lead_minutes: Mutex::default(),
+            notes_max_len: Mutex::new(60),
+            notes_pattern: Mutex::default(),
+            default_notes: Mutex::default(),
             frontend_path: Mutex::new(PathBuf::new()),
+            rate_limit_per_minute: Mutex::default(),
+            webhook_url: Mutex::default(),
+            webhook_max_attempts: Mutex::new(3),
+            webhook_retry_delay_ms: Mutex::new(10),
+            webhook_timeout_ms: Mutex::new(1000),
+            allowed_origins: Mutex::default(),
+            sse_keep_alive_seconds: Mutex::new(15),
+            max_body_bytes: Mutex::new(1024 * 1024),
+            max_concurrent_requests: Mutex::new(512),
+            access_log_level: Mutex::new(Level::INFO),
+            read_only: AtomicBool::default(),
         }
     }
 }
@@ -119,24 +571,231 @@ impl MockConfiguration {
     }
 }
 
+impl Default for MockConfiguration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Configuration for MockConfiguration {
     fn website_title(&self) -> String {
         String::new()
     }
 
-    fn password(&self) -> String {
-        self.0.password.lock().unwrap().clone()
+    fn name_max_len(&self) -> usize {
+        20
+    }
+
+    fn name_pattern(&self) -> Option<String> {
+        None
+    }
+
+    fn notes_max_len(&self) -> usize {
+        *self.0.notes_max_len.lock().unwrap()
+    }
+
+    fn notes_pattern(&self) -> Option<String> {
+        self.0.notes_pattern.lock().unwrap().clone()
+    }
+
+    fn default_notes(&self) -> String {
+        self.0.default_notes.lock().unwrap().clone()
+    }
+
+    fn display_timezone(&self) -> String {
+        self.0.display_timezone.lock().unwrap().clone()
+    }
+
+    fn empty_schedule_message(&self) -> Option<String> {
+        self.0.empty_schedule_message.lock().unwrap().clone()
+    }
+
+    fn business_hours(&self) -> Option<BusinessHours> {
+        self.0.business_hours.lock().unwrap().clone()
+    }
+
+    fn slot_granularity_minutes(&self) -> Option<i64> {
+        *self.0.slot_granularity_minutes.lock().unwrap()
+    }
+
+    fn reject_unaligned_slots(&self) -> bool {
+        self.0.reject_unaligned_slots.load(Ordering::SeqCst)
+    }
+
+    fn password(&self) -> AdminPassword {
+        match self.0.password_hash.lock().unwrap().clone() {
+            Some(password_hash) => AdminPassword::Hash(password_hash.into()),
+            None => AdminPassword::Plaintext(self.0.password.lock().unwrap().clone().into()),
+        }
+    }
+
+    fn admins(&self) -> Vec<Admin> {
+        self.0.admins.lock().unwrap().clone()
+    }
+
+    fn set_password(&self, admin_name: &str, password: AdminPassword) {
+        if admin_name != DEFAULT_ADMIN_NAME {
+            let mut admins = self.0.admins.lock().unwrap();
+            if let Some(admin) = admins.iter_mut().find(|admin| admin.name == admin_name) {
+                admin.password = password;
+            }
+            return;
+        }
+        let (plaintext, hash) = match password {
+            AdminPassword::Plaintext(password) => (password.expose().to_string(), None),
+            AdminPassword::Hash(password_hash) => {
+                (String::new(), Some(password_hash.expose().to_string()))
+            }
+        };
+        *self.0.password.lock().unwrap() = plaintext;
+        *self.0.password_hash.lock().unwrap() = hash;
+    }
+
+    fn auth_header_name(&self) -> String {
+        self.0.auth_header_name.lock().unwrap().clone()
+    }
+
+    fn auth_lockout_attempts(&self) -> u32 {
+        *self.0.auth_lockout_attempts.lock().unwrap()
+    }
+
+    fn auth_lockout_window_seconds(&self) -> u64 {
+        *self.0.auth_lockout_window_seconds.lock().unwrap()
+    }
+
+    fn auth_lockout_seconds(&self) -> u64 {
+        *self.0.auth_lockout_seconds.lock().unwrap()
+    }
+
+    fn base_path(&self) -> String {
+        self.0.base_path.lock().unwrap().clone()
     }
 
     fn frontend_path(&self) -> PathBuf {
         self.0.frontend_path.lock().unwrap().clone()
     }
 
+    fn bind_address(&self) -> String {
+        "127.0.0.1".into()
+    }
+
     fn port(&self) -> String {
         "1234".into()
     }
 
+    fn tls_cert_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn tls_key_path(&self) -> Option<PathBuf> {
+        None
+    }
+
     fn database_url(&self) -> Option<String> {
         unimplemented!()
     }
+
+    fn database_pool_size(&self) -> Option<u32> {
+        unimplemented!()
+    }
+
+    fn skip_migrations(&self) -> bool {
+        unimplemented!()
+    }
+
+    fn allow_overlap(&self) -> bool {
+        unimplemented!()
+    }
+
+    fn booking_hold_minutes(&self) -> Option<u32> {
+        unimplemented!()
+    }
+
+    fn max_bookings_per_client(&self) -> Option<u32> {
+        *self.0.max_bookings_per_client.lock().unwrap()
+    }
+
+    fn min_booking_lead_minutes(&self) -> i64 {
+        *self.0.min_booking_lead_minutes.lock().unwrap()
+    }
+
+    fn max_timeslots(&self) -> Option<usize> {
+        unimplemented!()
+    }
+
+    fn hard_delete(&self) -> bool {
+        unimplemented!()
+    }
+
+    fn seed_examples(&self) -> u32 {
+        unimplemented!()
+    }
+
+    fn cleanup_retention_hours(&self) -> i64 {
+        24
+    }
+
+    fn cleanup_interval_seconds(&self) -> u64 {
+        60
+    }
+
+    fn past_grace_minutes(&self) -> i64 {
+        5
+    }
+
+    fn max_future_days(&self) -> Option<i64> {
+        *self.0.max_future_days.lock().unwrap()
+    }
+
+    fn allowed_categories(&self) -> Vec<String> {
+        self.0.allowed_categories.lock().unwrap().clone()
+    }
+
+    fn rate_limit_per_minute(&self) -> u32 {
+        *self.0.rate_limit_per_minute.lock().unwrap()
+    }
+
+    fn webhook_url(&self) -> Option<String> {
+        self.0.webhook_url.lock().unwrap().clone()
+    }
+
+    fn webhook_max_attempts(&self) -> u32 {
+        *self.0.webhook_max_attempts.lock().unwrap()
+    }
+
+    fn webhook_retry_delay_ms(&self) -> u64 {
+        *self.0.webhook_retry_delay_ms.lock().unwrap()
+    }
+
+    fn webhook_timeout_ms(&self) -> u64 {
+        *self.0.webhook_timeout_ms.lock().unwrap()
+    }
+
+    fn allowed_origins(&self) -> Vec<String> {
+        self.0.allowed_origins.lock().unwrap().clone()
+    }
+
+    fn sse_keep_alive_seconds(&self) -> u64 {
+        *self.0.sse_keep_alive_seconds.lock().unwrap()
+    }
+
+    fn max_body_bytes(&self) -> usize {
+        *self.0.max_body_bytes.lock().unwrap()
+    }
+
+    fn max_concurrent_requests(&self) -> usize {
+        *self.0.max_concurrent_requests.lock().unwrap()
+    }
+
+    fn access_log_level(&self) -> Level {
+        *self.0.access_log_level.lock().unwrap()
+    }
+
+    fn read_only(&self) -> bool {
+        self.0.read_only.load(Ordering::SeqCst)
+    }
+
+    fn set_read_only(&self, read_only: bool) {
+        self.0.read_only.store(read_only, Ordering::SeqCst);
+    }
 }
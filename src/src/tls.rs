@@ -0,0 +1,113 @@
+use axum::Router;
+use axum_server::{tls_rustls::RustlsConfig, Handle};
+use std::{future::Future, net::SocketAddr, path::Path};
+use tracing::{error, info};
+
+/// Reads the PEM encoded certificate chain and private key.
+pub async fn load_tls_config(cert_path: &Path, key_path: &Path) -> Result<RustlsConfig, String> {
+    RustlsConfig::from_pem_file(cert_path, key_path)
+        .await
+        .map_err(|err| {
+            error!(
+                ?err,
+                ?cert_path,
+                ?key_path,
+                "Failed to load TLS certificate"
+            );
+            format!(
+                "Failed to load TLS certificate \"{}\" and key \"{}\": {err}",
+                cert_path.display(),
+                key_path.display()
+            )
+        })
+}
+
+/// Serves the app over HTTPS. Completes after `shutdown` completed and all in-flight requests
+/// were answered.
+pub async fn serve_tls(
+    listener: std::net::TcpListener,
+    app: Router,
+    tls_config: RustlsConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), String> {
+    let handle = Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        shutdown.await;
+        shutdown_handle.graceful_shutdown(None);
+    });
+
+    info!("Serving over HTTPS");
+    axum_server::from_tcp_rustls(listener, tls_config)
+        .handle(handle)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .map_err(|err| {
+            error!(?err, "HTTPS server failed");
+            err.to_string()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        http::create_app,
+        shutdown::ShutdownSignal,
+        testutils::{MockConfiguration, MockTimeslotBackend},
+    };
+    use reqwest::{Certificate, Client, StatusCode};
+    use std::fs;
+
+    const CERT_PATH: &str = "testdata/tls/cert.pem";
+    const KEY_PATH: &str = "testdata/tls/key.pem";
+
+    #[tokio::test]
+    async fn test_tls_handshake() {
+        let tls_config = load_tls_config(Path::new(CERT_PATH), Path::new(KEY_PATH))
+            .await
+            .unwrap();
+        let app = create_app(
+            MockTimeslotBackend::new(),
+            MockConfiguration::new(),
+            ShutdownSignal::new(),
+        );
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shutdown = ShutdownSignal::new();
+        let server = tokio::spawn(serve_tls(listener, app, tls_config, {
+            let shutdown = shutdown.clone();
+            async move { shutdown.triggered().await }
+        }));
+
+        // The certificate is issued for "localhost", which must therefore be the requested host
+        let client = Client::builder()
+            .add_root_certificate(Certificate::from_pem(&fs::read(CERT_PATH).unwrap()).unwrap())
+            .resolve("localhost", addr)
+            .build()
+            .unwrap();
+        let response = client
+            .get(format!("https://localhost:{}/health", addr.port()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Plain HTTP is not served
+        assert!(Client::new()
+            .get(format!("http://{addr}/health"))
+            .send()
+            .await
+            .is_err());
+
+        shutdown.trigger();
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_certificate() {
+        let result =
+            load_tls_config(Path::new("testdata/tls/missing.pem"), Path::new(KEY_PATH)).await;
+        assert!(result.unwrap_err().contains("testdata/tls/missing.pem"));
+    }
+}
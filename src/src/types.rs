@@ -1,13 +1,666 @@
 use crate::schema::timeslots;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Jsonb;
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Queryable, AsChangeset)]
+const CONFIRMATION_CODE_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const CONFIRMATION_CODE_LENGTH: usize = 8;
+
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Queryable, QueryableByName, Insertable,
+)]
 pub struct Timeslot {
     pub id: Uuid,
     pub datetime: DateTime<Utc>,
     pub available: bool,
     pub booker_name: String,
     pub notes: String,
+    pub capacity: i32,
+    pub booked_count: i32,
+    pub duration_minutes: i32,
+    /// Removed timeslots are archived instead of deleted, unless hard delete is configured
+    #[serde(default)]
+    pub archived: bool,
+    /// Names of the clients waiting for the timeslot to become free again, in joining order
+    #[serde(default)]
+    pub waitlist: Vec<String>,
+    /// Incremented on every edit, so an edit based on an outdated state can be rejected
+    #[serde(default)]
+    pub version: i32,
+    /// Notes only visible to admins, e.g. internal reminders
+    #[serde(default)]
+    pub internal_notes: Option<String>,
+    /// Kind of service, e.g. "haircut", clients can filter the timeslots by
+    #[serde(default)]
+    pub category: Option<String>,
+    /// The booked places in booking order, only visible to admins. The names of their bookers are
+    /// additionally joined in `booker_name`.
+    #[serde(default)]
+    #[diesel(deserialize_as = BookingsJson, serialize_as = BookingsJson)]
+    pub bookings: Vec<Booking>,
+}
+
+/// A single booked place of a timeslot, with the details of its booker.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Booking {
+    pub booker_name: String,
+    /// Lets the booker look up and cancel the booking, different for every booking
+    pub confirmation_code: String,
+    #[serde(default)]
+    pub client_email: Option<String>,
+    #[serde(default)]
+    pub client_phone: Option<String>,
+    /// Notes of the booker, e.g. special requests, only visible to admins
+    #[serde(default)]
+    pub booker_notes: Option<String>,
+    /// Start of the hold of the booking. Bookings made before holds existed are never released
+    #[serde(default)]
+    pub booked_at: Option<DateTime<Utc>>,
+    /// Confirmed bookings are not released when their hold expires
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+impl Booking {
+    /// An unconfirmed booking with a new confirmation code, held from now on.
+    pub fn new(
+        booker_name: String,
+        client_email: Option<String>,
+        client_phone: Option<String>,
+        booker_notes: Option<String>,
+    ) -> Self {
+        Self {
+            booker_name,
+            confirmation_code: generate_confirmation_code(),
+            client_email,
+            client_phone,
+            booker_notes,
+            booked_at: Some(Utc::now()),
+            confirmed: false,
+        }
+    }
+
+    /// Whether the booking is unconfirmed since before `cutoff`.
+    pub fn hold_expired(&self, cutoff: DateTime<Utc>) -> bool {
+        !self.confirmed && self.booked_at.is_some_and(|booked_at| booked_at < cutoff)
+    }
+}
+
+/// The bookings of a timeslot as stored in the `jsonb` column of Postgres.
+#[derive(Debug, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Jsonb)]
+pub struct BookingsJson(Vec<Booking>);
+
+impl From<Vec<Booking>> for BookingsJson {
+    fn from(bookings: Vec<Booking>) -> Self {
+        Self(bookings)
+    }
+}
+
+impl From<BookingsJson> for Vec<Booking> {
+    fn from(bookings: BookingsJson) -> Self {
+        bookings.0
+    }
+}
+
+// The binary format of jsonb is a version byte followed by the JSON text
+const JSONB_VERSION: u8 = 1;
+
+impl FromSql<Jsonb, Pg> for BookingsJson {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        match value.as_bytes().split_first() {
+            Some((&JSONB_VERSION, json)) => Ok(Self(serde_json::from_slice(json)?)),
+            _ => Err("Unsupported jsonb format of the bookings".into()),
+        }
+    }
+}
+
+impl ToSql<Jsonb, Pg> for BookingsJson {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&[JSONB_VERSION])?;
+        serde_json::to_writer(out, &self.0)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl Timeslot {
+    pub fn end(&self) -> DateTime<Utc> {
+        self.datetime + Duration::minutes(self.duration_minutes.into())
+    }
+
+    /// Whether the timeslot shares any time with the window from start (inclusive) to end
+    /// (exclusive). Adjacent timeslots don't overlap.
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.datetime < end && start < self.end()
+    }
+
+    /// Books a single place. Whether a place is left has to be checked before.
+    pub fn add_booking(&mut self, booking: Booking) {
+        self.booker_name = if self.booker_name.is_empty() {
+            booking.booker_name.clone()
+        } else {
+            format!("{}, {}", self.booker_name, booking.booker_name)
+        };
+        self.bookings.push(booking);
+        self.booked_count += 1;
+        self.available = self.booked_count < self.capacity;
+    }
+
+    /// Releases all places. If clients are waiting, they get the released places in order and
+    /// the confirmation codes of the new bookings are returned.
+    pub fn cancel_booking(&mut self) -> Vec<String> {
+        self.available = true;
+        self.booked_count = 0;
+        self.booker_name = String::new();
+        self.bookings.clear();
+        self.promote_waitlisted()
+    }
+
+    /// Releases only the place of the booking with the confirmation code, the other bookers keep
+    /// theirs. If clients are waiting, the first of them gets the place instead and the
+    /// confirmation code of the new booking is returned. Nothing changes if no booking has the
+    /// code.
+    pub fn cancel_booking_of(&mut self, confirmation_code: &str) -> Vec<String> {
+        let Some(position) = self
+            .bookings
+            .iter()
+            .position(|booking| booking.confirmation_code == confirmation_code)
+        else {
+            return Vec::new();
+        };
+        self.bookings.remove(position);
+        self.booked_count = (self.booked_count - 1).max(0);
+        self.booker_name = self
+            .bookings
+            .iter()
+            .map(|booking| booking.booker_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.available = self.booked_count < self.capacity;
+        self.promote_waitlisted()
+    }
+
+    /// Books the places left for the waitlisted clients in order, until the timeslot is full or
+    /// nobody is waiting anymore, and returns the confirmation codes of the new bookings.
+    fn promote_waitlisted(&mut self) -> Vec<String> {
+        let mut confirmation_codes = Vec::new();
+        while self.available && !self.waitlist.is_empty() {
+            let booking = Booking::new(self.waitlist.remove(0), None, None, None);
+            confirmation_codes.push(booking.confirmation_code.clone());
+            self.add_booking(booking);
+        }
+        confirmation_codes
+    }
+
+    /// Exchanges the bookings with the other timeslot, e.g. if two clients trade their
+    /// appointments. The waitlists stay with their timeslots. Fails if the bookings don't fit
+    /// into the capacity of the other timeslot.
+    pub fn swap_bookings(&mut self, other: &mut Self) -> Result<(), String> {
+        if self.booked_count > other.capacity || other.booked_count > self.capacity {
+            return Err("The bookings don't fit into the other timeslot".into());
+        }
+        std::mem::swap(&mut self.booked_count, &mut other.booked_count);
+        std::mem::swap(&mut self.booker_name, &mut other.booker_name);
+        std::mem::swap(&mut self.bookings, &mut other.bookings);
+        self.available = self.booked_count < self.capacity;
+        other.available = other.booked_count < other.capacity;
+        Ok(())
+    }
+
+    /// Confirms the current bookings, so they are kept beyond their hold. Later bookings are held
+    /// again. Returns false if the timeslot is not booked.
+    pub fn confirm_bookings(&mut self) -> bool {
+        for booking in &mut self.bookings {
+            booking.confirmed = true;
+        }
+        !self.bookings.is_empty()
+    }
+
+    /// Whether a booking of the timeslot is unconfirmed since before `cutoff`.
+    pub fn hold_expired(&self, cutoff: DateTime<Utc>) -> bool {
+        !self.archived
+            && self
+                .bookings
+                .iter()
+                .any(|booking| booking.hold_expired(cutoff))
+    }
+
+    /// Releases the places of the bookings which are unconfirmed since before `cutoff` like
+    /// `cancel_booking_of`, the other bookers keep theirs. Returns how many were released.
+    pub fn release_expired_holds(&mut self, cutoff: DateTime<Utc>) -> usize {
+        let expired: Vec<String> = self
+            .bookings
+            .iter()
+            .filter(|booking| booking.hold_expired(cutoff))
+            .map(|booking| booking.confirmation_code.clone())
+            .collect();
+        for confirmation_code in &expired {
+            self.cancel_booking_of(confirmation_code);
+        }
+        expired.len()
+    }
+
+    /// Number of places booked under the client name. Names are compared case-insensitively and
+    /// without surrounding whitespace.
+    pub fn bookings_of(&self, client_name: &str) -> usize {
+        let client_name = client_name.trim().to_lowercase();
+        self.bookings
+            .iter()
+            .filter(|booking| booking.booker_name.trim().to_lowercase() == client_name)
+            .count()
+    }
+
+    /// Whether the name of one of the bookers contains the search term, ignoring case.
+    pub fn booker_name_contains(&self, search: &str) -> bool {
+        let search = search.trim().to_lowercase();
+        self.bookings
+            .iter()
+            .any(|booking| booking.booker_name.to_lowercase().contains(&search))
+    }
+
+    /// Number of places which can still be booked.
+    pub fn remaining(&self) -> i32 {
+        (self.capacity - self.booked_count).max(0)
+    }
+
+    /// Removes everything which must not be visible to non-admin clients.
+    pub fn into_public(mut self) -> Self {
+        self.bookings.clear();
+        self.internal_notes = None;
+        self
+    }
+
+    /// The public timeslot as seen by the booker with the confirmation code. Only their own
+    /// booking is kept, the other bookers of the timeslot stay private. `None` if no booking has
+    /// the code.
+    pub fn into_booking_of(self, confirmation_code: &str) -> Option<Self> {
+        let booking = self.booking(confirmation_code)?.clone();
+        let mut timeslot = self.into_public();
+        timeslot.booker_name = booking.booker_name.clone();
+        timeslot.bookings = vec![booking];
+        Some(timeslot)
+    }
+
+    /// The booking with the confirmation code.
+    pub fn booking(&self, confirmation_code: &str) -> Option<&Booking> {
+        self.bookings
+            .iter()
+            .find(|booking| booking.confirmation_code == confirmation_code)
+    }
+}
+
+/// A timeslot as sent to clients, extended by values derived from it. The stored timeslot stays
+/// unchanged.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimeslotResponse {
+    #[serde(flatten)]
+    pub timeslot: Timeslot,
+    /// Places left, e.g. to show "3 of 5 left"
+    pub remaining: i32,
+}
+
+impl From<Timeslot> for TimeslotResponse {
+    fn from(timeslot: Timeslot) -> Self {
+        Self {
+            remaining: timeslot.remaining(),
+            timeslot,
+        }
+    }
+}
+
+/// Everything needed to add a new timeslot, e.g. as part of a bulk add.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeslotDraft {
+    pub datetime: DateTime<Utc>,
+    pub notes: String,
+    pub capacity: i32,
+    pub duration_minutes: i32,
+    pub internal_notes: Option<String>,
+    pub category: Option<String>,
+}
+
+impl TimeslotDraft {
+    pub fn end(&self) -> DateTime<Utc> {
+        self.datetime + Duration::minutes(self.duration_minutes.into())
+    }
+
+    /// The timeslot which adding the draft creates, with a new id.
+    pub fn into_timeslot(self) -> Timeslot {
+        Timeslot {
+            id: Uuid::new_v4(),
+            datetime: self.datetime,
+            available: true,
+            booker_name: String::new(),
+            notes: self.notes,
+            capacity: self.capacity,
+            booked_count: 0,
+            duration_minutes: self.duration_minutes,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: self.internal_notes,
+            category: self.category,
+            bookings: vec![],
+        }
+    }
+}
+
+/// What happened to the timeslot of a `TimeslotEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeslotEventKind {
+    Booked,
+    Cancelled,
+    Added,
+    Removed,
+}
+
+impl TimeslotEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Booked => "booked",
+            Self::Cancelled => "cancelled",
+            Self::Added => "added",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+/// Change of a single timeslot, e.g. for a live feed of the bookings. The timeslot is the state
+/// after the change, or the last known state if it was removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeslotEvent {
+    pub kind: TimeslotEventKind,
+    pub timeslot: Timeslot,
+}
+
+/// Generates a random 8 character base32 (RFC 4648) code, e.g. "K7Q2MZ4D".
+pub fn generate_confirmation_code() -> String {
+    let random_bits = u128::from_be_bytes(*Uuid::new_v4().as_bytes());
+    (0..CONFIRMATION_CODE_LENGTH)
+        .map(|index| {
+            CONFIRMATION_CODE_ALPHABET[((random_bits >> (index * 5)) & 0x1f) as usize] as char
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn timeslot(capacity: i32) -> Timeslot {
+        TimeslotDraft {
+            datetime: Utc::now(),
+            notes: String::new(),
+            capacity,
+            duration_minutes: 30,
+            internal_notes: None,
+            category: None,
+        }
+        .into_timeslot()
+    }
+
+    #[test]
+    fn test_generate_confirmation_code() {
+        let code = generate_confirmation_code();
+        assert_eq!(code.len(), CONFIRMATION_CODE_LENGTH);
+        assert!(code
+            .bytes()
+            .all(|character| CONFIRMATION_CODE_ALPHABET.contains(&character)));
+        assert_ne!(code, generate_confirmation_code());
+    }
+
+    #[test_case::test_case("Stefan", 2 ; "same name")]
+    #[test_case::test_case(" stefan ", 2 ; "case and whitespace")]
+    #[test_case::test_case("Peter", 1 ; "other name")]
+    #[test_case::test_case("Anna", 0 ; "part of a name")]
+    #[test_case::test_case("smith, anna", 1 ; "name with comma")]
+    fn test_bookings_of(client_name: &str, expected: usize) {
+        let mut timeslot = timeslot(4);
+        assert_eq!(timeslot.bookings_of(client_name), 0);
+
+        for booker_name in ["Stefan", "Peter", "STEFAN ", "Smith, Anna"] {
+            timeslot.add_booking(Booking::new(booker_name.into(), None, None, None));
+        }
+        assert_eq!(timeslot.bookings_of(client_name), expected);
+    }
+
+    #[test_case::test_case(5, 0, 5 ; "not booked")]
+    #[test_case::test_case(5, 2, 3 ; "partially booked")]
+    #[test_case::test_case(1, 1, 0 ; "fully booked")]
+    fn test_timeslot_response_remaining(capacity: i32, booked_count: i32, expected: i32) {
+        let mut timeslot = timeslot(capacity);
+        timeslot.booked_count = booked_count;
+
+        let json = serde_json::to_value(TimeslotResponse::from(timeslot.clone())).unwrap();
+        assert_eq!(json["remaining"], expected);
+        assert_eq!(json["capacity"], capacity);
+        assert_eq!(json["id"], timeslot.id.to_string());
+    }
+
+    #[test]
+    fn test_cancel_booking_promotes_waitlist() {
+        let mut timeslot = timeslot(2);
+        timeslot.add_booking(Booking::new(
+            "Stefan".into(),
+            Some("stefan@example.com".into()),
+            None,
+            None,
+        ));
+        timeslot.add_booking(Booking::new("Peter".into(), None, None, None));
+        assert_eq!(timeslot.booker_name, "Stefan, Peter");
+        assert!(!timeslot.available);
+        timeslot.waitlist = vec!["Anna".into()];
+
+        let confirmation_codes = timeslot.cancel_booking();
+        assert_eq!(timeslot.bookings.len(), 1);
+        assert_eq!(
+            confirmation_codes,
+            vec![timeslot.bookings[0].confirmation_code.clone()]
+        );
+        assert_eq!(timeslot.bookings[0].booker_name, "Anna");
+        assert_eq!(timeslot.bookings[0].client_email, None);
+        assert_eq!(timeslot.booker_name, "Anna");
+        assert_eq!(timeslot.booked_count, 1);
+        assert!(timeslot.available);
+        assert!(timeslot.waitlist.is_empty());
+
+        assert!(timeslot.cancel_booking().is_empty());
+        assert_eq!(timeslot.booker_name, "");
+        assert_eq!(timeslot.booked_count, 0);
+        assert!(timeslot.bookings.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_booking_promotes_waitlist_until_full() {
+        let mut timeslot = timeslot(3);
+        for client_name in ["Stefan", "Peter", "Maria"] {
+            timeslot.add_booking(Booking::new(client_name.into(), None, None, None));
+        }
+        timeslot.waitlist = vec!["Anna".into(), "Paul".into(), "Lisa".into(), "Tom".into()];
+
+        // Every released place goes to the next waitlisted client
+        let confirmation_codes = timeslot.cancel_booking();
+        let codes_of_bookings: Vec<String> = timeslot
+            .bookings
+            .iter()
+            .map(|booking| booking.confirmation_code.clone())
+            .collect();
+        assert_eq!(confirmation_codes, codes_of_bookings);
+        assert_eq!(timeslot.booker_name, "Anna, Paul, Lisa");
+        assert_eq!(timeslot.booked_count, 3);
+        assert!(!timeslot.available);
+        assert_eq!(timeslot.waitlist, vec![String::from("Tom")]);
+    }
+
+    #[test]
+    fn test_cancel_booking_of() {
+        let mut timeslot = timeslot(2);
+        let stefan = Booking::new(
+            "Stefan".into(),
+            Some("stefan@example.com".into()),
+            Some("+43 660 1234567".into()),
+            None,
+        );
+        let peter = Booking::new("Peter".into(), Some("peter@example.com".into()), None, None);
+        timeslot.add_booking(stefan.clone());
+        timeslot.add_booking(peter.clone());
+
+        assert!(timeslot.cancel_booking_of("UNKNOWN2").is_empty());
+        assert_eq!(timeslot.booked_count, 2);
+
+        assert!(timeslot
+            .cancel_booking_of(&stefan.confirmation_code)
+            .is_empty());
+        assert_eq!(timeslot.bookings, vec![peter.clone()]);
+        assert_eq!(timeslot.booker_name, "Peter");
+        assert_eq!(timeslot.booked_count, 1);
+        assert!(timeslot.available);
+
+        // The released place goes to the first waitlisted client
+        timeslot.add_booking(stefan.clone());
+        timeslot.waitlist = vec!["Anna".into()];
+        let confirmation_codes = timeslot.cancel_booking_of(&peter.confirmation_code);
+        assert_eq!(timeslot.booker_name, "Stefan, Anna");
+        assert_eq!(
+            confirmation_codes,
+            vec![timeslot.bookings[1].confirmation_code.clone()]
+        );
+        assert_eq!(timeslot.booked_count, 2);
+        assert!(!timeslot.available);
+        assert!(timeslot.waitlist.is_empty());
+    }
+
+    #[test]
+    fn test_into_booking_of() {
+        let mut timeslot = timeslot(2);
+        timeslot.internal_notes = Some("Pays in cash".into());
+        let stefan = Booking::new(
+            "Stefan".into(),
+            Some("stefan@example.com".into()),
+            Some("+43 660 1234567".into()),
+            Some("Window seat".into()),
+        );
+        timeslot.add_booking(stefan.clone());
+        timeslot.add_booking(Booking::new(
+            "Peter".into(),
+            Some("peter@example.com".into()),
+            None,
+            None,
+        ));
+
+        let own_booking = timeslot
+            .clone()
+            .into_booking_of(&stefan.confirmation_code)
+            .unwrap();
+        assert_eq!(own_booking.bookings, vec![stefan]);
+        assert_eq!(own_booking.booker_name, "Stefan");
+        assert_eq!(own_booking.booked_count, 2);
+        assert_eq!(own_booking.internal_notes, None);
+        assert_eq!(timeslot.into_booking_of("UNKNOWN2"), None);
+    }
+
+    #[test]
+    fn test_swap_bookings() {
+        let stefan = Booking::new("Stefan".into(), None, None, None);
+        let mut group = timeslot(3);
+        group.add_booking(stefan.clone());
+        group.add_booking(Booking::new("Peter".into(), None, None, None));
+        group.waitlist = vec!["Anna".into()];
+        let mut single = timeslot(1);
+
+        // Two bookings don't fit into a single place
+        assert!(group.swap_bookings(&mut single).is_err());
+        assert_eq!(group.booker_name, "Stefan, Peter");
+
+        let anna = Booking {
+            confirmed: true,
+            ..Booking::new("Anna".into(), None, None, None)
+        };
+        single.add_booking(anna.clone());
+        let mut group = timeslot(3);
+        group.add_booking(stefan.clone());
+        group.waitlist = vec!["Anna".into()];
+        group.swap_bookings(&mut single).unwrap();
+
+        assert_eq!(group.booker_name, "Anna");
+        assert_eq!(group.bookings, vec![anna]);
+        assert!(group.available);
+        assert_eq!(group.waitlist, vec![String::from("Anna")]);
+        assert_eq!(single.booker_name, "Stefan");
+        assert_eq!(single.bookings, vec![stefan]);
+        assert!(!single.available);
+    }
+
+    #[test_case::test_case (Some(-20), false, true ; "unconfirmed")]
+    #[test_case::test_case (Some(-20), true, false ; "confirmed")]
+    #[test_case::test_case (Some(-5), false, false ; "within hold")]
+    #[test_case::test_case (None, false, false ; "booked before holds")]
+    fn test_hold_expired(booked_minutes_ago: Option<i64>, confirmed: bool, expired: bool) {
+        let booking = Booking {
+            booked_at: booked_minutes_ago.map(|minutes| Utc::now() + Duration::minutes(minutes)),
+            confirmed,
+            ..Booking::new("Stefan".into(), None, None, None)
+        };
+
+        assert_eq!(
+            booking.hold_expired(Utc::now() - Duration::minutes(10)),
+            expired
+        );
+    }
+
+    #[test]
+    fn test_release_expired_holds() {
+        let mut timeslot = Timeslot {
+            datetime: Utc::now() + Duration::hours(1),
+            ..timeslot(3)
+        };
+        let held_since = |minutes| Some(Utc::now() - Duration::minutes(minutes));
+        let confirmed = Booking {
+            booked_at: held_since(20),
+            confirmed: true,
+            ..Booking::new("Stefan".into(), None, None, None)
+        };
+        let within_hold = Booking {
+            booked_at: held_since(5),
+            ..Booking::new("Peter".into(), None, None, None)
+        };
+        timeslot.add_booking(confirmed.clone());
+        timeslot.add_booking(Booking {
+            booked_at: held_since(20),
+            ..Booking::new("Anna".into(), None, None, None)
+        });
+        timeslot.add_booking(within_hold.clone());
+        let cutoff = Utc::now() - Duration::minutes(10);
+        assert!(timeslot.hold_expired(cutoff));
+
+        // Only the expired booking is released, the confirmed and the later one stay
+        assert_eq!(timeslot.release_expired_holds(cutoff), 1);
+        assert_eq!(timeslot.bookings, vec![confirmed, within_hold]);
+        assert_eq!(timeslot.booker_name, "Stefan, Peter");
+        assert_eq!(timeslot.booked_count, 2);
+        assert!(timeslot.available);
+        assert!(!timeslot.hold_expired(cutoff));
+        assert_eq!(timeslot.release_expired_holds(cutoff), 0);
+    }
+
+    #[test_case::test_case (0, 30, true ; "same window")]
+    #[test_case::test_case (15, 30, true ; "starts within")]
+    #[test_case::test_case (-15, 30, true ; "ends within")]
+    #[test_case::test_case (-30, 120, true ; "encloses")]
+    #[test_case::test_case (30, 30, false ; "adjacent after")]
+    #[test_case::test_case (-30, 30, false ; "adjacent before")]
+    #[test_case::test_case (60, 30, false ; "later")]
+    fn test_overlaps(start_offset_minutes: i64, duration_minutes: i64, overlapping: bool) {
+        let timeslot = timeslot(1);
+        let start = timeslot.datetime + Duration::minutes(start_offset_minutes);
+        let end = start + Duration::minutes(duration_minutes);
+
+        assert_eq!(timeslot.overlaps(start, end), overlapping);
+    }
 }
@@ -0,0 +1,193 @@
+use crate::types::Timeslot;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Notifies an external service about bookings by posting the booked timeslot as JSON.
+#[derive(Clone)]
+pub struct Webhook {
+    client: reqwest::Client,
+    url: String,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl Webhook {
+    pub fn new(url: String, max_attempts: u32, retry_delay: Duration, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Webhook client can't be built");
+        Self {
+            client,
+            url,
+            max_attempts,
+            retry_delay,
+        }
+    }
+
+    /// Sends the notification in the background. Failed deliveries are retried with exponential
+    /// backoff, notifications which still fail are only logged.
+    pub fn notify(&self, timeslot: Timeslot) {
+        let webhook = self.clone();
+        tokio::spawn(async move {
+            let mut retry_delay = webhook.retry_delay;
+            for attempt in 1..=webhook.max_attempts {
+                match webhook.deliver(&timeslot).await {
+                    Ok(()) => {
+                        debug!(url = %webhook.url, attempt, "Webhook delivered");
+                        return;
+                    }
+                    Err(err) if attempt < webhook.max_attempts => {
+                        warn!(url = %webhook.url, attempt, %err, ?retry_delay, "Failed to deliver webhook. Retrying");
+                        tokio::time::sleep(retry_delay).await;
+                        retry_delay = retry_delay.saturating_mul(2);
+                    }
+                    Err(err) => {
+                        error!(url = %webhook.url, attempt, %err, payload = ?timeslot, "Failed to deliver webhook. Giving up");
+                    }
+                }
+            }
+        });
+    }
+
+    async fn deliver(&self, timeslot: &Timeslot) -> Result<(), String> {
+        match self.client.post(&self.url).json(timeslot).send().await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(format!("Webhook rejected with {}", response.status())),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Booking;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use chrono::Utc;
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+    use tokio::{
+        net::TcpListener,
+        sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        time::timeout,
+    };
+    use uuid::Uuid;
+
+    type ReceiverState = (Arc<AtomicU32>, u32, UnboundedSender<Timeslot>);
+
+    /// Rejects the first `failures` requests with 503 and forwards the payload of the later ones
+    async fn init_flaky_receiver(
+        failures: u32,
+    ) -> (String, Arc<AtomicU32>, UnboundedReceiver<Timeslot>) {
+        let (sender, receiver) = unbounded_channel();
+        let requests = Arc::new(AtomicU32::new(0));
+        let app = Router::new()
+            .route(
+                "/webhook",
+                post(
+                    |State((requests, failures, sender)): State<ReceiverState>,
+                     Json(timeslot): Json<Timeslot>| async move {
+                        if requests.fetch_add(1, Ordering::SeqCst) < failures {
+                            return StatusCode::SERVICE_UNAVAILABLE;
+                        }
+                        sender.send(timeslot).unwrap();
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state((requests.clone(), failures, sender));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{addr}/webhook"), requests, receiver)
+    }
+
+    fn booked_timeslot() -> Timeslot {
+        Timeslot {
+            id: Uuid::new_v4(),
+            datetime: Utc::now(),
+            available: false,
+            booker_name: "Stefan".into(),
+            notes: "Workshop".into(),
+            capacity: 1,
+            booked_count: 1,
+            duration_minutes: 30,
+            archived: false,
+            waitlist: vec![],
+            version: 0,
+            internal_notes: None,
+            category: None,
+            bookings: vec![Booking {
+                booker_name: "Stefan".into(),
+                confirmation_code: "ABCD2345".into(),
+                client_email: None,
+                client_phone: None,
+                booker_notes: None,
+                booked_at: None,
+                confirmed: false,
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_delivered() {
+        let (url, requests, mut receiver) = init_flaky_receiver(2).await;
+        let webhook = Webhook::new(url, 3, Duration::from_millis(10), Duration::from_secs(1));
+
+        let timeslot = booked_timeslot();
+        webhook.notify(timeslot.clone());
+
+        let delivered = timeout(Duration::from_secs(1), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(delivered, timeslot);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_give_up_after_max_attempts() {
+        let (url, requests, mut receiver) = init_flaky_receiver(u32::MAX).await;
+        let webhook = Webhook::new(url, 3, Duration::from_millis(10), Duration::from_secs(1));
+
+        webhook.notify(booked_timeslot());
+
+        // The retries wait 10 and 20 ms
+        assert!(timeout(Duration::from_millis(200), receiver.recv())
+            .await
+            .is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_hanging_receiver_times_out() {
+        let requests = Arc::new(AtomicU32::new(0));
+        let app = Router::new()
+            .route(
+                "/webhook",
+                post(|State(requests): State<Arc<AtomicU32>>| async move {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    std::future::pending::<StatusCode>().await
+                }),
+            )
+            .with_state(requests.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        let webhook = Webhook::new(
+            format!("http://{addr}/webhook"),
+            2,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+
+        webhook.notify(booked_timeslot());
+
+        // Without the timeout the first attempt would never finish
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}